@@ -0,0 +1,704 @@
+mod show;
+mod signing;
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use spacemoney::constants::MAX_TIERS;
+use spacemoney::states::TokenType;
+use spacemoney::states::{AccrualMode, PenaltyCurve, PenaltyStep, PostLock, Rounding};
+use spacemoney_client::instructions::UsdtAccounts;
+use spacemoney_client::{
+    fetch_platform_state, fetch_withdrawal_queue, instructions, pda, BonusTree,
+};
+
+use crate::signing::{process_transaction, TransactionSigners};
+
+#[derive(Parser)]
+#[command(name = "spacemoney-cli", version, about = "SpaceMoney admin tool")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+pub struct SigningArgs {
+    /// Admin keypair file, or `-` to read the keypair JSON from stdin
+    #[arg(long, short = 'k')]
+    keypair: Option<String>,
+
+    /// Admin pubkey when the admin signs elsewhere (with --sign-only or --signer)
+    #[arg(long)]
+    authority: Option<Pubkey>,
+
+    /// Offline signature as PUBKEY=SIGNATURE, may be repeated
+    #[arg(long = "signer", value_name = "PUBKEY=SIGNATURE")]
+    signers: Vec<String>,
+
+    /// Recent blockhash to sign with instead of fetching one
+    #[arg(long)]
+    blockhash: Option<String>,
+
+    /// Sign without sending and print the signatures
+    #[arg(long)]
+    sign_only: bool,
+
+    /// Print the base64-encoded transaction without sending it
+    #[arg(long)]
+    dump_transaction: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum TokenArg {
+    Sol,
+    Usdt,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum AccrualArg {
+    Daily,
+    PerSecond,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum RoundingArg {
+    Down,
+    Nearest,
+    Up,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PostLockArg {
+    None,
+    AutoRoll,
+    Flexible,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PenaltyCurveArg {
+    Flat,
+    Linear,
+    Step,
+}
+
+// FROM_BPS:RATE_BPS, e.g. 5000:2500 for 25% from half way through the lock
+fn parse_penalty_step(value: &str) -> Result<PenaltyStep, String> {
+    let (from_bps, rate_bps) = value
+        .split_once(':')
+        .ok_or_else(|| "expected FROM_BPS:RATE_BPS".to_string())?;
+    Ok(PenaltyStep {
+        from_bps: from_bps.parse().map_err(|err| format!("{err}"))?,
+        rate_bps: rate_bps.parse().map_err(|err| format!("{err}"))?,
+    })
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initialize the platform and tier config
+    Initialize {
+        #[arg(long)]
+        usdt_mint: Pubkey,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Pause deposits, withdrawals and claims
+    Pause {
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Resume a paused program
+    Resume {
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Update a tier's minimum stake, multiplier, lock period and capacity
+    UpdateTier {
+        /// Position in the tier list; the defaults are 0 = Boot, 1 = Symbiotic, 2 = Space
+        #[arg(long)]
+        tier: u8,
+        #[arg(long)]
+        min_stake: u64,
+        #[arg(long)]
+        multiplier: u64,
+        #[arg(long)]
+        lock_seconds: i64,
+        /// Maximum active stakes, 0 for no cap
+        #[arg(long, default_value_t = 0)]
+        max_stakes: u64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Append a tier to the tier list
+    AddTier {
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        min_stake: u64,
+        #[arg(long)]
+        multiplier: u64,
+        #[arg(long)]
+        lock_seconds: i64,
+        /// Maximum active stakes, 0 for no cap
+        #[arg(long, default_value_t = 0)]
+        max_stakes: u64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Close a tier to new deposits
+    DisableTier {
+        tier: u8,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Set a tier's stake caps and deposit window; 0 leaves each one open
+    SetTierLimits {
+        #[arg(long)]
+        tier: u8,
+        /// Cap on the tier's total principal, per token in its base units
+        #[arg(long, default_value_t = 0)]
+        max_total_stake: u64,
+        /// Cap on each user's principal in the tier, per token in its base units
+        #[arg(long, default_value_t = 0)]
+        max_user_stake: u64,
+        /// Unix timestamp deposits open at
+        #[arg(long, default_value_t = 0)]
+        open_at: i64,
+        /// Unix timestamp deposits close at
+        #[arg(long, default_value_t = 0)]
+        close_at: i64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Choose how a tier's rewards accrue
+    SetTierAccrual {
+        #[arg(long)]
+        tier: u8,
+        #[arg(long, value_enum)]
+        mode: AccrualArg,
+        /// Rounding for per-second accrual
+        #[arg(long, value_enum, default_value_t = RoundingArg::Down)]
+        rounding: RoundingArg,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Choose what the tier's new stakes do after their lock
+    SetTierPostLock {
+        #[arg(long)]
+        tier: u8,
+        #[arg(long, value_enum)]
+        post_lock: PostLockArg,
+        /// Share of the locked rate flexible stakes keep earning, in basis points
+        #[arg(long, default_value_t = 0)]
+        rate_bps: u16,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Make the tier's stakes wait out a cooldown before being paid out
+    SetTierUnbonding {
+        #[arg(long)]
+        tier: u8,
+        /// Seconds between requesting and completing an unstake, 0 to withdraw directly
+        #[arg(long)]
+        seconds: i64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Set the tier's early-exit penalty curve
+    SetTierPenalty {
+        #[arg(long)]
+        tier: u8,
+        /// flat is the default 20%; linear decays from --rate-bps to 0 at lock end
+        #[arg(long, value_enum)]
+        curve: PenaltyCurveArg,
+        /// Starting rate of a linear curve, in basis points
+        #[arg(long, default_value_t = 0)]
+        rate_bps: u16,
+        /// Step of a step curve as FROM_BPS:RATE_BPS of the lock elapsed, may be repeated
+        #[arg(long = "step", value_name = "FROM_BPS:RATE_BPS", value_parser = parse_penalty_step)]
+        steps: Vec<PenaltyStep>,
+        /// Charge the rate on principal too
+        #[arg(long)]
+        on_principal: bool,
+        /// Take back the rate on rewards already claimed
+        #[arg(long)]
+        clawback: bool,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Change the accepted USDT mint
+    SetUsdtMint {
+        usdt_mint: Pubkey,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Set the share of each deposit fee credited to the depositor's referrer
+    SetReferralShare {
+        /// Share in basis points, at most 10000
+        share_bps: u16,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Create the insurance fund and its USDT vault
+    InitInsuranceFund {
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Set the share of deposit fees and penalties paid into the insurance fund
+    SetInsuranceShare {
+        /// Share in basis points, at most 10000
+        share_bps: u16,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Create a campaign boosting deposits into some tiers for a while
+    CreateCampaign {
+        /// Unix time deposits start being boosted
+        #[arg(long)]
+        start: i64,
+        /// Unix time the campaign ends
+        #[arg(long)]
+        end: i64,
+        /// Eligible tier, may be repeated
+        #[arg(long = "tier", required = true)]
+        tiers: Vec<u8>,
+        /// Extra share of a boosted stake's first-lock rewards, in basis points
+        #[arg(long)]
+        boost_bps: u16,
+        /// Most the SOL boosts may add, in lamports; 0 leaves SOL out
+        #[arg(long, default_value_t = 0)]
+        budget_sol: u64,
+        /// Most the USDT boosts may add, in base units; 0 leaves USDT out
+        #[arg(long, default_value_t = 0)]
+        budget_usdt: u64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// End a campaign past its end time, or cancel it early as the admin
+    EndCampaign {
+        id: u64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Set aside treasury funds for a round of bonuses claimed with Merkle proofs
+    CreateDistributor {
+        /// File of `PUBKEY,AMOUNT` lines, one per claim; a claim's line number
+        /// from 0 is its index
+        #[arg(long)]
+        claims: std::path::PathBuf,
+        #[arg(long, value_enum, default_value = "sol")]
+        token: TokenArg,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Return a distributor's unclaimed funds to the treasury and close it
+    CloseDistributor {
+        id: u64,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Transfer treasury funds to any address
+    AdminTransfer {
+        #[arg(long)]
+        recipient: Pubkey,
+        /// Amount in lamports or USDT base units
+        #[arg(long)]
+        amount: u64,
+        #[arg(long, value_enum, default_value = "sol")]
+        token: TokenArg,
+        /// Platform USDT vault, required for USDT
+        #[arg(long, required_if_eq("token", "usdt"))]
+        platform_token_account: Option<Pubkey>,
+        /// Recipient USDT account, required for USDT
+        #[arg(long, required_if_eq("token", "usdt"))]
+        recipient_token_account: Option<Pubkey>,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Fill queued withdrawals in order while the vault has the liquidity
+    ProcessQueue {
+        #[arg(long, value_enum, default_value = "sol")]
+        token: TokenArg,
+        /// Platform USDT vault, required for USDT
+        #[arg(long, required_if_eq("token", "usdt"))]
+        platform_token_account: Option<Pubkey>,
+        /// Most tickets to fill in one transaction
+        #[arg(long, default_value_t = 10)]
+        max_tickets: usize,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Add reward liquidity to the vault without touching the treasury
+    FundRewards {
+        /// Amount in lamports or USDT base units
+        #[arg(long)]
+        amount: u64,
+        #[arg(long, value_enum, default_value = "sol")]
+        token: TokenArg,
+        /// USDT account to fund from, required for USDT
+        #[arg(long, required_if_eq("token", "usdt"))]
+        funder_token_account: Option<Pubkey>,
+        /// Platform USDT vault, required for USDT
+        #[arg(long, required_if_eq("token", "usdt"))]
+        platform_token_account: Option<Pubkey>,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Upgrade accounts on an older layout; defaults to the platform state and tier config
+    Migrate {
+        /// Accounts to migrate, e.g. user accounts
+        accounts: Vec<Pubkey>,
+        #[command(flatten)]
+        signing: SigningArgs,
+    },
+    /// Inspect program accounts
+    #[command(subcommand)]
+    Show(ShowCommand),
+}
+
+#[derive(Subcommand)]
+enum ShowCommand {
+    /// Platform state and tier configuration
+    Platform,
+    /// A user's stakes
+    User { user: Pubkey },
+    /// Tickets waiting in the withdrawal queue
+    Queue,
+    /// A referrer's referred volume and rewards
+    Referral { referrer: Pubkey },
+    /// A campaign's terms and spend
+    Campaign { id: u64 },
+    /// A bonus distributor's root and claims so far
+    Distributor { id: u64 },
+    /// The insurance fund's balances and coverage of staked funds
+    Insurance,
+}
+
+fn read_bonus_claims(path: &std::path::Path) -> Result<Vec<(Pubkey, u64)>> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (claimant, amount) = line
+                .split_once(',')
+                .with_context(|| format!("expected PUBKEY,AMOUNT, got {line:?}"))?;
+            Ok((claimant.trim().parse()?, amount.trim().parse()?))
+        })
+        .collect()
+}
+
+fn send(
+    rpc: &RpcClient,
+    signing: &SigningArgs,
+    build: impl FnOnce(&Pubkey) -> Result<solana_sdk::instruction::Instruction>,
+) -> Result<()> {
+    let signers = TransactionSigners::from_args(signing)?;
+    let instruction = build(&signers.authority)?;
+    process_transaction(rpc, signing, &signers, &[instruction])
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+
+    match cli.command {
+        Command::Initialize { usdt_mint, signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::initialize(admin, &usdt_mint))
+        }),
+        Command::Pause { signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::pause_program(admin))
+        }),
+        Command::Resume { signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::resume_program(admin))
+        }),
+        Command::UpdateTier {
+            tier,
+            min_stake,
+            multiplier,
+            lock_seconds,
+            max_stakes,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            Ok(instructions::update_tier_config(
+                admin,
+                tier,
+                min_stake,
+                multiplier,
+                lock_seconds,
+                max_stakes,
+            ))
+        }),
+        Command::AddTier {
+            name,
+            min_stake,
+            multiplier,
+            lock_seconds,
+            max_stakes,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            Ok(instructions::add_tier(
+                admin,
+                &name,
+                min_stake,
+                multiplier,
+                lock_seconds,
+                max_stakes,
+            ))
+        }),
+        Command::DisableTier { tier, signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::disable_tier(admin, tier))
+        }),
+        Command::SetTierLimits {
+            tier,
+            max_total_stake,
+            max_user_stake,
+            open_at,
+            close_at,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            Ok(instructions::set_tier_limits(
+                admin,
+                tier,
+                max_total_stake,
+                max_user_stake,
+                open_at,
+                close_at,
+            ))
+        }),
+        Command::SetTierAccrual {
+            tier,
+            mode,
+            rounding,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            let mode = match mode {
+                AccrualArg::Daily => AccrualMode::Daily,
+                AccrualArg::PerSecond => AccrualMode::PerSecond,
+            };
+            let rounding = match rounding {
+                RoundingArg::Down => Rounding::Down,
+                RoundingArg::Nearest => Rounding::Nearest,
+                RoundingArg::Up => Rounding::Up,
+            };
+            Ok(instructions::set_tier_accrual(admin, tier, mode, rounding))
+        }),
+        Command::SetTierPostLock {
+            tier,
+            post_lock,
+            rate_bps,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            let post_lock = match post_lock {
+                PostLockArg::None => PostLock::None,
+                PostLockArg::AutoRoll => PostLock::AutoRoll,
+                PostLockArg::Flexible => PostLock::Flexible,
+            };
+            Ok(instructions::set_tier_post_lock(
+                admin, tier, post_lock, rate_bps,
+            ))
+        }),
+        Command::SetTierUnbonding {
+            tier,
+            seconds,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            Ok(instructions::set_tier_unbonding(admin, tier, seconds))
+        }),
+        Command::SetTierPenalty {
+            tier,
+            curve,
+            rate_bps,
+            steps,
+            on_principal,
+            clawback,
+            signing,
+        } => send(&rpc, &signing, |admin| {
+            let curve = match curve {
+                PenaltyCurveArg::Flat => PenaltyCurve::Flat,
+                PenaltyCurveArg::Linear => PenaltyCurve::Linear,
+                PenaltyCurveArg::Step => PenaltyCurve::Step,
+            };
+            Ok(instructions::set_tier_penalty(
+                admin,
+                tier,
+                curve,
+                rate_bps,
+                &steps,
+                on_principal,
+                clawback,
+            ))
+        }),
+        Command::SetUsdtMint { usdt_mint, signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::set_usdt_mint(admin, &usdt_mint))
+        }),
+        Command::SetReferralShare { share_bps, signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::set_referral_share(admin, share_bps))
+        }),
+        Command::InitInsuranceFund { signing } => {
+            let usdt_mint = fetch_platform_state(&rpc)?.usdt_mint;
+            send(&rpc, &signing, |admin| {
+                Ok(instructions::initialize_insurance_fund(admin, &usdt_mint))
+            })
+        }
+        Command::SetInsuranceShare { share_bps, signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::set_insurance_share(admin, share_bps))
+        }),
+        Command::CreateCampaign {
+            start,
+            end,
+            tiers,
+            boost_bps,
+            budget_sol,
+            budget_usdt,
+            signing,
+        } => {
+            let tier_mask = tiers.iter().try_fold(0u16, |mask, &tier| {
+                anyhow::ensure!((tier as usize) < MAX_TIERS, "tier {tier} is out of range");
+                Ok(mask | 1 << tier)
+            })?;
+            let campaign_id = fetch_platform_state(&rpc)?.campaign_count;
+            send(&rpc, &signing, |admin| {
+                Ok(instructions::create_campaign(
+                    admin,
+                    campaign_id,
+                    start,
+                    end,
+                    tier_mask,
+                    boost_bps,
+                    budget_sol,
+                    budget_usdt,
+                ))
+            })
+        }
+        Command::EndCampaign { id, signing } => send(&rpc, &signing, |authority| {
+            Ok(instructions::end_campaign(authority, id))
+        }),
+        Command::CreateDistributor {
+            claims,
+            token,
+            signing,
+        } => {
+            let claims = read_bonus_claims(&claims)?;
+            anyhow::ensure!(!claims.is_empty(), "no claims");
+            let total_amount = claims.iter().try_fold(0u64, |total, (_, amount)| {
+                total.checked_add(*amount).context("claims overflow u64")
+            })?;
+            let tree = BonusTree::new(&claims);
+            let token_type = match token {
+                TokenArg::Sol => TokenType::SOL,
+                TokenArg::Usdt => TokenType::USDT,
+            };
+            let distributor_id = fetch_platform_state(&rpc)?.distributor_count;
+            println!(
+                "Distributor {distributor_id}, root {}",
+                show::hex(&tree.root())
+            );
+            send(&rpc, &signing, |admin| {
+                Ok(instructions::create_distributor(
+                    admin,
+                    distributor_id,
+                    tree.root(),
+                    token_type,
+                    total_amount,
+                    claims.len() as u32,
+                ))
+            })
+        }
+        Command::CloseDistributor { id, signing } => send(&rpc, &signing, |admin| {
+            Ok(instructions::close_distributor(admin, id))
+        }),
+        Command::AdminTransfer {
+            recipient,
+            amount,
+            token,
+            platform_token_account,
+            recipient_token_account,
+            signing,
+        } => send(&rpc, &signing, |admin| match token {
+            TokenArg::Sol => Ok(instructions::admin_transfer_sol(admin, &recipient, amount)),
+            TokenArg::Usdt => Ok(instructions::admin_transfer_usdt(
+                admin,
+                &recipient,
+                &platform_token_account.context("--platform-token-account is required")?,
+                &recipient_token_account.context("--recipient-token-account is required")?,
+                amount,
+            )),
+        }),
+        Command::ProcessQueue {
+            token,
+            platform_token_account,
+            max_tickets,
+            signing,
+        } => {
+            let token_type = match token {
+                TokenArg::Sol => TokenType::SOL,
+                TokenArg::Usdt => TokenType::USDT,
+            };
+            let queue = fetch_withdrawal_queue(&rpc)?;
+            let destinations: Vec<Pubkey> = queue
+                .tickets
+                .iter()
+                .filter(|ticket| ticket.token_type == token_type)
+                .take(max_tickets)
+                .map(|ticket| ticket.destination)
+                .collect();
+            anyhow::ensure!(!destinations.is_empty(), "no queued tickets");
+            send(&rpc, &signing, |_| {
+                Ok(instructions::process_queue(
+                    platform_token_account.as_ref(),
+                    &destinations,
+                ))
+            })
+        }
+        Command::FundRewards {
+            amount,
+            token,
+            funder_token_account,
+            platform_token_account,
+            signing,
+        } => send(&rpc, &signing, |funder| match token {
+            TokenArg::Sol => Ok(instructions::fund_rewards(funder, None, amount)),
+            TokenArg::Usdt => {
+                let usdt = UsdtAccounts {
+                    user_token_account: funder_token_account
+                        .context("--funder-token-account is required")?,
+                    platform_token_account: platform_token_account
+                        .context("--platform-token-account is required")?,
+                };
+                Ok(instructions::fund_rewards(funder, Some(&usdt), amount))
+            }
+        }),
+        Command::Migrate { accounts, signing } => {
+            let accounts = if accounts.is_empty() {
+                vec![pda::platform_state(), pda::tier_config()]
+            } else {
+                accounts
+            };
+            let signers = TransactionSigners::from_args(&signing)?;
+            let instructions: Vec<_> = accounts
+                .iter()
+                .map(|account| instructions::migrate_account(&signers.authority, account))
+                .collect();
+            process_transaction(&rpc, &signing, &signers, &instructions)
+        }
+        Command::Show(ShowCommand::Platform) => show::show_platform(&rpc),
+        Command::Show(ShowCommand::User { user }) => show::show_user(&rpc, &user),
+        Command::Show(ShowCommand::Queue) => show::show_queue(&rpc),
+        Command::Show(ShowCommand::Referral { referrer }) => show::show_referral(&rpc, &referrer),
+        Command::Show(ShowCommand::Campaign { id }) => show::show_campaign(&rpc, id),
+        Command::Show(ShowCommand::Distributor { id }) => show::show_distributor(&rpc, id),
+        Command::Show(ShowCommand::Insurance) => show::show_insurance(&rpc),
+    }
+}
@@ -0,0 +1,497 @@
+use anyhow::Result;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::native_token::lamports_to_sol;
+use solana_sdk::pubkey::Pubkey;
+use spacemoney::constants::MAX_TIERS;
+use spacemoney::states::{
+    AccrualMode, PenaltyCurve, PostLock, Rounding, TierConfig, TierInfo, TokenType, UserStake,
+};
+use spacemoney_client::{
+    fetch_campaign, fetch_distributor, fetch_insurance_fund, fetch_platform_state,
+    fetch_referral_account, fetch_tier_config, fetch_user_account, fetch_withdrawal_queue, pda,
+};
+
+// Falls back to the tier id when the tier config can't be read
+fn tier_name(tiers: Option<&TierConfig>, tier: u8) -> String {
+    tiers
+        .and_then(|tiers| tiers.tiers.get(tier as usize))
+        .map(|info| info.name.clone())
+        .unwrap_or_else(|| tier.to_string())
+}
+
+fn token_name(token_type: TokenType) -> &'static str {
+    match token_type {
+        TokenType::SOL => "SOL",
+        TokenType::USDT => "USDT",
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn accrual_name(tier: &TierInfo) -> &'static str {
+    match (tier.accrual_mode, tier.rounding) {
+        (AccrualMode::Daily, _) => "daily",
+        (AccrualMode::PerSecond, Rounding::Down) => "second/down",
+        (AccrualMode::PerSecond, Rounding::Nearest) => "second/nearest",
+        (AccrualMode::PerSecond, Rounding::Up) => "second/up",
+    }
+}
+
+fn post_lock_name(tier: &TierInfo) -> String {
+    match tier.post_lock {
+        PostLock::None => "stop".to_string(),
+        PostLock::AutoRoll => "roll".to_string(),
+        PostLock::Flexible => format!("flex {}bps", tier.post_lock_rate_bps),
+    }
+}
+
+fn penalty_name(tier: &TierInfo) -> String {
+    let curve = match tier.penalty_curve {
+        PenaltyCurve::Flat => "flat".to_string(),
+        PenaltyCurve::Linear => format!("linear {}bps", tier.penalty_rate_bps),
+        PenaltyCurve::Step => tier.penalty_steps[..tier.penalty_step_count as usize]
+            .iter()
+            .map(|step| format!("{}:{}", step.from_bps, step.rate_bps))
+            .collect::<Vec<_>>()
+            .join(","),
+    };
+    let mut extras = Vec::new();
+    if tier.penalty_on_principal {
+        extras.push("+principal");
+    }
+    if tier.penalty_clawback {
+        extras.push("+clawback");
+    }
+    format!("{curve}{}", extras.concat())
+}
+
+fn stake_status(stake: &UserStake) -> String {
+    if !stake.is_active {
+        "closed".to_string()
+    } else if stake.is_unbonding {
+        format!("unbonding until {}", stake.unbonding_until)
+    } else {
+        "active".to_string()
+    }
+}
+
+pub fn show_platform(rpc: &RpcClient) -> Result<()> {
+    let platform = fetch_platform_state(rpc)?;
+    let tiers = fetch_tier_config(rpc)?;
+
+    println!("Platform state:      {}", pda::platform_state());
+    println!("Admin:               {}", platform.admin);
+    println!("Paused:              {}", platform.is_paused);
+    println!("USDT mint:           {}", platform.usdt_mint);
+    println!(
+        "Total staked SOL:    {}",
+        lamports_to_sol(platform.total_staked_sol)
+    );
+    println!("Total staked USDT:   {}", platform.total_staked_usdt);
+    println!(
+        "Treasury SOL:        {}",
+        lamports_to_sol(platform.treasury_sol)
+    );
+    println!("Treasury USDT:       {}", platform.treasury_usdt);
+    println!("Stake transfers:     {}", platform.stake_transfers_enabled);
+    println!("spSOL mint:          {}", platform.spsol_mint);
+    println!(
+        "Liquid staked SOL:   {}",
+        lamports_to_sol(platform.liquid_staked_sol)
+    );
+    println!(
+        "Queued SOL:          {}",
+        lamports_to_sol(platform.queued_withdrawals_sol)
+    );
+    println!("Queued USDT:         {}", platform.queued_withdrawals_usdt);
+    println!("Referral share:      {}bps", platform.referral_share_bps);
+    println!("Insurance share:     {}bps", platform.insurance_share_bps);
+    println!("Campaigns:           {}", platform.campaign_count);
+    println!("Distributors:        {}", platform.distributor_count);
+    println!("Created at:          {}", platform.created_at);
+    println!("Layout version:      {}", platform.version);
+    println!();
+    println!(
+        "{:<4} {:<12} {:>16} {:>11} {:>12} {:>9} {:<7} {:<14} {:<14}",
+        "Id",
+        "Tier",
+        "Min stake",
+        "Multiplier",
+        "Lock seconds",
+        "Stakes",
+        "Enabled",
+        "Accrual",
+        "After lock"
+    );
+    for (id, tier) in tiers.tiers.iter().enumerate() {
+        let stakes = if tier.max_stakes == 0 {
+            tier.active_stakes.to_string()
+        } else {
+            format!("{}/{}", tier.active_stakes, tier.max_stakes)
+        };
+        println!(
+            "{:<4} {:<12} {:>16} {:>11} {:>12} {:>9} {:<7} {:<14} {:<14}",
+            id,
+            tier.name,
+            tier.min_stake,
+            tier.multiplier,
+            tier.lock_seconds,
+            stakes,
+            tier.enabled,
+            accrual_name(tier),
+            post_lock_name(tier),
+        );
+    }
+    println!();
+    println!(
+        "{:<4} {:>16} {:>16} {:>16} {:>16} {:>12} {:>12} {:>10} Penalty",
+        "Id",
+        "Staked SOL",
+        "Staked USDT",
+        "Max total",
+        "Max per user",
+        "Opens",
+        "Closes",
+        "Unbonding"
+    );
+    for (id, tier) in tiers.tiers.iter().enumerate() {
+        println!(
+            "{:<4} {:>16} {:>16} {:>16} {:>16} {:>12} {:>12} {:>10} {}",
+            id,
+            tier.total_staked_sol,
+            tier.total_staked_usdt,
+            limit(tier.max_total_stake),
+            limit(tier.max_user_stake),
+            limit(tier.open_at),
+            limit(tier.close_at),
+            limit(tier.unbonding_seconds),
+            penalty_name(tier),
+        );
+    }
+    Ok(())
+}
+
+// Zero means no cap or an open-ended window
+fn limit<T: PartialEq + Default + ToString>(value: T) -> String {
+    if value == T::default() {
+        "-".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn show_user(rpc: &RpcClient, user: &Pubkey) -> Result<()> {
+    let account = fetch_user_account(rpc, user)?;
+    let tiers = fetch_tier_config(rpc).ok();
+
+    println!("User account:        {}", pda::user_account(user));
+    println!("User:                {}", account.user);
+    println!(
+        "Total claimed SOL:   {}",
+        lamports_to_sol(account.total_claimed_sol)
+    );
+    println!("Total claimed USDT:  {}", account.total_claimed_usdt);
+    println!("Last claim time:     {}", account.last_claim_time);
+    println!(
+        "Loyalty:             level {} ({} stake-days, +{}bps on new stakes)",
+        account.loyalty_level(),
+        account.loyalty_stake_days,
+        account.loyalty_bonus_bps()
+    );
+    println!();
+    println!(
+        "{:<6} {:<10} {:<5} {:>16} {:>16} {:>12} {:>12} {:<26} Receipt",
+        "Index", "Tier", "Token", "Amount", "Claimed", "Deposited", "Lock until", "Status"
+    );
+    for (index, stake) in account.stakes.iter().enumerate() {
+        let receipt = if stake.has_receipt() {
+            stake.receipt_mint.to_string()
+        } else {
+            "-".to_string()
+        };
+        println!(
+            "{:<6} {:<10} {:<5} {:>16} {:>16} {:>12} {:>12} {:<26} {}",
+            index,
+            tier_name(tiers.as_ref(), stake.tier),
+            token_name(stake.token_type),
+            stake.amount,
+            stake.claimed_rewards,
+            stake.deposited_at,
+            stake.lock_until,
+            stake_status(stake),
+            receipt,
+        );
+    }
+    Ok(())
+}
+
+pub fn show_queue(rpc: &RpcClient) -> Result<()> {
+    let queue = fetch_withdrawal_queue(rpc)?;
+
+    println!("Withdrawal queue:    {}", pda::withdrawal_queue());
+    println!("Tickets:             {}", queue.tickets.len());
+    println!();
+    println!(
+        "{:<6} {:<44} {:>6} {:<5} {:>16} {:>16} {:>12}",
+        "Ticket", "User", "Stake", "Token", "Principal", "Rewards", "Queued at"
+    );
+    for ticket in &queue.tickets {
+        println!(
+            "{:<6} {:<44} {:>6} {:<5} {:>16} {:>16} {:>12}",
+            ticket.id,
+            ticket.user,
+            ticket.stake_index,
+            token_name(ticket.token_type),
+            ticket.principal,
+            ticket.rewards,
+            ticket.queued_at,
+        );
+    }
+    Ok(())
+}
+
+pub fn show_referral(rpc: &RpcClient, referrer: &Pubkey) -> Result<()> {
+    let referral = fetch_referral_account(rpc, referrer)?;
+
+    println!("Referral account:    {}", pda::referral_account(referrer));
+    println!("Referrer:            {}", referral.referrer);
+    println!("Referred deposits:   {}", referral.referred_deposits);
+    println!(
+        "Referred SOL:        {}",
+        lamports_to_sol(referral.referred_volume_sol)
+    );
+    println!("Referred USDT:       {}", referral.referred_volume_usdt);
+    println!(
+        "Pending SOL:         {}",
+        lamports_to_sol(referral.pending_sol)
+    );
+    println!("Pending USDT:        {}", referral.pending_usdt);
+    println!(
+        "Claimed SOL:         {}",
+        lamports_to_sol(referral.total_claimed_sol)
+    );
+    println!("Claimed USDT:        {}", referral.total_claimed_usdt);
+    println!("Registered at:       {}", referral.created_at);
+    Ok(())
+}
+
+pub fn show_campaign(rpc: &RpcClient, id: u64) -> Result<()> {
+    let campaign = fetch_campaign(rpc, id)?;
+    let tiers = fetch_tier_config(rpc).ok();
+    let eligible: Vec<String> = (0..MAX_TIERS as u8)
+        .filter(|tier| campaign.tier_mask & (1 << tier) != 0)
+        .map(|tier| tier_name(tiers.as_ref(), tier))
+        .collect();
+
+    println!("Campaign account:    {}", pda::campaign(id));
+    println!("Id:                  {}", campaign.id);
+    println!(
+        "Runs:                {} to {}",
+        campaign.start_time, campaign.end_time
+    );
+    println!("Tiers:               {}", eligible.join(", "));
+    println!("Boost:               {}bps", campaign.boost_bps);
+    println!(
+        "SOL spent:           {} of {}{}",
+        lamports_to_sol(campaign.spent_sol),
+        lamports_to_sol(campaign.budget_sol),
+        if campaign.sol_exhausted {
+            " (exhausted)"
+        } else {
+            ""
+        }
+    );
+    println!(
+        "USDT spent:          {} of {}{}",
+        campaign.spent_usdt,
+        campaign.budget_usdt,
+        if campaign.usdt_exhausted {
+            " (exhausted)"
+        } else {
+            ""
+        }
+    );
+    println!("Boosted deposits:    {}", campaign.boosted_deposits);
+    if campaign.ended_at == 0 {
+        println!("Ended at:            -");
+    } else {
+        println!("Ended at:            {}", campaign.ended_at);
+    }
+    Ok(())
+}
+
+pub fn show_distributor(rpc: &RpcClient, id: u64) -> Result<()> {
+    let distributor = fetch_distributor(rpc, id)?;
+    let amount = |amount: u64| match distributor.token_type {
+        TokenType::SOL => lamports_to_sol(amount).to_string(),
+        TokenType::USDT => amount.to_string(),
+    };
+
+    println!("Distributor account: {}", pda::distributor(id));
+    println!("Id:                  {}", distributor.id);
+    println!("Merkle root:         {}", hex(&distributor.merkle_root));
+    println!(
+        "Token:               {}",
+        token_name(distributor.token_type)
+    );
+    println!(
+        "Claimed:             {} of {}",
+        amount(distributor.claimed_amount),
+        amount(distributor.total_amount)
+    );
+    println!(
+        "Claims:              {} of {}",
+        distributor.claimed_count, distributor.max_claims
+    );
+    println!("Created at:          {}", distributor.created_at);
+    Ok(())
+}
+
+pub fn show_insurance(rpc: &RpcClient) -> Result<()> {
+    let fund = fetch_insurance_fund(rpc)?;
+    let platform = fetch_platform_state(rpc)?;
+
+    println!("Insurance fund:      {}", pda::insurance_fund());
+    println!("USDT vault:          {}", pda::insurance_vault());
+    println!("Share:               {}bps", platform.insurance_share_bps);
+    println!("Balance SOL:         {}", lamports_to_sol(fund.balance_sol));
+    println!("Balance USDT:        {}", fund.balance_usdt);
+    println!(
+        "Contributed SOL:     {}",
+        lamports_to_sol(fund.contributed_sol)
+    );
+    println!("Contributed USDT:    {}", fund.contributed_usdt);
+    println!(
+        "Backstopped SOL:     {}",
+        lamports_to_sol(fund.backstopped_sol)
+    );
+    println!("Backstopped USDT:    {}", fund.backstopped_usdt);
+    println!("Backstops:           {}", fund.backstop_count);
+    println!(
+        "Coverage SOL:        {}bps",
+        fund.coverage_bps(TokenType::SOL, platform.total_staked_sol)
+    );
+    println!(
+        "Coverage USDT:       {}bps",
+        fund.coverage_bps(TokenType::USDT, platform.total_staked_usdt)
+    );
+    println!("Created at:          {}", fund.created_at);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spacemoney::states::PenaltyStep;
+
+    fn tiers() -> TierConfig {
+        TierConfig {
+            tiers: vec![
+                TierInfo::from_lock_days("Boot", 1, 1, 30),
+                TierInfo::from_lock_days("Space", 25, 3, 180),
+            ],
+            bump: 255,
+            version: TierConfig::VERSION,
+            reserved: [0; TierConfig::RESERVED],
+        }
+    }
+
+    fn stake(is_active: bool, is_unbonding: bool) -> UserStake {
+        UserStake {
+            amount: 1,
+            tier: 0,
+            token_type: TokenType::SOL,
+            deposited_at: 0,
+            lock_until: 0,
+            claimed_rewards: 0,
+            is_active,
+            receipt_mint: Pubkey::default(),
+            post_lock: PostLock::None,
+            post_lock_rate_bps: 0,
+            is_unbonding,
+            unstake_requested_at: 0,
+            unbonding_until: 1_700_000_000,
+            loyalty_bonus_bps: 0,
+            campaign_boost_bps: 0,
+            terms_fixed: false,
+            multiplier: 0,
+            lock_days: 0,
+            accrual_mode: AccrualMode::Daily,
+            rounding: Rounding::Down,
+            reserved: [0; UserStake::RESERVED],
+        }
+    }
+
+    #[test]
+    fn names_tiers_and_tokens() {
+        let tiers = tiers();
+        assert_eq!(tier_name(Some(&tiers), 1), "Space");
+        // Unknown tiers, or no tier config at all, fall back to the id
+        assert_eq!(tier_name(Some(&tiers), 7), "7");
+        assert_eq!(tier_name(None, 0), "0");
+        assert_eq!(token_name(TokenType::SOL), "SOL");
+        assert_eq!(token_name(TokenType::USDT), "USDT");
+    }
+
+    #[test]
+    fn formats_hex_and_limits() {
+        assert_eq!(hex(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+        assert_eq!(hex(&[]), "");
+        assert_eq!(limit(0u64), "-");
+        assert_eq!(limit(100u64), "100");
+        assert_eq!(limit(-5i64), "-5");
+    }
+
+    #[test]
+    fn describes_tier_policies() {
+        let mut tier = TierInfo::from_lock_days("Boot", 1, 1, 30);
+        assert_eq!(accrual_name(&tier), "daily");
+        assert_eq!(post_lock_name(&tier), "stop");
+        assert_eq!(penalty_name(&tier), "flat");
+
+        tier.accrual_mode = AccrualMode::PerSecond;
+        tier.rounding = Rounding::Nearest;
+        tier.post_lock = PostLock::Flexible;
+        tier.post_lock_rate_bps = 2_500;
+        assert_eq!(accrual_name(&tier), "second/nearest");
+        assert_eq!(post_lock_name(&tier), "flex 2500bps");
+        tier.post_lock = PostLock::AutoRoll;
+        assert_eq!(post_lock_name(&tier), "roll");
+
+        tier.penalty_curve = PenaltyCurve::Linear;
+        tier.penalty_rate_bps = 3_000;
+        tier.penalty_clawback = true;
+        assert_eq!(penalty_name(&tier), "linear 3000bps+clawback");
+
+        // Only the schedule's used steps are listed
+        tier.penalty_curve = PenaltyCurve::Step;
+        tier.penalty_steps[0] = PenaltyStep {
+            from_bps: 0,
+            rate_bps: 5_000,
+        };
+        tier.penalty_steps[1] = PenaltyStep {
+            from_bps: 5_000,
+            rate_bps: 1_000,
+        };
+        tier.penalty_steps[2] = PenaltyStep {
+            from_bps: 9_000,
+            rate_bps: 500,
+        };
+        tier.penalty_step_count = 2;
+        tier.penalty_on_principal = true;
+        assert_eq!(penalty_name(&tier), "0:5000,5000:1000+principal+clawback");
+    }
+
+    #[test]
+    fn describes_stake_status() {
+        assert_eq!(stake_status(&stake(true, false)), "active");
+        assert_eq!(
+            stake_status(&stake(true, true)),
+            "unbonding until 1700000000"
+        );
+        // A closed stake reads as closed whatever its unbonding flag
+        assert_eq!(stake_status(&stake(false, true)), "closed");
+    }
+}
@@ -0,0 +1,268 @@
+use std::io;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair, read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::signer::presigner::Presigner;
+use solana_sdk::transaction::Transaction;
+
+use crate::SigningArgs;
+
+// `-` reads the JSON keypair bytes from stdin, anything else is a keypair file path
+pub fn load_keypair(source: &str) -> Result<Keypair> {
+    if source == "-" {
+        read_keypair(&mut io::stdin())
+            .map_err(|err| anyhow!("failed to read keypair from stdin: {err}"))
+    } else {
+        read_keypair_file(source).map_err(|err| anyhow!("failed to read keypair {source}: {err}"))
+    }
+}
+
+// Parses a `PUBKEY=SIGNATURE` pair produced by `--sign-only`
+pub fn parse_presigner(value: &str) -> Result<Presigner> {
+    let (pubkey, signature) = value
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected PUBKEY=SIGNATURE, got {value}"))?;
+    let pubkey = Pubkey::from_str(pubkey).context("invalid signer pubkey")?;
+    let signature = Signature::from_str(signature).context("invalid signature")?;
+    Ok(Presigner::new(&pubkey, &signature))
+}
+
+pub struct TransactionSigners {
+    pub authority: Pubkey,
+    keypair: Option<Keypair>,
+    presigners: Vec<Presigner>,
+}
+
+impl TransactionSigners {
+    pub fn from_args(args: &SigningArgs) -> Result<Self> {
+        let keypair = args.keypair.as_deref().map(load_keypair).transpose()?;
+        let presigners = args
+            .signers
+            .iter()
+            .map(|value| parse_presigner(value))
+            .collect::<Result<Vec<_>>>()?;
+        let authority = match (&keypair, args.authority) {
+            (_, Some(authority)) => authority,
+            (Some(keypair), None) => keypair.pubkey(),
+            (None, None) => bail!("either --keypair or --authority is required"),
+        };
+        Ok(Self {
+            authority,
+            keypair,
+            presigners,
+        })
+    }
+
+    fn signers(&self) -> Vec<&dyn Signer> {
+        let mut signers: Vec<&dyn Signer> = Vec::new();
+        if let Some(keypair) = &self.keypair {
+            signers.push(keypair);
+        }
+        for presigner in &self.presigners {
+            signers.push(presigner);
+        }
+        signers
+    }
+}
+
+// Signs with whatever signers are available, then sends, prints the
+// signatures (`--sign-only`) or prints the encoded transaction (`--dump-transaction`)
+pub fn process_transaction(
+    rpc: &RpcClient,
+    args: &SigningArgs,
+    signers: &TransactionSigners,
+    instructions: &[Instruction],
+) -> Result<()> {
+    let blockhash = match &args.blockhash {
+        Some(blockhash) => Hash::from_str(blockhash).context("invalid blockhash")?,
+        None if args.sign_only => bail!("--sign-only requires --blockhash"),
+        None => rpc.get_latest_blockhash()?,
+    };
+
+    let message = Message::new_with_blockhash(instructions, Some(&signers.authority), &blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    transaction
+        .try_partial_sign(&signers.signers(), blockhash)
+        .context("failed to sign transaction")?;
+
+    if args.sign_only {
+        println!("Blockhash: {blockhash}");
+        let signer_keys = &transaction.message.account_keys
+            [..transaction.message.header.num_required_signatures as usize];
+        for (pubkey, signature) in signer_keys.iter().zip(&transaction.signatures) {
+            if *signature == Signature::default() {
+                println!("Absent signer: {pubkey}");
+            } else {
+                println!("Signer: {pubkey}={signature}");
+            }
+        }
+    }
+
+    if args.dump_transaction {
+        println!("{}", BASE64.encode(bincode::serialize(&transaction)?));
+    }
+
+    if args.sign_only || args.dump_transaction {
+        return Ok(());
+    }
+
+    if !transaction.is_signed() {
+        bail!("transaction is missing signatures; pass --keypair or --signer");
+    }
+
+    let signature = rpc
+        .send_and_confirm_transaction(&transaction)
+        .map_err(|err| match spacemoney_client::error_from_rpc_error(&err) {
+            Some(program_error) => anyhow!("{err} ({program_error})"),
+            None => anyhow!(err),
+        })?;
+    println!("Signature: {signature}");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signature::write_keypair_file;
+    use solana_sdk::system_instruction;
+
+    fn args(
+        keypair: Option<String>,
+        authority: Option<Pubkey>,
+        signers: Vec<String>,
+    ) -> SigningArgs {
+        SigningArgs {
+            keypair,
+            authority,
+            signers,
+            blockhash: Some(Hash::new_unique().to_string()),
+            sign_only: false,
+            dump_transaction: false,
+        }
+    }
+
+    // Keypair files are named after their pubkey, so tests don't collide
+    fn keypair_file(keypair: &Keypair) -> String {
+        let path = std::env::temp_dir().join(format!("spacemoney-cli-{}.json", keypair.pubkey()));
+        write_keypair_file(keypair, &path).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn loads_keypair_files() {
+        let keypair = Keypair::new();
+        let path = keypair_file(&keypair);
+        assert_eq!(load_keypair(&path).unwrap().pubkey(), keypair.pubkey());
+
+        std::fs::write(&path, "not a keypair").unwrap();
+        assert!(load_keypair(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+        let err = load_keypair(&path).unwrap_err().to_string();
+        assert!(err.starts_with(&format!("failed to read keypair {path}")));
+    }
+
+    #[test]
+    fn parses_presigners_from_sign_only_output() {
+        let keypair = Keypair::new();
+        let message = b"offline";
+        let signature = keypair.sign_message(message);
+        let presigner = parse_presigner(&format!("{}={signature}", keypair.pubkey())).unwrap();
+        assert_eq!(presigner.pubkey(), keypair.pubkey());
+        assert_eq!(presigner.try_sign_message(message).unwrap(), signature);
+
+        // A presigner only vouches for the message it signed
+        assert!(presigner.try_sign_message(b"other").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_presigners() {
+        let keypair = Keypair::new();
+        let signature = keypair.sign_message(b"offline");
+
+        let err = parse_presigner(&keypair.pubkey().to_string()).unwrap_err();
+        assert!(err.to_string().starts_with("expected PUBKEY=SIGNATURE"));
+        let err = parse_presigner(&format!("not-a-pubkey={signature}")).unwrap_err();
+        assert_eq!(err.to_string(), "invalid signer pubkey");
+        let err = parse_presigner(&format!("{}=not-a-signature", keypair.pubkey())).unwrap_err();
+        assert_eq!(err.to_string(), "invalid signature");
+        assert!(parse_presigner("").is_err());
+    }
+
+    #[test]
+    fn authority_comes_from_the_flag_or_the_keypair() {
+        let keypair = Keypair::new();
+        let path = keypair_file(&keypair);
+        let authority = Pubkey::new_unique();
+
+        let signers =
+            TransactionSigners::from_args(&args(Some(path.clone()), None, vec![])).unwrap();
+        assert_eq!(signers.authority, keypair.pubkey());
+        assert_eq!(signers.signers().len(), 1);
+
+        // --authority wins, e.g. when the keypair only pays
+        let signers =
+            TransactionSigners::from_args(&args(Some(path.clone()), Some(authority), vec![]))
+                .unwrap();
+        assert_eq!(signers.authority, authority);
+        std::fs::remove_file(&path).unwrap();
+
+        let signature = keypair.sign_message(b"offline");
+        let presigned = vec![format!("{}={signature}", keypair.pubkey())];
+        let signers =
+            TransactionSigners::from_args(&args(None, Some(authority), presigned)).unwrap();
+        assert_eq!(signers.authority, authority);
+        assert_eq!(signers.signers().len(), 1);
+
+        let err = TransactionSigners::from_args(&args(None, None, vec![]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "either --keypair or --authority is required"
+        );
+        let malformed = vec!["no-separator".to_string()];
+        assert!(TransactionSigners::from_args(&args(None, Some(authority), malformed)).is_err());
+    }
+
+    #[test]
+    fn signs_offline_and_refuses_to_send_unsigned() {
+        let rpc = RpcClient::new_mock("succeeds".to_string());
+        let keypair = Keypair::new();
+        let path = keypair_file(&keypair);
+        let transfer_ixs = [system_instruction::transfer(
+            &keypair.pubkey(),
+            &Pubkey::new_unique(),
+            1,
+        )];
+
+        let mut sign_only = args(Some(path.clone()), None, vec![]);
+        sign_only.sign_only = true;
+        let signers = TransactionSigners::from_args(&sign_only).unwrap();
+        process_transaction(&rpc, &sign_only, &signers, &transfer_ixs).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        sign_only.blockhash = None;
+        let err = process_transaction(&rpc, &sign_only, &signers, &transfer_ixs).unwrap_err();
+        assert_eq!(err.to_string(), "--sign-only requires --blockhash");
+
+        sign_only.blockhash = Some("not-a-hash".to_string());
+        let err = process_transaction(&rpc, &sign_only, &signers, &transfer_ixs).unwrap_err();
+        assert_eq!(err.to_string(), "invalid blockhash");
+
+        // Without the authority's signature nothing is sent
+        let authority_only = args(None, Some(keypair.pubkey()), vec![]);
+        let signers = TransactionSigners::from_args(&authority_only).unwrap();
+        let err = process_transaction(&rpc, &authority_only, &signers, &transfer_ixs).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("transaction is missing signatures"));
+    }
+}
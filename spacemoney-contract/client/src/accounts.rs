@@ -0,0 +1,76 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use solana_rpc_client::rpc_client::RpcClient;
+use spacemoney::states::{
+    Campaign, ClaimedBitmap, Distributor, InsuranceFund, PlatformState, ReferralAccount,
+    TierConfig, UserAccount, ValidatorList, WithdrawalQueue,
+};
+
+use crate::errors::ClientError;
+use crate::pda;
+
+// Decodes raw account data, checking the Anchor discriminator
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+pub fn fetch_account<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<T, ClientError> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value
+        .ok_or(ClientError::AccountNotFound(*address))?;
+    if account.owner != spacemoney::ID {
+        return Err(ClientError::InvalidOwner(*address));
+    }
+    decode_account(&account.data)
+}
+
+pub fn fetch_platform_state(rpc: &RpcClient) -> Result<PlatformState, ClientError> {
+    fetch_account(rpc, &pda::platform_state())
+}
+
+pub fn fetch_user_account(rpc: &RpcClient, user: &Pubkey) -> Result<UserAccount, ClientError> {
+    fetch_account(rpc, &pda::user_account(user))
+}
+
+pub fn fetch_tier_config(rpc: &RpcClient) -> Result<TierConfig, ClientError> {
+    fetch_account(rpc, &pda::tier_config())
+}
+
+pub fn fetch_validator_list(rpc: &RpcClient) -> Result<ValidatorList, ClientError> {
+    fetch_account(rpc, &pda::validator_list())
+}
+
+pub fn fetch_withdrawal_queue(rpc: &RpcClient) -> Result<WithdrawalQueue, ClientError> {
+    fetch_account(rpc, &pda::withdrawal_queue())
+}
+
+pub fn fetch_referral_account(
+    rpc: &RpcClient,
+    referrer: &Pubkey,
+) -> Result<ReferralAccount, ClientError> {
+    fetch_account(rpc, &pda::referral_account(referrer))
+}
+
+pub fn fetch_campaign(rpc: &RpcClient, campaign_id: u64) -> Result<Campaign, ClientError> {
+    fetch_account(rpc, &pda::campaign(campaign_id))
+}
+
+pub fn fetch_distributor(rpc: &RpcClient, distributor_id: u64) -> Result<Distributor, ClientError> {
+    fetch_account(rpc, &pda::distributor(distributor_id))
+}
+
+pub fn fetch_insurance_fund(rpc: &RpcClient) -> Result<InsuranceFund, ClientError> {
+    fetch_account(rpc, &pda::insurance_fund())
+}
+
+pub fn fetch_claimed_bitmap(
+    rpc: &RpcClient,
+    distributor_id: u64,
+) -> Result<ClaimedBitmap, ClientError> {
+    fetch_account(rpc, &pda::claimed_bitmap(distributor_id))
+}
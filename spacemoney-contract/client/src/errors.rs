@@ -0,0 +1,129 @@
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::Pubkey;
+use solana_rpc_client_api::client_error::Error as RpcError;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use spacemoney::errors::SpaceMoneyError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("RPC error: {0}")]
+    Rpc(Box<RpcError>),
+
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+
+    #[error("Account {0} is not owned by the SpaceMoney program")]
+    InvalidOwner(Pubkey),
+
+    #[error("Failed to decode account or return data: {0}")]
+    Decode(String),
+
+    #[error("Simulation failed: {0}")]
+    Simulation(TransactionError),
+
+    #[error("The SpaceMoney program returned no data")]
+    MissingReturnData,
+}
+
+impl From<RpcError> for ClientError {
+    fn from(err: RpcError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
+
+// Every program error in declaration order, so index + ERROR_CODE_OFFSET is the code.
+// Append here whenever a variant is added to SpaceMoneyError.
+pub const ALL_ERRORS: &[SpaceMoneyError] = &[
+    SpaceMoneyError::ProgramPaused,
+    SpaceMoneyError::Unauthorized,
+    SpaceMoneyError::InvalidTier,
+    SpaceMoneyError::InsufficientStakeAmount,
+    SpaceMoneyError::StakeLocked,
+    SpaceMoneyError::InvalidStakeIndex,
+    SpaceMoneyError::StakeNotFound,
+    SpaceMoneyError::InsufficientTreasuryBalance,
+    SpaceMoneyError::InsufficientBalance,
+    SpaceMoneyError::NumericalOverflow,
+    SpaceMoneyError::InvalidUsdtMint,
+    SpaceMoneyError::TokenAccountOwnerMismatch,
+    SpaceMoneyError::InvalidTokenType,
+    SpaceMoneyError::CalculationError,
+    SpaceMoneyError::MaxRewardsClaimed,
+    SpaceMoneyError::NoRewardsAvailable,
+    SpaceMoneyError::InvalidSplitAmount,
+    SpaceMoneyError::IncompatibleStakes,
+    SpaceMoneyError::RewardPrecisionLoss,
+    SpaceMoneyError::StakeTransfersDisabled,
+    SpaceMoneyError::InvalidRecipient,
+    SpaceMoneyError::StakeHasReceipt,
+    SpaceMoneyError::MissingReceiptAccounts,
+    SpaceMoneyError::LiquidStakingDisabled,
+    SpaceMoneyError::InvalidSpsolMint,
+    SpaceMoneyError::InvalidLiquidDeposit,
+    SpaceMoneyError::ValidatorAlreadyExists,
+    SpaceMoneyError::ValidatorNotFound,
+    SpaceMoneyError::ValidatorListFull,
+    SpaceMoneyError::InvalidVoteAccount,
+    SpaceMoneyError::RebalanceTooSoon,
+    SpaceMoneyError::InvalidDelegationTarget,
+    SpaceMoneyError::UnknownAccountType,
+    SpaceMoneyError::AccountAlreadyMigrated,
+    SpaceMoneyError::TierDisabled,
+    SpaceMoneyError::TierFull,
+    SpaceMoneyError::TierListFull,
+    SpaceMoneyError::InvalidTierParams,
+    SpaceMoneyError::TierNotOpen,
+    SpaceMoneyError::TierClosed,
+    SpaceMoneyError::TierStakeCapExceeded,
+    SpaceMoneyError::UserStakeCapExceeded,
+    SpaceMoneyError::StakeUnbonding,
+    SpaceMoneyError::StakeNotUnbonding,
+    SpaceMoneyError::UnbondingNotComplete,
+    SpaceMoneyError::UnbondingRequired,
+    SpaceMoneyError::WithdrawalQueueFull,
+    SpaceMoneyError::InvalidQueueDestination,
+    SpaceMoneyError::InvalidAmount,
+    SpaceMoneyError::SelfReferral,
+    SpaceMoneyError::InvalidReferralShare,
+    SpaceMoneyError::NoReferralRewards,
+    SpaceMoneyError::InvalidCampaignWindow,
+    SpaceMoneyError::InvalidCampaignBoost,
+    SpaceMoneyError::EmptyCampaign,
+    SpaceMoneyError::CampaignNotActive,
+    SpaceMoneyError::CampaignNotEligible,
+    SpaceMoneyError::CampaignStillRunning,
+    SpaceMoneyError::CampaignEnded,
+    SpaceMoneyError::InvalidDistributor,
+    SpaceMoneyError::InvalidClaimIndex,
+    SpaceMoneyError::InvalidMerkleProof,
+    SpaceMoneyError::BonusAlreadyClaimed,
+    SpaceMoneyError::DistributorOverdrawn,
+    SpaceMoneyError::InvalidInsuranceShare,
+    SpaceMoneyError::InsuranceFundRequired,
+    SpaceMoneyError::TierHasActiveStakes,
+    SpaceMoneyError::TicketNotFound,
+];
+
+pub fn error_from_code(code: u32) -> Option<SpaceMoneyError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    ALL_ERRORS.get(index as usize).copied()
+}
+
+pub fn error_from_instruction_error(err: &InstructionError) -> Option<SpaceMoneyError> {
+    match err {
+        InstructionError::Custom(code) => error_from_code(*code),
+        _ => None,
+    }
+}
+
+pub fn error_from_transaction_error(err: &TransactionError) -> Option<SpaceMoneyError> {
+    match err {
+        TransactionError::InstructionError(_, err) => error_from_instruction_error(err),
+        _ => None,
+    }
+}
+
+pub fn error_from_rpc_error(err: &RpcError) -> Option<SpaceMoneyError> {
+    error_from_transaction_error(&err.get_transaction_error()?)
+}
@@ -0,0 +1,1292 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::{stake, sysvar};
+use spacemoney::states::{AccrualMode, PenaltyCurve, PenaltyStep, PostLock, Rounding, TokenType};
+use spacemoney::{accounts, instruction};
+
+use crate::pda;
+
+// Who may act on a stake: the depositor, or the holder of its receipt NFT
+#[derive(Clone, Copy, Debug)]
+pub enum StakeAuthority {
+    Depositor(Pubkey),
+    ReceiptHolder {
+        depositor: Pubkey,
+        holder: Pubkey,
+        token_account: Pubkey,
+    },
+}
+
+impl StakeAuthority {
+    pub fn depositor(&self) -> Pubkey {
+        match self {
+            StakeAuthority::Depositor(depositor) => *depositor,
+            StakeAuthority::ReceiptHolder { depositor, .. } => *depositor,
+        }
+    }
+
+    pub fn signer(&self) -> Pubkey {
+        match self {
+            StakeAuthority::Depositor(depositor) => *depositor,
+            StakeAuthority::ReceiptHolder { holder, .. } => *holder,
+        }
+    }
+
+    pub fn receipt_token_account(&self) -> Option<Pubkey> {
+        match self {
+            StakeAuthority::Depositor(_) => None,
+            StakeAuthority::ReceiptHolder { token_account, .. } => Some(*token_account),
+        }
+    }
+}
+
+// Token accounts needed when a stake or transfer moves USDT
+#[derive(Clone, Copy, Debug)]
+pub struct UsdtAccounts {
+    pub user_token_account: Pubkey,
+    pub platform_token_account: Pubkey,
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: spacemoney::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(admin: &Pubkey, usdt_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::Initialize {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::Initialize {
+            usdt_mint: *usdt_mint,
+        },
+    )
+}
+
+pub fn deposit_sol(
+    user: &Pubkey,
+    amount: u64,
+    tier: u8,
+    referrer: Option<&Pubkey>,
+    campaign: Option<u64>,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::DepositSol {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+            receipt_mint: None,
+            receipt_metadata: None,
+            receipt_token_account: None,
+            spsol_mint: None,
+            user_spsol_account: None,
+            vault_authority: None,
+            referrer: referrer.map(pda::referral_account),
+            campaign: campaign.map(pda::campaign),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            user: *user,
+            system_program: system_program::ID,
+            token_program: None,
+            associated_token_program: None,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::DepositSol { amount, tier },
+    )
+}
+
+// `stake_index` is the index the new stake will get, i.e. the user's current stake count
+pub fn deposit_sol_with_receipt(
+    user: &Pubkey,
+    stake_index: u64,
+    amount: u64,
+    tier: u8,
+    referrer: Option<&Pubkey>,
+    campaign: Option<u64>,
+    insurance: bool,
+) -> Instruction {
+    let user_account = pda::user_account(user);
+    let receipt_mint = pda::receipt_mint(&user_account, stake_index);
+    build(
+        accounts::DepositSol {
+            platform_state: pda::platform_state(),
+            user_account,
+            tier_config: pda::tier_config(),
+            receipt_mint: Some(receipt_mint),
+            receipt_metadata: Some(pda::receipt_metadata(&user_account, stake_index)),
+            receipt_token_account: Some(get_associated_token_address(user, &receipt_mint)),
+            spsol_mint: None,
+            user_spsol_account: None,
+            vault_authority: Some(pda::vault_authority()),
+            referrer: referrer.map(pda::referral_account),
+            campaign: campaign.map(pda::campaign),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            user: *user,
+            system_program: system_program::ID,
+            token_program: Some(token::ID),
+            associated_token_program: Some(associated_token::ID),
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::DepositSol { amount, tier },
+    )
+}
+
+pub fn deposit_sol_liquid(
+    user: &Pubkey,
+    user_spsol_account: &Pubkey,
+    amount: u64,
+    referrer: Option<&Pubkey>,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::DepositSol {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+            receipt_mint: None,
+            receipt_metadata: None,
+            receipt_token_account: None,
+            spsol_mint: Some(pda::spsol_mint()),
+            user_spsol_account: Some(*user_spsol_account),
+            vault_authority: Some(pda::vault_authority()),
+            referrer: referrer.map(pda::referral_account),
+            campaign: None,
+            insurance_fund: insurance.then(pda::insurance_fund),
+            user: *user,
+            system_program: system_program::ID,
+            token_program: Some(token::ID),
+            associated_token_program: None,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::DepositSol { amount, tier: 0 },
+    )
+}
+
+pub fn deposit_usdt(
+    user: &Pubkey,
+    usdt: &UsdtAccounts,
+    amount: u64,
+    tier: u8,
+    referrer: Option<&Pubkey>,
+    campaign: Option<u64>,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::DepositUsdt {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+            user_token_account: usdt.user_token_account,
+            platform_token_account: usdt.platform_token_account,
+            receipt_mint: None,
+            receipt_metadata: None,
+            receipt_token_account: None,
+            vault_authority: None,
+            referrer: referrer.map(pda::referral_account),
+            campaign: campaign.map(pda::campaign),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            insurance_vault: insurance.then(pda::insurance_vault),
+            user: *user,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            associated_token_program: None,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::DepositUsdt { amount, tier },
+    )
+}
+
+// `stake_index` is the index the new stake will get, i.e. the user's current stake count
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_usdt_with_receipt(
+    user: &Pubkey,
+    usdt: &UsdtAccounts,
+    stake_index: u64,
+    amount: u64,
+    tier: u8,
+    referrer: Option<&Pubkey>,
+    campaign: Option<u64>,
+    insurance: bool,
+) -> Instruction {
+    let user_account = pda::user_account(user);
+    let receipt_mint = pda::receipt_mint(&user_account, stake_index);
+    build(
+        accounts::DepositUsdt {
+            platform_state: pda::platform_state(),
+            user_account,
+            tier_config: pda::tier_config(),
+            user_token_account: usdt.user_token_account,
+            platform_token_account: usdt.platform_token_account,
+            receipt_mint: Some(receipt_mint),
+            receipt_metadata: Some(pda::receipt_metadata(&user_account, stake_index)),
+            receipt_token_account: Some(get_associated_token_address(user, &receipt_mint)),
+            vault_authority: Some(pda::vault_authority()),
+            referrer: referrer.map(pda::referral_account),
+            campaign: campaign.map(pda::campaign),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            insurance_vault: insurance.then(pda::insurance_vault),
+            user: *user,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            associated_token_program: Some(associated_token::ID),
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::DepositUsdt { amount, tier },
+    )
+}
+
+pub fn redeem_spsol(user: &Pubkey, user_spsol_account: &Pubkey, spsol_amount: u64) -> Instruction {
+    build(
+        accounts::RedeemSpsol {
+            platform_state: pda::platform_state(),
+            spsol_mint: pda::spsol_mint(),
+            user_spsol_account: *user_spsol_account,
+            user: *user,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::RedeemSpsol { spsol_amount },
+    )
+}
+
+pub fn withdraw_sol(authority: &StakeAuthority, stake_index: u64, insurance: bool) -> Instruction {
+    build(
+        accounts::WithdrawSol {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            receipt_token_account: authority.receipt_token_account(),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            user: authority.signer(),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::WithdrawSol { stake_index },
+    )
+}
+
+pub fn withdraw_usdt(
+    authority: &StakeAuthority,
+    usdt: &UsdtAccounts,
+    stake_index: u64,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::WithdrawUsdt {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            user_token_account: usdt.user_token_account,
+            platform_token_account: usdt.platform_token_account,
+            vault_authority: pda::vault_authority(),
+            receipt_token_account: authority.receipt_token_account(),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            insurance_vault: insurance.then(pda::insurance_vault),
+            user: authority.signer(),
+            token_program: token::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::WithdrawUsdt { stake_index },
+    )
+}
+
+// Starts the tier's unbonding cooldown; rewards stop accruing here
+pub fn request_unstake(authority: &StakeAuthority, stake_index: u64) -> Instruction {
+    build(
+        accounts::RequestUnstake {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            receipt_token_account: authority.receipt_token_account(),
+            user: authority.signer(),
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::RequestUnstake { stake_index },
+    )
+}
+
+// Pays out an unbonded stake; `usdt` is only needed for USDT stakes, and
+// `insurance` when the vault is short of a SOL payout
+pub fn complete_unstake(
+    authority: &StakeAuthority,
+    usdt: Option<&UsdtAccounts>,
+    stake_index: u64,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::CompleteUnstake {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            user_token_account: usdt.map(|usdt| usdt.user_token_account),
+            platform_token_account: usdt.map(|usdt| usdt.platform_token_account),
+            vault_authority: pda::vault_authority(),
+            receipt_token_account: authority.receipt_token_account(),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            user: authority.signer(),
+            token_program: usdt.map(|_| token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::CompleteUnstake { stake_index },
+    )
+}
+
+// Closes an unlocked or unbonded stake into a withdrawal ticket; `usdt` is only
+// needed for USDT stakes, whose ticket pays out to `usdt.user_token_account`
+pub fn queue_withdrawal(
+    authority: &StakeAuthority,
+    usdt: Option<&UsdtAccounts>,
+    stake_index: u64,
+) -> Instruction {
+    build(
+        accounts::QueueWithdrawal {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            withdrawal_queue: pda::withdrawal_queue(),
+            user_token_account: usdt.map(|usdt| usdt.user_token_account),
+            receipt_token_account: authority.receipt_token_account(),
+            user: authority.signer(),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::QueueWithdrawal { stake_index },
+    )
+}
+
+// Fills queued tickets of one token in order. `destinations` are the ticket
+// destinations from the front of that token's queue; `platform_token_account`
+// selects the USDT queue, otherwise the SOL queue is processed.
+pub fn process_queue(
+    platform_token_account: Option<&Pubkey>,
+    destinations: &[Pubkey],
+) -> Instruction {
+    let token_type = match platform_token_account {
+        Some(_) => TokenType::USDT,
+        None => TokenType::SOL,
+    };
+    let mut ix = build(
+        accounts::ProcessQueue {
+            platform_state: pda::platform_state(),
+            withdrawal_queue: pda::withdrawal_queue(),
+            platform_token_account: platform_token_account.copied(),
+            vault_authority: pda::vault_authority(),
+            token_program: platform_token_account.map(|_| token::ID),
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::ProcessQueue { token_type },
+    );
+    ix.accounts.extend(
+        destinations
+            .iter()
+            .map(|destination| AccountMeta::new(*destination, false)),
+    );
+    ix
+}
+
+// Moves the user's USDT ticket to `user_token_account`, keeping its place in
+// the queue
+pub fn redirect_ticket(user: &Pubkey, user_token_account: &Pubkey, ticket_id: u64) -> Instruction {
+    build(
+        accounts::RedirectTicket {
+            platform_state: pda::platform_state(),
+            withdrawal_queue: pda::withdrawal_queue(),
+            user_token_account: *user_token_account,
+            user: *user,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::RedirectTicket { ticket_id },
+    )
+}
+
+// Tops up the vault; `usdt` funds USDT from `usdt.user_token_account`,
+// otherwise SOL is sent from the funder's wallet
+pub fn fund_rewards(funder: &Pubkey, usdt: Option<&UsdtAccounts>, amount: u64) -> Instruction {
+    let token_type = match usdt {
+        Some(_) => TokenType::USDT,
+        None => TokenType::SOL,
+    };
+    build(
+        accounts::FundRewards {
+            platform_state: pda::platform_state(),
+            funder_token_account: usdt.map(|usdt| usdt.user_token_account),
+            platform_token_account: usdt.map(|usdt| usdt.platform_token_account),
+            funder: *funder,
+            token_program: usdt.map(|_| token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::FundRewards { amount, token_type },
+    )
+}
+
+pub fn register_referrer(referrer: &Pubkey) -> Instruction {
+    build(
+        accounts::RegisterReferrer {
+            platform_state: pda::platform_state(),
+            referral_account: pda::referral_account(referrer),
+            referrer: *referrer,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::RegisterReferrer {},
+    )
+}
+
+// `usdt` claims the USDT balance; without it the SOL balance is claimed
+pub fn claim_referral_rewards(referrer: &Pubkey, usdt: Option<&UsdtAccounts>) -> Instruction {
+    let token_type = match usdt {
+        Some(_) => TokenType::USDT,
+        None => TokenType::SOL,
+    };
+    build(
+        accounts::ClaimReferralRewards {
+            platform_state: pda::platform_state(),
+            referral_account: pda::referral_account(referrer),
+            referrer_token_account: usdt.map(|usdt| usdt.user_token_account),
+            platform_token_account: usdt.map(|usdt| usdt.platform_token_account),
+            vault_authority: pda::vault_authority(),
+            referrer: *referrer,
+            token_program: usdt.map(|_| token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::ClaimReferralRewards { token_type },
+    )
+}
+
+// `usdt` is only needed for USDT stakes
+pub fn force_withdraw(
+    authority: &StakeAuthority,
+    usdt: Option<&UsdtAccounts>,
+    stake_index: u64,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::ForceWithdraw {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            user_token_account: usdt.map(|usdt| usdt.user_token_account),
+            platform_token_account: usdt.map(|usdt| usdt.platform_token_account),
+            vault_authority: pda::vault_authority(),
+            receipt_token_account: authority.receipt_token_account(),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            insurance_vault: usdt.and(insurance.then(pda::insurance_vault)),
+            user: authority.signer(),
+            token_program: usdt.map(|_| token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::ForceWithdraw { stake_index },
+    )
+}
+
+// `usdt` is only needed for USDT stakes; `insurance` passes the insurance
+// fund, needed when the vault is short of a SOL claim
+pub fn claim_rewards(
+    authority: &StakeAuthority,
+    usdt: Option<&UsdtAccounts>,
+    stake_index: u64,
+    insurance: bool,
+) -> Instruction {
+    build(
+        accounts::ClaimRewards {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(&authority.depositor()),
+            tier_config: pda::tier_config(),
+            user_token_account: usdt.map(|usdt| usdt.user_token_account),
+            platform_token_account: usdt.map(|usdt| usdt.platform_token_account),
+            vault_authority: pda::vault_authority(),
+            receipt_token_account: authority.receipt_token_account(),
+            insurance_fund: insurance.then(pda::insurance_fund),
+            user: authority.signer(),
+            token_program: usdt.map(|_| token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::ClaimRewards { stake_index },
+    )
+}
+
+pub fn split_stake(user: &Pubkey, stake_index: u64, amount: u64) -> Instruction {
+    build(
+        accounts::SplitStake {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+            user: *user,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SplitStake {
+            stake_index,
+            amount,
+        },
+    )
+}
+
+pub fn merge_stakes(user: &Pubkey, target_index: u64, source_indices: Vec<u64>) -> Instruction {
+    build(
+        accounts::MergeStakes {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+            user: *user,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::MergeStakes {
+            target_index,
+            source_indices,
+        },
+    )
+}
+
+pub fn transfer_stake(user: &Pubkey, recipient: &Pubkey, stake_index: u64) -> Instruction {
+    build(
+        accounts::TransferStake {
+            platform_state: pda::platform_state(),
+            user_account: pda::user_account(user),
+            recipient_account: pda::user_account(recipient),
+            tier_config: pda::tier_config(),
+            recipient: *recipient,
+            user: *user,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::TransferStake { stake_index },
+    )
+}
+
+pub fn admin_transfer_sol(admin: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::AdminTransfer {
+            platform_state: pda::platform_state(),
+            platform_token_account: None,
+            vault_authority: pda::vault_authority(),
+            recipient: *recipient,
+            recipient_token_account: None,
+            admin: *admin,
+            token_program: None,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::AdminTransfer {
+            amount,
+            token_type: 0,
+        },
+    )
+}
+
+pub fn admin_transfer_usdt(
+    admin: &Pubkey,
+    recipient: &Pubkey,
+    platform_token_account: &Pubkey,
+    recipient_token_account: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::AdminTransfer {
+            platform_state: pda::platform_state(),
+            platform_token_account: Some(*platform_token_account),
+            vault_authority: pda::vault_authority(),
+            recipient: *recipient,
+            recipient_token_account: Some(*recipient_token_account),
+            admin: *admin,
+            token_program: Some(token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::AdminTransfer {
+            amount,
+            token_type: 1,
+        },
+    )
+}
+
+pub fn set_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::SetAdmin {
+            platform_state: pda::platform_state(),
+            admin: *admin,
+            new_admin: *new_admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetAdmin {},
+    )
+}
+
+pub fn update_tier_config(
+    admin: &Pubkey,
+    tier: u8,
+    min_stake: u64,
+    multiplier: u64,
+    lock_seconds: i64,
+    max_stakes: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateTierConfig {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::UpdateTierConfig {
+            tier,
+            min_stake,
+            multiplier,
+            lock_seconds,
+            max_stakes,
+        },
+    )
+}
+
+// The new tier's id is the current length of the tier list
+pub fn add_tier(
+    admin: &Pubkey,
+    name: &str,
+    min_stake: u64,
+    multiplier: u64,
+    lock_seconds: i64,
+    max_stakes: u64,
+) -> Instruction {
+    build(
+        accounts::AddTier {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::AddTier {
+            name: name.to_string(),
+            min_stake,
+            multiplier,
+            lock_seconds,
+            max_stakes,
+        },
+    )
+}
+
+pub fn disable_tier(admin: &Pubkey, tier: u8) -> Instruction {
+    build(
+        accounts::DisableTier {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::DisableTier { tier },
+    )
+}
+
+// Zero leaves a cap or side of the deposit window open
+pub fn set_tier_limits(
+    admin: &Pubkey,
+    tier: u8,
+    max_total_stake: u64,
+    max_user_stake: u64,
+    open_at: i64,
+    close_at: i64,
+) -> Instruction {
+    build(
+        accounts::SetTierLimits {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetTierLimits {
+            tier,
+            max_total_stake,
+            max_user_stake,
+            open_at,
+            close_at,
+        },
+    )
+}
+
+// Per-second accrual with the given rounding, or back to whole days
+pub fn set_tier_accrual(
+    admin: &Pubkey,
+    tier: u8,
+    accrual_mode: AccrualMode,
+    rounding: Rounding,
+) -> Instruction {
+    build(
+        accounts::SetTierAccrual {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetTierAccrual {
+            tier,
+            accrual_mode,
+            rounding,
+        },
+    )
+}
+
+// What the tier's new stakes do after their lock; the rate only applies to Flexible
+pub fn set_tier_post_lock(
+    admin: &Pubkey,
+    tier: u8,
+    post_lock: PostLock,
+    post_lock_rate_bps: u16,
+) -> Instruction {
+    build(
+        accounts::SetTierPostLock {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetTierPostLock {
+            tier,
+            post_lock,
+            post_lock_rate_bps,
+        },
+    )
+}
+
+// Cooldown between request_unstake and complete_unstake; 0 allows direct withdrawals
+pub fn set_tier_unbonding(admin: &Pubkey, tier: u8, unbonding_seconds: i64) -> Instruction {
+    build(
+        accounts::SetTierUnbonding {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetTierUnbonding {
+            tier,
+            unbonding_seconds,
+        },
+    )
+}
+
+// `steps` is the Step curve's schedule, in ascending `from_bps`
+pub fn set_tier_penalty(
+    admin: &Pubkey,
+    tier: u8,
+    curve: PenaltyCurve,
+    rate_bps: u16,
+    steps: &[PenaltyStep],
+    on_principal: bool,
+    clawback: bool,
+) -> Instruction {
+    build(
+        accounts::SetTierPenalty {
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetTierPenalty {
+            tier,
+            curve,
+            rate_bps,
+            steps: steps.to_vec(),
+            on_principal,
+            clawback,
+        },
+    )
+}
+
+pub fn set_usdt_mint(admin: &Pubkey, usdt_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::SetUsdtMint {
+            platform_state: pda::platform_state(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetUsdtMint {
+            usdt_mint: *usdt_mint,
+        },
+    )
+}
+
+pub fn set_stake_transfers(admin: &Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetStakeTransfers {
+            platform_state: pda::platform_state(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetStakeTransfers { enabled },
+    )
+}
+
+pub fn set_referral_share(admin: &Pubkey, share_bps: u16) -> Instruction {
+    build(
+        accounts::SetReferralShare {
+            platform_state: pda::platform_state(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetReferralShare { share_bps },
+    )
+}
+
+pub fn initialize_insurance_fund(admin: &Pubkey, usdt_mint: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeInsuranceFund {
+            platform_state: pda::platform_state(),
+            insurance_fund: pda::insurance_fund(),
+            insurance_vault: pda::insurance_vault(),
+            usdt_mint: *usdt_mint,
+            admin: *admin,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::InitializeInsuranceFund {},
+    )
+}
+
+pub fn set_insurance_share(admin: &Pubkey, share_bps: u16) -> Instruction {
+    build(
+        accounts::SetInsuranceShare {
+            platform_state: pda::platform_state(),
+            insurance_fund: pda::insurance_fund(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetInsuranceShare { share_bps },
+    )
+}
+
+// `campaign_id` is the id the new campaign will get, i.e. the platform's campaign count
+#[allow(clippy::too_many_arguments)]
+pub fn create_campaign(
+    admin: &Pubkey,
+    campaign_id: u64,
+    start_time: i64,
+    end_time: i64,
+    tier_mask: u16,
+    boost_bps: u16,
+    budget_sol: u64,
+    budget_usdt: u64,
+) -> Instruction {
+    build(
+        accounts::CreateCampaign {
+            platform_state: pda::platform_state(),
+            campaign: pda::campaign(campaign_id),
+            admin: *admin,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::CreateCampaign {
+            start_time,
+            end_time,
+            tier_mask,
+            boost_bps,
+            budget_sol,
+            budget_usdt,
+        },
+    )
+}
+
+pub fn end_campaign(authority: &Pubkey, campaign_id: u64) -> Instruction {
+    build(
+        accounts::EndCampaign {
+            platform_state: pda::platform_state(),
+            campaign: pda::campaign(campaign_id),
+            authority: *authority,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::EndCampaign {},
+    )
+}
+
+// `distributor_id` is the id the new distributor will get, i.e. the platform's
+// distributor count; `merkle_root` comes from a `BonusTree`
+pub fn create_distributor(
+    admin: &Pubkey,
+    distributor_id: u64,
+    merkle_root: [u8; 32],
+    token_type: TokenType,
+    total_amount: u64,
+    max_claims: u32,
+) -> Instruction {
+    build(
+        accounts::CreateDistributor {
+            platform_state: pda::platform_state(),
+            distributor: pda::distributor(distributor_id),
+            claimed_bitmap: pda::claimed_bitmap(distributor_id),
+            admin: *admin,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::CreateDistributor {
+            merkle_root,
+            token_type,
+            total_amount,
+            max_claims,
+        },
+    )
+}
+
+// `usdt` is only needed for USDT distributors
+pub fn claim_bonus(
+    claimant: &Pubkey,
+    usdt: Option<&UsdtAccounts>,
+    distributor_id: u64,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::ClaimBonus {
+            platform_state: pda::platform_state(),
+            distributor: pda::distributor(distributor_id),
+            claimed_bitmap: pda::claimed_bitmap(distributor_id),
+            claimant_token_account: usdt.map(|usdt| usdt.user_token_account),
+            platform_token_account: usdt.map(|usdt| usdt.platform_token_account),
+            vault_authority: pda::vault_authority(),
+            claimant: *claimant,
+            token_program: usdt.map(|_| token::ID),
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::ClaimBonus {
+            index,
+            amount,
+            proof,
+        },
+    )
+}
+
+pub fn close_distributor(admin: &Pubkey, distributor_id: u64) -> Instruction {
+    build(
+        accounts::CloseDistributor {
+            platform_state: pda::platform_state(),
+            distributor: pda::distributor(distributor_id),
+            claimed_bitmap: pda::claimed_bitmap(distributor_id),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::CloseDistributor {},
+    )
+}
+
+pub fn initialize_liquid_staking(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeLiquidStaking {
+            platform_state: pda::platform_state(),
+            spsol_mint: pda::spsol_mint(),
+            vault_authority: pda::vault_authority(),
+            admin: *admin,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::InitializeLiquidStaking {},
+    )
+}
+
+pub fn add_validator(admin: &Pubkey, vote_account: &Pubkey) -> Instruction {
+    build(
+        accounts::AddValidator {
+            platform_state: pda::platform_state(),
+            validator_list: pda::validator_list(),
+            vote_account: *vote_account,
+            admin: *admin,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::AddValidator {},
+    )
+}
+
+pub fn remove_validator(admin: &Pubkey, vote_account: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveValidator {
+            platform_state: pda::platform_state(),
+            validator_list: pda::validator_list(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::RemoveValidator {
+            vote_account: *vote_account,
+        },
+    )
+}
+
+pub fn set_delegation_target(admin: &Pubkey, target_delegation_bps: u64) -> Instruction {
+    build(
+        accounts::SetDelegationTarget {
+            platform_state: pda::platform_state(),
+            validator_list: pda::validator_list(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::SetDelegationTarget {
+            target_delegation_bps,
+        },
+    )
+}
+
+pub fn rebalance_validator(cranker: &Pubkey, vote_account: &Pubkey) -> Instruction {
+    build(
+        accounts::RebalanceValidator {
+            platform_state: pda::platform_state(),
+            validator_list: pda::validator_list(),
+            vote_account: *vote_account,
+            validator_stake: pda::validator_stake(vote_account),
+            vault_authority: pda::vault_authority(),
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+            stake_history: sysvar::stake_history::ID,
+            #[allow(deprecated)]
+            stake_config: stake::config::ID,
+            stake_program: stake::program::ID,
+            system_program: system_program::ID,
+            cranker: *cranker,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::RebalanceValidator {},
+    )
+}
+
+pub fn get_claimable(user: &Pubkey, stake_index: u64) -> Instruction {
+    build(
+        accounts::GetClaimable {
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+        },
+        instruction::GetClaimable { stake_index },
+    )
+}
+
+pub fn get_user_summary(user: &Pubkey) -> Instruction {
+    build(
+        accounts::GetUserSummary {
+            user_account: pda::user_account(user),
+            tier_config: pda::tier_config(),
+        },
+        instruction::GetUserSummary {},
+    )
+}
+
+pub fn get_loyalty(user: &Pubkey) -> Instruction {
+    build(
+        accounts::GetLoyalty {
+            user_account: pda::user_account(user),
+        },
+        instruction::GetLoyalty {},
+    )
+}
+
+// Pass `liquid_staking` once spSOL exists to get its supply back as well
+pub fn get_platform_stats(liquid_staking: bool) -> Instruction {
+    build(
+        accounts::GetPlatformStats {
+            platform_state: pda::platform_state(),
+            spsol_mint: liquid_staking.then(pda::spsol_mint),
+        },
+        instruction::GetPlatformStats {},
+    )
+}
+
+// Upgrades a platform state, user account or tier config from an older layout
+pub fn migrate_account(payer: &Pubkey, account: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAccount {
+            account: *account,
+            platform_state: pda::platform_state(),
+            tier_config: pda::tier_config(),
+            payer: *payer,
+            system_program: system_program::ID,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::MigrateAccount {},
+    )
+}
+
+pub fn pause_program(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::PauseProgram {
+            platform_state: pda::platform_state(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::PauseProgram {},
+    )
+}
+
+pub fn resume_program(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::ResumeProgram {
+            platform_state: pda::platform_state(),
+            admin: *admin,
+            #[cfg(not(feature = "log-events"))]
+            event_authority: pda::event_authority(),
+            #[cfg(not(feature = "log-events"))]
+            program: spacemoney::ID,
+        },
+        instruction::ResumeProgram {},
+    )
+}
@@ -0,0 +1,13 @@
+pub mod accounts;
+pub mod errors;
+pub mod instructions;
+pub mod merkle;
+pub mod pda;
+pub mod views;
+
+pub use accounts::*;
+pub use errors::*;
+pub use merkle::*;
+pub use spacemoney::ID as PROGRAM_ID;
+pub use spacemoney_math as math;
+pub use views::*;
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::Pubkey;
+use spacemoney::utils::{bonus_leaf, merkle_parent};
+
+// The Merkle tree behind a bonus distributor. Leaf `i` pays `claims[i]`, and
+// `i` is the index passed to `claim_bonus`.
+pub struct BonusTree {
+    // Leaves first, root last
+    layers: Vec<Vec<[u8; 32]>>,
+}
+
+impl BonusTree {
+    pub fn new(claims: &[(Pubkey, u64)]) -> Self {
+        let leaves: Vec<[u8; 32]> = claims
+            .iter()
+            .enumerate()
+            .map(|(index, (claimant, amount))| bonus_leaf(index as u32, claimant, *amount))
+            .collect();
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            // An unpaired node moves up unchanged
+            let next = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [a, b] => merkle_parent(a, b),
+                    [a] => *a,
+                    _ => unreachable!(),
+                })
+                .collect();
+            layers.push(next);
+        }
+        Self { layers }
+    }
+
+    // All zeroes for an empty tree
+    pub fn root(&self) -> [u8; 32] {
+        self.layers
+            .last()
+            .and_then(|layer| layer.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::new();
+        let mut index = index;
+        for layer in &self.layers[..self.layers.len() - 1] {
+            if let Some(sibling) = layer.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        proof
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
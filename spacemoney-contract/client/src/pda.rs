@@ -0,0 +1,86 @@
+use anchor_lang::prelude::Pubkey;
+use spacemoney::constants::*;
+
+pub fn find_program_address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &spacemoney::ID)
+}
+
+pub fn platform_state() -> Pubkey {
+    find_program_address(&[PLATFORM_SEED]).0
+}
+
+pub fn user_account(user: &Pubkey) -> Pubkey {
+    find_program_address(&[USER_SEED, user.as_ref()]).0
+}
+
+pub fn tier_config() -> Pubkey {
+    find_program_address(&[TIERS_SEED]).0
+}
+
+pub fn vault_authority() -> Pubkey {
+    find_program_address(&[VAULT_SEED]).0
+}
+
+pub fn receipt_mint(user_account: &Pubkey, stake_index: u64) -> Pubkey {
+    find_program_address(&[
+        RECEIPT_SEED,
+        user_account.as_ref(),
+        &stake_index.to_le_bytes(),
+    ])
+    .0
+}
+
+pub fn receipt_metadata(user_account: &Pubkey, stake_index: u64) -> Pubkey {
+    find_program_address(&[
+        RECEIPT_METADATA_SEED,
+        user_account.as_ref(),
+        &stake_index.to_le_bytes(),
+    ])
+    .0
+}
+
+pub fn spsol_mint() -> Pubkey {
+    find_program_address(&[SPSOL_MINT_SEED]).0
+}
+
+pub fn validator_list() -> Pubkey {
+    find_program_address(&[VALIDATOR_LIST_SEED]).0
+}
+
+pub fn validator_stake(vote_account: &Pubkey) -> Pubkey {
+    find_program_address(&[VALIDATOR_STAKE_SEED, vote_account.as_ref()]).0
+}
+
+pub fn withdrawal_queue() -> Pubkey {
+    find_program_address(&[WITHDRAWAL_QUEUE_SEED]).0
+}
+
+pub fn referral_account(referrer: &Pubkey) -> Pubkey {
+    find_program_address(&[REFERRAL_SEED, referrer.as_ref()]).0
+}
+
+pub fn campaign(campaign_id: u64) -> Pubkey {
+    find_program_address(&[CAMPAIGN_SEED, &campaign_id.to_le_bytes()]).0
+}
+
+pub fn insurance_fund() -> Pubkey {
+    find_program_address(&[INSURANCE_FUND_SEED]).0
+}
+
+pub fn insurance_vault() -> Pubkey {
+    find_program_address(&[INSURANCE_VAULT_SEED]).0
+}
+
+pub fn distributor(distributor_id: u64) -> Pubkey {
+    find_program_address(&[DISTRIBUTOR_SEED, &distributor_id.to_le_bytes()]).0
+}
+
+pub fn claimed_bitmap(distributor_id: u64) -> Pubkey {
+    let distributor = distributor(distributor_id);
+    find_program_address(&[CLAIMED_BITMAP_SEED, distributor.as_ref()]).0
+}
+
+// Signs the self-CPI that carries events (see #[event_cpi])
+pub fn event_authority() -> Pubkey {
+    find_program_address(&[EVENT_AUTHORITY_SEED]).0
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorDeserialize;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcSimulateTransactionConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::message::Message;
+use solana_sdk::transaction::Transaction;
+use spacemoney::instructions::{ClaimableView, LoyaltyView, PlatformStats, UserSummary};
+
+use crate::errors::ClientError;
+use crate::instructions;
+
+// Decodes what a view instruction returned, rejecting data set by another program
+pub fn decode_return_data<T: AnchorDeserialize>(
+    program_id: &Pubkey,
+    data: &[u8],
+) -> Result<T, ClientError> {
+    if *program_id != spacemoney::ID {
+        return Err(ClientError::MissingReturnData);
+    }
+    T::try_from_slice(data).map_err(|err| ClientError::Decode(err.to_string()))
+}
+
+// Simulates a view instruction and decodes its return data. `payer` only has
+// to exist: signatures aren't checked and nothing is charged.
+pub fn simulate_view<T: AnchorDeserialize>(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    instruction: Instruction,
+) -> Result<T, ClientError> {
+    let transaction = Transaction::new_unsigned(Message::new(&[instruction], Some(payer)));
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(rpc.commitment()),
+        ..RpcSimulateTransactionConfig::default()
+    };
+    let result = rpc
+        .simulate_transaction_with_config(&transaction, config)?
+        .value;
+    if let Some(err) = result.err {
+        return Err(ClientError::Simulation(err));
+    }
+
+    let return_data = result.return_data.ok_or(ClientError::MissingReturnData)?;
+    let program_id = return_data
+        .program_id
+        .parse::<Pubkey>()
+        .map_err(|err| ClientError::Decode(err.to_string()))?;
+    let data = STANDARD
+        .decode(&return_data.data.0)
+        .map_err(|err| ClientError::Decode(err.to_string()))?;
+    decode_return_data(&program_id, &data)
+}
+
+pub fn fetch_claimable(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    user: &Pubkey,
+    stake_index: u64,
+) -> Result<ClaimableView, ClientError> {
+    simulate_view(rpc, payer, instructions::get_claimable(user, stake_index))
+}
+
+pub fn fetch_user_summary(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    user: &Pubkey,
+) -> Result<UserSummary, ClientError> {
+    simulate_view(rpc, payer, instructions::get_user_summary(user))
+}
+
+pub fn fetch_loyalty(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    user: &Pubkey,
+) -> Result<LoyaltyView, ClientError> {
+    simulate_view(rpc, payer, instructions::get_loyalty(user))
+}
+
+pub fn fetch_platform_stats(
+    rpc: &RpcClient,
+    payer: &Pubkey,
+    liquid_staking: bool,
+) -> Result<PlatformStats, ClientError> {
+    simulate_view(rpc, payer, instructions::get_platform_stats(liquid_staking))
+}
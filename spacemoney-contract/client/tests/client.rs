@@ -0,0 +1,110 @@
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::transaction::TransactionError;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::instructions::ClaimableView;
+use spacemoney::utils::{bonus_leaf, verify_merkle_proof};
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::{
+    decode_return_data, error_from_code, error_from_transaction_error, pda, BonusTree, ClientError,
+    ALL_ERRORS,
+};
+
+#[test]
+fn error_codes_round_trip() {
+    for (index, err) in ALL_ERRORS.iter().enumerate() {
+        let code = *err as u32 + ERROR_CODE_OFFSET;
+        assert_eq!(code, ERROR_CODE_OFFSET + index as u32);
+        assert_eq!(error_from_code(code).map(|e| e as u32), Some(*err as u32));
+    }
+    assert!(error_from_code(ERROR_CODE_OFFSET + ALL_ERRORS.len() as u32).is_none());
+    assert!(error_from_code(0).is_none());
+
+    let tx_err = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(SpaceMoneyError::StakeLocked as u32 + ERROR_CODE_OFFSET),
+    );
+    assert!(matches!(
+        error_from_transaction_error(&tx_err),
+        Some(SpaceMoneyError::StakeLocked)
+    ));
+}
+
+#[test]
+fn builders_derive_program_addresses() {
+    let user = Pubkey::new_unique();
+    let ix = instructions::deposit_sol(&user, 1_000_000_000, 0, None, None, false);
+    assert_eq!(ix.program_id, spacemoney::ID);
+    assert_eq!(ix.accounts[0].pubkey, pda::platform_state());
+    assert_eq!(ix.accounts[1].pubkey, pda::user_account(&user));
+    assert_eq!(ix.accounts[2].pubkey, pda::tier_config());
+
+    // Receipt holders sign for the depositor's user account
+    let holder = Pubkey::new_unique();
+    let token_account = Pubkey::new_unique();
+    let authority = StakeAuthority::ReceiptHolder {
+        depositor: user,
+        holder,
+        token_account,
+    };
+    let ix = instructions::withdraw_sol(&authority, 0, true);
+    assert_eq!(ix.accounts[1].pubkey, pda::user_account(&user));
+    assert_eq!(ix.accounts[3].pubkey, token_account);
+    assert_eq!(ix.accounts[4].pubkey, pda::insurance_fund());
+    assert_eq!(ix.accounts[5].pubkey, holder);
+    assert!(ix.accounts[5].is_signer);
+}
+
+#[test]
+fn decodes_view_return_data() {
+    let view = ClaimableView {
+        stake_index: 2,
+        claimable: 1_500_000,
+        earned: 2_000_000,
+        claimed_rewards: 500_000,
+        max_rewards: 9_000_000,
+        withdrawable: 1_001_200_000,
+        is_locked: true,
+        lock_until: 1_800_000_000,
+        timestamp: 1_700_000_000,
+    };
+    let data = view.try_to_vec().unwrap();
+
+    let decoded: ClaimableView = decode_return_data(&spacemoney::ID, &data).unwrap();
+    assert!(decoded == view);
+
+    // Return data left by a CPI'd program is not a view result
+    assert!(matches!(
+        decode_return_data::<ClaimableView>(&Pubkey::new_unique(), &data),
+        Err(ClientError::MissingReturnData)
+    ));
+
+    let ix = instructions::get_platform_stats(false);
+    assert_eq!(ix.accounts[0].pubkey, pda::platform_state());
+    // Anchor passes the program id for a missing optional account
+    assert_eq!(ix.accounts[1].pubkey, spacemoney::ID);
+}
+
+#[test]
+fn bonus_tree_proofs_verify_on_chain() {
+    // An odd count leaves an unpaired node on the way up
+    let claims: Vec<(Pubkey, u64)> = (1..=5).map(|n| (Pubkey::new_unique(), n * 1_000)).collect();
+    let tree = BonusTree::new(&claims);
+    let root = tree.root();
+
+    for (index, (claimant, amount)) in claims.iter().enumerate() {
+        let leaf = bonus_leaf(index as u32, claimant, *amount);
+        assert!(verify_merkle_proof(&tree.proof(index), &root, leaf));
+        // The proof is bound to the claimant, the amount and the index
+        let other = bonus_leaf(index as u32, claimant, amount + 1);
+        assert!(!verify_merkle_proof(&tree.proof(index), &root, other));
+        let moved = bonus_leaf(index as u32 + 1, claimant, *amount);
+        assert!(!verify_merkle_proof(&tree.proof(index), &root, moved));
+    }
+
+    let single = BonusTree::new(&claims[..1]);
+    assert!(single.proof(0).is_empty());
+    assert_eq!(single.root(), bonus_leaf(0, &claims[0].0, claims[0].1));
+}
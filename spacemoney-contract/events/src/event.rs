@@ -0,0 +1,108 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use spacemoney::events::*;
+
+use crate::DecodeError;
+
+macro_rules! program_events {
+    ($($name:ident),* $(,)?) => {
+        /// Every event the SpaceMoney program emits.
+        pub enum SpaceMoneyEvent {
+            $($name($name),)*
+        }
+
+        impl SpaceMoneyEvent {
+            /// Decodes `discriminator || borsh(event)`. Returns `None` for
+            /// discriminators that don't belong to a SpaceMoney event.
+            pub fn from_bytes(data: &[u8]) -> Result<Option<Self>, DecodeError> {
+                if data.len() < 8 {
+                    return Err(DecodeError::TooShort);
+                }
+                let (discriminator, mut body) = data.split_at(8);
+
+                $(
+                    if discriminator == $name::DISCRIMINATOR {
+                        let event = $name::deserialize(&mut body).map_err(|err| {
+                            DecodeError::Deserialize {
+                                name: stringify!($name),
+                                reason: err.to_string(),
+                            }
+                        })?;
+                        return Ok(Some(SpaceMoneyEvent::$name(event)));
+                    }
+                )*
+
+                Ok(None)
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SpaceMoneyEvent::$name(_) => stringify!($name),)*
+                }
+            }
+
+            /// Platform totals right after the instruction that emitted the event.
+            pub fn totals(&self) -> &PlatformTotals {
+                match self {
+                    $(SpaceMoneyEvent::$name(event) => &event.totals,)*
+                }
+            }
+        }
+    };
+}
+
+program_events!(
+    InitializedEvent,
+    DepositedEvent,
+    WithdrewEvent,
+    UnstakeRequestedEvent,
+    WithdrawalQueuedEvent,
+    WithdrawalFilledEvent,
+    WithdrawalSkippedEvent,
+    WithdrawalRedirectedEvent,
+    RewardsFundedEvent,
+    ClaimedRewardsEvent,
+    ForceWithdrewEvent,
+    AdminTransferredEvent,
+    AdminChangedEvent,
+    ProgramPausedEvent,
+    ProgramResumedEvent,
+    TierConfigUpdatedEvent,
+    SplitStakeEvent,
+    MergedStakesEvent,
+    TransferredStakeEvent,
+    StakeTransfersUpdatedEvent,
+    MintedReceiptEvent,
+    LiquidStakingInitializedEvent,
+    MintedSpsolEvent,
+    RedeemedSpsolEvent,
+    ValidatorAddedEvent,
+    ValidatorRemovedEvent,
+    DelegationTargetUpdatedEvent,
+    DelegatedStakeEvent,
+    DeactivatedStakeEvent,
+    WithdrewStakeEvent,
+    UsdtMintChangedEvent,
+    AccountMigratedEvent,
+    TierAddedEvent,
+    TierDisabledEvent,
+    TierLimitsUpdatedEvent,
+    TierAccrualUpdatedEvent,
+    TierPostLockUpdatedEvent,
+    TierUnbondingUpdatedEvent,
+    TierPenaltyUpdatedEvent,
+    LoyaltyUpdatedEvent,
+    ReferrerRegisteredEvent,
+    ReferralCreditedEvent,
+    ReferralRewardsClaimedEvent,
+    ReferralShareUpdatedEvent,
+    CampaignCreatedEvent,
+    CampaignBoostedEvent,
+    CampaignEndedEvent,
+    DistributorCreatedEvent,
+    BonusClaimedEvent,
+    DistributorClosedEvent,
+    InsuranceFundInitializedEvent,
+    InsuranceShareUpdatedEvent,
+    InsuranceContributedEvent,
+    InsuranceBackstopEvent,
+);
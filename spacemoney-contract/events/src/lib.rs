@@ -0,0 +1,19 @@
+pub mod event;
+pub mod logs;
+
+pub use event::*;
+pub use logs::*;
+pub use spacemoney::events::*;
+pub use spacemoney::ID as PROGRAM_ID;
+
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeError {
+    #[error("Event data is shorter than a discriminator")]
+    TooShort,
+
+    #[error("Invalid base64 in program data: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("Failed to deserialize {name}: {reason}")]
+    Deserialize { name: &'static str, reason: String },
+}
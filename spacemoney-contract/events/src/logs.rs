@@ -0,0 +1,79 @@
+use anchor_lang::prelude::Pubkey;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use spacemoney::constants::EVENT_AUTHORITY_SEED;
+
+use crate::{DecodeError, SpaceMoneyEvent};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// The PDA that signs SpaceMoney's event self-CPIs. Only the program can sign
+/// for it, so an inner instruction carrying it is a genuine event.
+pub fn event_authority() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &spacemoney::ID).0
+}
+
+/// Decodes the data of an inner instruction emitted by `emit_event!`. Returns
+/// `None` for instructions that aren't event self-CPIs. The caller must check
+/// the instruction targets SpaceMoney and is signed by [`event_authority`].
+pub fn parse_event_instruction(data: &[u8]) -> Result<Option<SpaceMoneyEvent>, DecodeError> {
+    match data.strip_prefix(&EVENT_IX_TAG_LE[..]) {
+        Some(event) => SpaceMoneyEvent::from_bytes(event),
+        None => Ok(None),
+    }
+}
+
+/// Decodes the events the SpaceMoney program logged in a transaction. Only
+/// programs built with the `log-events` feature log their events.
+pub fn parse_logs(logs: &[String]) -> Result<Vec<SpaceMoneyEvent>, DecodeError> {
+    parse_logs_for(&spacemoney::ID, logs)
+}
+
+/// Decodes the events logged by `program_id`, which must be a SpaceMoney
+/// deployment. `Program data:` lines are attributed to the program at the top
+/// of the invocation stack, so data logged by other programs (including CPIs
+/// made by SpaceMoney) is ignored.
+pub fn parse_logs_for(
+    program_id: &Pubkey,
+    logs: &[String],
+) -> Result<Vec<SpaceMoneyEvent>, DecodeError> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        if let Some(data) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            let bytes = STANDARD.decode(data.trim())?;
+            if let Some(event) = SpaceMoneyEvent::from_bytes(&bytes)? {
+                events.push(event);
+            }
+            continue;
+        }
+
+        let Some(rest) = line.strip_prefix("Program ") else {
+            continue;
+        };
+        let mut parts = rest.split_whitespace();
+        let (Some(id), Some(status)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        // Skips `Program log:` and other lines whose first word isn't a program id
+        if id.parse::<Pubkey>().is_err() {
+            continue;
+        }
+        match status {
+            "invoke" => stack.push(id),
+            "success" | "failed:" => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+
+    Ok(events)
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use spacemoney_events::*;
+
+fn data_line(event: &impl Event) -> String {
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn totals() -> PlatformTotals {
+    PlatformTotals {
+        total_staked_sol: 980_000_000,
+        total_staked_usdt: 0,
+        treasury_sol: 20_000_000,
+        treasury_usdt: 0,
+        liquid_staked_sol: 0,
+    }
+}
+
+fn deposited(user: Pubkey) -> DepositedEvent {
+    DepositedEvent {
+        user,
+        amount: 980_000_000,
+        tier: 1,
+        token_type: TokenType::SOL,
+        fee: 20_000_000,
+        lock_until: 1_700_000_000,
+        timestamp: 1_690_000_000,
+        stake_index: 0,
+        active_stakes: 1,
+        loyalty_bonus_bps: 0,
+        campaign_boost_bps: 0,
+        totals: totals(),
+    }
+}
+
+#[test]
+fn decodes_events_logged_by_the_program() {
+    let user = Pubkey::new_unique();
+    let id = spacemoney::ID;
+    let logs = vec![
+        format!("Program {id} invoke [1]"),
+        "Program log: Instruction: DepositSol".to_string(),
+        data_line(&deposited(user)),
+        data_line(&MintedReceiptEvent {
+            user,
+            stake_index: 0,
+            mint: Pubkey::new_unique(),
+            timestamp: 1_690_000_000,
+            totals: totals(),
+        }),
+        format!("Program {id} consumed 41000 of 200000 compute units"),
+        format!("Program {id} success"),
+    ];
+
+    let events = parse_logs(&logs).unwrap();
+    assert_eq!(events.len(), 2);
+    match &events[0] {
+        SpaceMoneyEvent::DepositedEvent(event) => {
+            assert_eq!(event.user, user);
+            assert_eq!(event.amount, 980_000_000);
+            assert_eq!(event.tier, 1);
+            assert!(event.token_type == TokenType::SOL);
+            assert_eq!(event.active_stakes, 1);
+            assert!(event.totals == totals());
+        }
+        other => panic!("unexpected {}", other.name()),
+    }
+    assert_eq!(events[1].name(), "MintedReceiptEvent");
+    assert!(*events[1].totals() == totals());
+}
+
+#[test]
+fn ignores_data_logged_by_other_programs() {
+    let user = Pubkey::new_unique();
+    let id = spacemoney::ID;
+    let other = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {other} invoke [1]"),
+        data_line(&deposited(user)),
+        format!("Program {id} invoke [2]"),
+        "Program log: success".to_string(),
+        data_line(&deposited(user)),
+        format!("Program {id} success"),
+        data_line(&deposited(user)),
+        format!("Program {other} success"),
+        data_line(&deposited(user)),
+    ];
+
+    let events = parse_logs(&logs).unwrap();
+    assert_eq!(events.len(), 1);
+}
+
+#[test]
+fn skips_unknown_discriminators_and_rejects_bad_data() {
+    let id = spacemoney::ID;
+    let unknown = format!("Program data: {}", STANDARD.encode([7u8; 16]));
+    let logs = vec![format!("Program {id} invoke [1]"), unknown];
+    assert!(parse_logs(&logs).unwrap().is_empty());
+
+    let truncated = deposited(Pubkey::new_unique()).data()[..20].to_vec();
+    let logs = vec![
+        format!("Program {id} invoke [1]"),
+        format!("Program data: {}", STANDARD.encode(truncated)),
+    ];
+    assert!(matches!(
+        parse_logs(&logs),
+        Err(DecodeError::Deserialize {
+            name: "DepositedEvent",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn decodes_event_self_cpi_data() {
+    let user = Pubkey::new_unique();
+    let mut data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+    data.extend(deposited(user).data());
+
+    match parse_event_instruction(&data).unwrap() {
+        Some(SpaceMoneyEvent::DepositedEvent(event)) => assert_eq!(event.user, user),
+        _ => panic!("expected a DepositedEvent"),
+    }
+
+    // Data without the event tag is not an event
+    assert!(parse_event_instruction(&deposited(user).data()[..16])
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,1123 @@
+use std::path::Path;
+
+use anyhow::Result;
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use spacemoney_events::{
+    parse_event_instruction, parse_logs, AccrualMode, CampaignEndReason, InsuranceSource,
+    PenaltyCurve, PostLock, Rounding, SpaceMoneyEvent, TokenType,
+};
+
+use crate::dump::DumpedTransaction;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature  TEXT PRIMARY KEY,
+    slot       INTEGER NOT NULL,
+    block_time INTEGER,
+    failed     INTEGER NOT NULL
+);
+
+-- Every decoded event, including admin and validator events
+CREATE TABLE IF NOT EXISTS events (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    name        TEXT NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+-- Stakes rebuilt from events; stake_index matches UserAccount.stakes
+CREATE TABLE IF NOT EXISTS stakes (
+    user            TEXT NOT NULL,
+    stake_index     INTEGER NOT NULL,
+    tier            INTEGER NOT NULL,
+    token_type      TEXT NOT NULL,
+    amount          INTEGER NOT NULL,
+    claimed_rewards INTEGER NOT NULL,
+    is_active       INTEGER NOT NULL,
+    PRIMARY KEY (user, stake_index)
+);
+
+-- Each user's latest loyalty score, from the stake that last closed
+CREATE TABLE IF NOT EXISTS loyalty (
+    user       TEXT PRIMARY KEY,
+    stake_days INTEGER NOT NULL,
+    level      INTEGER NOT NULL,
+    bonus_bps  INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+-- Promotional campaigns with what their boosts have reserved so far
+CREATE TABLE IF NOT EXISTS campaigns (
+    id               INTEGER PRIMARY KEY,
+    start_time       INTEGER NOT NULL,
+    end_time         INTEGER NOT NULL,
+    tier_mask        INTEGER NOT NULL,
+    boost_bps        INTEGER NOT NULL,
+    budget_sol       INTEGER NOT NULL,
+    budget_usdt      INTEGER NOT NULL,
+    spent_sol        INTEGER NOT NULL DEFAULT 0,
+    spent_usdt       INTEGER NOT NULL DEFAULT 0,
+    boosted_deposits INTEGER NOT NULL DEFAULT 0,
+    ended_at         INTEGER,
+    end_reason       TEXT
+);
+
+-- Bonus distributors, how much of each has been claimed and what closing returned
+CREATE TABLE IF NOT EXISTS distributors (
+    id              INTEGER PRIMARY KEY,
+    merkle_root     TEXT NOT NULL,
+    token_type      TEXT NOT NULL,
+    total_amount    INTEGER NOT NULL,
+    max_claims      INTEGER NOT NULL,
+    claimed_amount  INTEGER NOT NULL DEFAULT 0,
+    claimed_count   INTEGER NOT NULL DEFAULT 0,
+    created_at      INTEGER NOT NULL,
+    closed_at       INTEGER,
+    returned_amount INTEGER
+);
+
+-- Insurance fund contributions and backstops with the fund's balance after each
+CREATE TABLE IF NOT EXISTS insurance_history (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    timestamp    INTEGER NOT NULL,
+    kind         TEXT NOT NULL,
+    source       TEXT,
+    user         TEXT NOT NULL,
+    stake_index  INTEGER,
+    token_type   TEXT NOT NULL,
+    amount       INTEGER NOT NULL,
+    balance      INTEGER NOT NULL,
+    coverage_bps INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE TABLE IF NOT EXISTS user_history (
+    signature   TEXT NOT NULL,
+    event_index INTEGER NOT NULL,
+    slot        INTEGER NOT NULL,
+    timestamp   INTEGER NOT NULL,
+    user        TEXT NOT NULL,
+    kind        TEXT NOT NULL,
+    stake_index INTEGER,
+    tier        INTEGER,
+    token_type  TEXT,
+    amount      INTEGER NOT NULL,
+    rewards     INTEGER NOT NULL DEFAULT 0,
+    fee         INTEGER NOT NULL DEFAULT 0,
+    penalty     INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (signature, event_index, user)
+);
+
+-- Stake flows per tier and token with running totals, plus tier list changes
+CREATE TABLE IF NOT EXISTS tier_history (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    slot            INTEGER NOT NULL,
+    timestamp       INTEGER NOT NULL,
+    tier            INTEGER NOT NULL,
+    kind            TEXT NOT NULL,
+    user            TEXT,
+    token_type      TEXT,
+    amount_change   INTEGER NOT NULL DEFAULT 0,
+    total_staked    INTEGER,
+    active_stakes   INTEGER,
+    name            TEXT,
+    min_stake       INTEGER,
+    multiplier      INTEGER,
+    lock_seconds    INTEGER,
+    max_stakes      INTEGER,
+    max_total_stake INTEGER,
+    max_user_stake  INTEGER,
+    open_at         INTEGER,
+    close_at        INTEGER,
+    accrual_mode    TEXT,
+    rounding        TEXT,
+    post_lock       TEXT,
+    post_lock_rate  INTEGER,
+    unbonding_seconds INTEGER,
+    penalty_curve   TEXT,
+    penalty_rate    INTEGER,
+    penalty_steps   TEXT,
+    penalty_on_principal INTEGER,
+    penalty_clawback INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+
+-- Platform totals carried by every event, i.e. the state after each instruction
+CREATE TABLE IF NOT EXISTS platform_totals (
+    signature         TEXT NOT NULL,
+    event_index       INTEGER NOT NULL,
+    slot              INTEGER NOT NULL,
+    total_staked_sol  INTEGER NOT NULL,
+    total_staked_usdt INTEGER NOT NULL,
+    treasury_sol      INTEGER NOT NULL,
+    treasury_usdt     INTEGER NOT NULL,
+    liquid_staked_sol INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+
+CREATE INDEX IF NOT EXISTS user_history_by_user ON user_history (user, slot);
+CREATE INDEX IF NOT EXISTS tier_history_by_tier ON tier_history (tier, slot);
+";
+
+pub struct Indexer {
+    conn: Connection,
+}
+
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct IndexStats {
+    pub transactions: usize,
+    pub skipped: usize,
+    pub events: usize,
+}
+
+// Where an event sits, shared by every row it produces
+struct Position<'a> {
+    signature: &'a str,
+    event_index: i64,
+    slot: i64,
+}
+
+struct TierFlow<'a> {
+    tier: u8,
+    token_type: TokenType,
+    kind: &'a str,
+    user: &'a str,
+    amount_change: i64,
+    timestamp: i64,
+}
+
+#[derive(Default)]
+struct UserRow<'a> {
+    user: String,
+    kind: &'a str,
+    stake_index: Option<u64>,
+    tier: Option<u8>,
+    token_type: Option<TokenType>,
+    amount: u64,
+    rewards: u64,
+    fee: u64,
+    penalty: u64,
+    timestamp: i64,
+}
+
+impl Indexer {
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Indexes transactions in slot order. Transactions already in the
+    /// database are skipped, so dumps can be re-indexed safely; the stake
+    /// table assumes new transactions come after the ones already indexed.
+    pub fn index(&mut self, transactions: &mut [DumpedTransaction]) -> Result<IndexStats> {
+        transactions.sort_by_key(|transaction| transaction.slot);
+
+        let mut stats = IndexStats::default();
+        let db = self.conn.transaction()?;
+        for transaction in transactions.iter() {
+            let known = db
+                .query_row(
+                    "SELECT 1 FROM transactions WHERE signature = ?1",
+                    [&transaction.signature],
+                    |_| Ok(()),
+                )
+                .optional()?
+                .is_some();
+            if known {
+                stats.skipped += 1;
+                continue;
+            }
+
+            db.execute(
+                "INSERT INTO transactions (signature, slot, block_time, failed) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    transaction.signature,
+                    transaction.slot as i64,
+                    transaction.block_time,
+                    transaction.failed
+                ],
+            )?;
+            stats.transactions += 1;
+
+            // Events logged before a failure were rolled back with it
+            if transaction.failed {
+                continue;
+            }
+
+            // Programs built with `log-events` log their events instead of CPI-ing them
+            let mut events = parse_logs(&transaction.logs)?;
+            for data in &transaction.event_instructions {
+                events.extend(parse_event_instruction(data)?);
+            }
+            for (event_index, event) in events.iter().enumerate() {
+                let position = Position {
+                    signature: &transaction.signature,
+                    event_index: event_index as i64,
+                    slot: transaction.slot as i64,
+                };
+                db.execute(
+                    "INSERT INTO events (signature, event_index, slot, block_time, name) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        position.signature,
+                        position.event_index,
+                        position.slot,
+                        transaction.block_time,
+                        event.name()
+                    ],
+                )?;
+                let totals = event.totals();
+                db.execute(
+                    "INSERT INTO platform_totals (signature, event_index, slot, total_staked_sol, total_staked_usdt, treasury_sol, treasury_usdt, liquid_staked_sol)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        position.signature,
+                        position.event_index,
+                        position.slot,
+                        totals.total_staked_sol as i64,
+                        totals.total_staked_usdt as i64,
+                        totals.treasury_sol as i64,
+                        totals.treasury_usdt as i64,
+                        totals.liquid_staked_sol as i64
+                    ],
+                )?;
+                apply_event(&db, &position, event)?;
+                stats.events += 1;
+            }
+        }
+        db.commit()?;
+
+        Ok(stats)
+    }
+}
+
+fn apply_event(db: &Transaction, position: &Position, event: &SpaceMoneyEvent) -> Result<()> {
+    match event {
+        SpaceMoneyEvent::DepositedEvent(event) => {
+            let user = event.user.to_string();
+            let stake_index = event.stake_index;
+            db.execute(
+                "INSERT INTO stakes (user, stake_index, tier, token_type, amount, claimed_rewards, is_active)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 0, 1)",
+                params![
+                    user,
+                    stake_index as i64,
+                    event.tier,
+                    token_name(event.token_type),
+                    event.amount as i64
+                ],
+            )?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: user.clone(),
+                    kind: "deposit",
+                    stake_index: Some(stake_index),
+                    tier: Some(event.tier),
+                    token_type: Some(event.token_type),
+                    amount: event.amount,
+                    fee: event.fee,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+            insert_tier_flow(
+                db,
+                position,
+                TierFlow {
+                    tier: event.tier,
+                    token_type: event.token_type,
+                    kind: "deposit",
+                    user: &user,
+                    amount_change: event.amount as i64,
+                    timestamp: event.timestamp,
+                },
+            )?;
+        }
+        SpaceMoneyEvent::WithdrewEvent(event) => {
+            let user = event.user.to_string();
+            let tier = close_stake(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: user.clone(),
+                    kind: "withdraw",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    amount: event.principal,
+                    rewards: event.rewards,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+            if let Some(tier) = tier {
+                insert_tier_flow(
+                    db,
+                    position,
+                    TierFlow {
+                        tier,
+                        token_type: event.token_type,
+                        kind: "withdraw",
+                        user: &user,
+                        amount_change: -(event.principal as i64),
+                        timestamp: event.timestamp,
+                    },
+                )?;
+            }
+        }
+        SpaceMoneyEvent::ForceWithdrewEvent(event) => {
+            let user = event.user.to_string();
+            let tier = close_stake(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: user.clone(),
+                    kind: "force_withdraw",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    amount: event.principal,
+                    rewards: event.rewards_after_penalty,
+                    penalty: event.penalty,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+            if let Some(tier) = tier {
+                insert_tier_flow(
+                    db,
+                    position,
+                    TierFlow {
+                        tier,
+                        token_type: event.token_type,
+                        kind: "force_withdraw",
+                        user: &user,
+                        amount_change: -(event.principal as i64),
+                        timestamp: event.timestamp,
+                    },
+                )?;
+            }
+        }
+        // The stake stays active until complete_unstake's WithdrewEvent
+        SpaceMoneyEvent::UnstakeRequestedEvent(event) => {
+            let user = event.user.to_string();
+            let tier = stake_tier(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user,
+                    kind: "unstake_requested",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    amount: event.amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        // The stake closes when queued; the payout is recorded once filled
+        SpaceMoneyEvent::WithdrawalQueuedEvent(event) => {
+            let user = event.user.to_string();
+            let tier = close_stake(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: user.clone(),
+                    kind: "withdraw_queued",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    amount: event.principal,
+                    rewards: event.rewards,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+            if let Some(tier) = tier {
+                insert_tier_flow(
+                    db,
+                    position,
+                    TierFlow {
+                        tier,
+                        token_type: event.token_type,
+                        kind: "withdraw_queued",
+                        user: &user,
+                        amount_change: -(event.principal as i64),
+                        timestamp: event.timestamp,
+                    },
+                )?;
+            }
+        }
+        SpaceMoneyEvent::WithdrawalFilledEvent(event) => {
+            let user = event.user.to_string();
+            let tier = stake_tier(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user,
+                    kind: "queue_filled",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    amount: event.principal,
+                    rewards: event.rewards,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::ClaimedRewardsEvent(event) => {
+            let user = event.user.to_string();
+            db.execute(
+                "UPDATE stakes SET claimed_rewards = claimed_rewards + ?3 WHERE user = ?1 AND stake_index = ?2",
+                params![user, event.stake_index as i64, event.amount as i64],
+            )?;
+            let tier = stake_tier(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user,
+                    kind: "claim",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    rewards: event.amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::LoyaltyUpdatedEvent(event) => {
+            db.execute(
+                "INSERT OR REPLACE INTO loyalty (user, stake_days, level, bonus_bps, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    event.user.to_string(),
+                    event.stake_days as i64,
+                    event.level,
+                    event.bonus_bps,
+                    event.timestamp
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::CampaignCreatedEvent(event) => {
+            db.execute(
+                "INSERT INTO campaigns (id, start_time, end_time, tier_mask, boost_bps, budget_sol, budget_usdt)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    event.campaign_id as i64,
+                    event.start_time,
+                    event.end_time,
+                    event.tier_mask,
+                    event.boost_bps,
+                    event.budget_sol as i64,
+                    event.budget_usdt as i64
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::CampaignBoostedEvent(event) => {
+            let spent_column = match event.token_type {
+                TokenType::SOL => "spent_sol",
+                TokenType::USDT => "spent_usdt",
+            };
+            db.execute(
+                &format!(
+                    "UPDATE campaigns SET {spent_column} = ?2, boosted_deposits = boosted_deposits + 1
+                     WHERE id = ?1"
+                ),
+                params![event.campaign_id as i64, event.spent as i64],
+            )?;
+        }
+        SpaceMoneyEvent::CampaignEndedEvent(event) => {
+            db.execute(
+                "UPDATE campaigns SET spent_sol = ?2, spent_usdt = ?3, boosted_deposits = ?4,
+                 ended_at = ?5, end_reason = ?6 WHERE id = ?1",
+                params![
+                    event.campaign_id as i64,
+                    event.spent_sol as i64,
+                    event.spent_usdt as i64,
+                    event.boosted_deposits as i64,
+                    event.timestamp,
+                    campaign_end_reason_name(event.reason)
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::DistributorCreatedEvent(event) => {
+            let merkle_root: String = event
+                .merkle_root
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect();
+            db.execute(
+                "INSERT INTO distributors (id, merkle_root, token_type, total_amount, max_claims, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    event.distributor_id as i64,
+                    merkle_root,
+                    token_name(event.token_type),
+                    event.total_amount as i64,
+                    event.max_claims,
+                    event.timestamp
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::BonusClaimedEvent(event) => {
+            db.execute(
+                "UPDATE distributors SET claimed_amount = ?2, claimed_count = claimed_count + 1
+                 WHERE id = ?1",
+                params![event.distributor_id as i64, event.claimed_amount as i64],
+            )?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: event.claimant.to_string(),
+                    kind: "bonus_claim",
+                    token_type: Some(event.token_type),
+                    rewards: event.amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::DistributorClosedEvent(event) => {
+            db.execute(
+                "UPDATE distributors SET claimed_amount = ?2, claimed_count = ?3, closed_at = ?4,
+                 returned_amount = ?5 WHERE id = ?1",
+                params![
+                    event.distributor_id as i64,
+                    event.claimed_amount as i64,
+                    event.claimed_count,
+                    event.timestamp,
+                    event.returned_amount as i64
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::InsuranceContributedEvent(event) => {
+            db.execute(
+                "INSERT INTO insurance_history (signature, event_index, slot, timestamp, kind, source, user,
+                    token_type, amount, balance, coverage_bps)
+                 VALUES (?1, ?2, ?3, ?4, 'contribution', ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    insurance_source_name(event.source),
+                    event.user.to_string(),
+                    token_name(event.token_type),
+                    event.amount as i64,
+                    event.balance as i64,
+                    event.coverage_bps as i64
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::InsuranceBackstopEvent(event) => {
+            let user = event.user.to_string();
+            db.execute(
+                "INSERT INTO insurance_history (signature, event_index, slot, timestamp, kind, user,
+                    stake_index, token_type, amount, balance, coverage_bps)
+                 VALUES (?1, ?2, ?3, ?4, 'backstop', ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    user,
+                    event.stake_index as i64,
+                    token_name(event.token_type),
+                    event.shortfall as i64,
+                    event.balance as i64,
+                    event.coverage_bps as i64
+                ],
+            )?;
+            let tier = stake_tier(db, &user, event.stake_index)?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user,
+                    kind: "insurance_backstop",
+                    stake_index: Some(event.stake_index),
+                    tier,
+                    token_type: Some(event.token_type),
+                    amount: event.shortfall,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::ReferralCreditedEvent(event) => {
+            // Recorded against the referrer: the referred deposit and its fee share
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: event.referrer.to_string(),
+                    kind: "referral_credit",
+                    token_type: Some(event.token_type),
+                    amount: event.volume,
+                    rewards: event.reward,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::ReferralRewardsClaimedEvent(event) => {
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: event.referrer.to_string(),
+                    kind: "referral_claim",
+                    token_type: Some(event.token_type),
+                    rewards: event.amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::SplitStakeEvent(event) => {
+            let user = event.user.to_string();
+            // The new stake copies the source's tier and token; the source keeps the rest
+            db.execute(
+                "INSERT INTO stakes (user, stake_index, tier, token_type, amount, claimed_rewards, is_active)
+                 SELECT user, ?3, tier, token_type, ?4, ?5, 1 FROM stakes WHERE user = ?1 AND stake_index = ?2",
+                params![
+                    user,
+                    event.stake_index as i64,
+                    event.new_stake_index as i64,
+                    event.amount as i64,
+                    event.claimed_rewards as i64
+                ],
+            )?;
+            db.execute(
+                "UPDATE stakes SET amount = amount - ?3, claimed_rewards = claimed_rewards - ?4
+                 WHERE user = ?1 AND stake_index = ?2",
+                params![
+                    user,
+                    event.stake_index as i64,
+                    event.amount as i64,
+                    event.claimed_rewards as i64
+                ],
+            )?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: user.clone(),
+                    kind: "split",
+                    stake_index: Some(event.new_stake_index),
+                    tier: stake_tier(db, &user, event.stake_index)?,
+                    amount: event.amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::MergedStakesEvent(event) => {
+            let user = event.user.to_string();
+            for source_index in &event.source_indices {
+                close_stake(db, &user, *source_index)?;
+            }
+            db.execute(
+                "UPDATE stakes SET amount = ?3, claimed_rewards = ?4 WHERE user = ?1 AND stake_index = ?2",
+                params![
+                    user,
+                    event.target_index as i64,
+                    event.amount as i64,
+                    event.claimed_rewards as i64
+                ],
+            )?;
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: user.clone(),
+                    kind: "merge",
+                    stake_index: Some(event.target_index),
+                    tier: stake_tier(db, &user, event.target_index)?,
+                    amount: event.amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::TransferredStakeEvent(event) => {
+            let from = event.from.to_string();
+            let to = event.to.to_string();
+            let tier = close_stake(db, &from, event.stake_index)?;
+            if let Some(tier) = tier {
+                db.execute(
+                    "INSERT INTO stakes (user, stake_index, tier, token_type, amount, claimed_rewards, is_active)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, 1)",
+                    params![
+                        to,
+                        event.new_stake_index as i64,
+                        tier,
+                        token_name(event.token_type),
+                        event.amount as i64,
+                        event.claimed_rewards as i64
+                    ],
+                )?;
+            }
+            for (user, kind, stake_index) in [
+                (from, "transfer_out", event.stake_index),
+                (to, "transfer_in", event.new_stake_index),
+            ] {
+                insert_user_row(
+                    db,
+                    position,
+                    UserRow {
+                        user,
+                        kind,
+                        stake_index: Some(stake_index),
+                        tier,
+                        token_type: Some(event.token_type),
+                        amount: event.amount,
+                        timestamp: event.timestamp,
+                        ..Default::default()
+                    },
+                )?;
+            }
+        }
+        SpaceMoneyEvent::MintedReceiptEvent(event) => {
+            let user = event.user.to_string();
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    tier: stake_tier(db, &user, event.stake_index)?,
+                    user,
+                    kind: "receipt",
+                    stake_index: Some(event.stake_index),
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::MintedSpsolEvent(event) => {
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: event.user.to_string(),
+                    kind: "liquid_deposit",
+                    token_type: Some(TokenType::SOL),
+                    amount: event.sol_amount,
+                    fee: event.fee,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::RedeemedSpsolEvent(event) => {
+            insert_user_row(
+                db,
+                position,
+                UserRow {
+                    user: event.user.to_string(),
+                    kind: "liquid_redeem",
+                    token_type: Some(TokenType::SOL),
+                    amount: event.sol_amount,
+                    timestamp: event.timestamp,
+                    ..Default::default()
+                },
+            )?;
+        }
+        SpaceMoneyEvent::TierConfigUpdatedEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, min_stake,
+                    multiplier, lock_seconds, max_stakes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'config', ?6, ?7, ?8, ?9)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    event.min_stake as i64,
+                    event.multiplier as i64,
+                    event.lock_seconds,
+                    event.max_stakes as i64
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierAddedEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, name, min_stake,
+                    multiplier, lock_seconds, max_stakes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'added', ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    event.name,
+                    event.min_stake as i64,
+                    event.multiplier as i64,
+                    event.lock_seconds,
+                    event.max_stakes as i64
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierLimitsUpdatedEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, max_total_stake,
+                    max_user_stake, open_at, close_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'limits', ?6, ?7, ?8, ?9)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    event.max_total_stake as i64,
+                    event.max_user_stake as i64,
+                    event.open_at,
+                    event.close_at
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierAccrualUpdatedEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, accrual_mode, rounding)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'accrual', ?6, ?7)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    accrual_mode_name(event.accrual_mode),
+                    rounding_name(event.rounding)
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierPostLockUpdatedEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, post_lock, post_lock_rate)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'post_lock', ?6, ?7)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    post_lock_name(event.post_lock),
+                    event.post_lock_rate_bps
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierUnbondingUpdatedEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, unbonding_seconds)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'unbonding', ?6)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    event.unbonding_seconds
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierPenaltyUpdatedEvent(event) => {
+            // Steps as FROM_BPS:RATE_BPS pairs, the same form the CLI takes
+            let steps = event
+                .steps
+                .iter()
+                .map(|step| format!("{}:{}", step.from_bps, step.rate_bps))
+                .collect::<Vec<_>>()
+                .join(",");
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, penalty_curve, penalty_rate, penalty_steps, penalty_on_principal, penalty_clawback)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'penalty', ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier,
+                    penalty_curve_name(event.curve),
+                    event.rate_bps,
+                    steps,
+                    event.on_principal,
+                    event.clawback
+                ],
+            )?;
+        }
+        SpaceMoneyEvent::TierDisabledEvent(event) => {
+            db.execute(
+                "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 'disabled')",
+                params![
+                    position.signature,
+                    position.event_index,
+                    position.slot,
+                    event.timestamp,
+                    event.tier
+                ],
+            )?;
+        }
+        // Admin, liquid pool and validator events are only recorded in `events`
+        _ => {}
+    }
+    Ok(())
+}
+
+fn token_name(token_type: TokenType) -> &'static str {
+    match token_type {
+        TokenType::SOL => "SOL",
+        TokenType::USDT => "USDT",
+    }
+}
+
+fn accrual_mode_name(accrual_mode: AccrualMode) -> &'static str {
+    match accrual_mode {
+        AccrualMode::Daily => "daily",
+        AccrualMode::PerSecond => "per_second",
+    }
+}
+
+fn rounding_name(rounding: Rounding) -> &'static str {
+    match rounding {
+        Rounding::Down => "down",
+        Rounding::Nearest => "nearest",
+        Rounding::Up => "up",
+    }
+}
+
+fn post_lock_name(post_lock: PostLock) -> &'static str {
+    match post_lock {
+        PostLock::None => "none",
+        PostLock::AutoRoll => "auto_roll",
+        PostLock::Flexible => "flexible",
+    }
+}
+
+fn penalty_curve_name(curve: PenaltyCurve) -> &'static str {
+    match curve {
+        PenaltyCurve::Flat => "flat",
+        PenaltyCurve::Linear => "linear",
+        PenaltyCurve::Step => "step",
+    }
+}
+
+fn campaign_end_reason_name(reason: CampaignEndReason) -> &'static str {
+    match reason {
+        CampaignEndReason::Expired => "expired",
+        CampaignEndReason::BudgetExhausted => "budget_exhausted",
+        CampaignEndReason::Cancelled => "cancelled",
+    }
+}
+
+fn insurance_source_name(source: InsuranceSource) -> &'static str {
+    match source {
+        InsuranceSource::DepositFee => "deposit_fee",
+        InsuranceSource::Penalty => "penalty",
+    }
+}
+
+// None when the stake predates the indexed history
+fn stake_tier(db: &Transaction, user: &str, stake_index: u64) -> Result<Option<u8>> {
+    Ok(db
+        .query_row(
+            "SELECT tier FROM stakes WHERE user = ?1 AND stake_index = ?2",
+            params![user, stake_index as i64],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+fn close_stake(db: &Transaction, user: &str, stake_index: u64) -> Result<Option<u8>> {
+    db.execute(
+        "UPDATE stakes SET is_active = 0 WHERE user = ?1 AND stake_index = ?2",
+        params![user, stake_index as i64],
+    )?;
+    stake_tier(db, user, stake_index)
+}
+
+fn tier_totals(db: &Transaction, tier: u8, token_type: TokenType) -> Result<(i64, i64)> {
+    Ok(db.query_row(
+        "SELECT COALESCE(SUM(amount), 0), COUNT(*) FROM stakes
+         WHERE tier = ?1 AND token_type = ?2 AND is_active = 1",
+        params![tier, token_name(token_type)],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?)
+}
+
+fn insert_user_row(db: &Transaction, position: &Position, row: UserRow) -> Result<()> {
+    db.execute(
+        "INSERT INTO user_history (signature, event_index, slot, timestamp, user, kind, stake_index, tier,
+            token_type, amount, rewards, fee, penalty)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            position.signature,
+            position.event_index,
+            position.slot,
+            row.timestamp,
+            row.user,
+            row.kind,
+            row.stake_index.map(|index| index as i64),
+            row.tier,
+            row.token_type.map(token_name),
+            row.amount as i64,
+            row.rewards as i64,
+            row.fee as i64,
+            row.penalty as i64
+        ],
+    )?;
+    Ok(())
+}
+
+// Runs after the stakes table is updated, so totals include this event
+fn insert_tier_flow(db: &Transaction, position: &Position, flow: TierFlow) -> Result<()> {
+    let (total_staked, active_stakes) = tier_totals(db, flow.tier, flow.token_type)?;
+    db.execute(
+        "INSERT INTO tier_history (signature, event_index, slot, timestamp, tier, kind, user, token_type,
+            amount_change, total_staked, active_stakes)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            position.signature,
+            position.event_index,
+            position.slot,
+            flow.timestamp,
+            flow.tier,
+            flow.kind,
+            flow.user,
+            token_name(flow.token_type),
+            flow.amount_change,
+            total_staked,
+            active_stakes
+        ],
+    )?;
+    Ok(())
+}
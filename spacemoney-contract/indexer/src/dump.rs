@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::Value;
+use spacemoney_events::{event_authority, PROGRAM_ID};
+
+/// The parts of a transaction the indexer needs.
+pub struct DumpedTransaction {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub failed: bool,
+    pub logs: Vec<String>,
+    /// Data of the event self-CPIs among the inner instructions
+    pub event_instructions: Vec<Vec<u8>>,
+}
+
+/// Reads transactions from a JSON dump. The file may hold one record, an
+/// array of records or one record per line, where a record is either a
+/// `getTransaction` result or a block (`solana block <slot> --output json`,
+/// or a `getBlock` result with a `slot` field added). Transactions must use
+/// the `json` encoding so their signatures and inner instructions are readable.
+pub fn load_dump(path: &Path) -> Result<Vec<DumpedTransaction>> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+    let records: Vec<Value> = match serde_json::from_str::<Value>(&contents) {
+        Ok(Value::Array(records)) => records,
+        Ok(record) => vec![record],
+        Err(_) => contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("parsing {}", path.display()))?,
+    };
+
+    let mut transactions = Vec::new();
+    for record in &records {
+        parse_record(record, &mut transactions)
+            .with_context(|| format!("in {}", path.display()))?;
+    }
+    Ok(transactions)
+}
+
+fn parse_record(record: &Value, out: &mut Vec<DumpedTransaction>) -> Result<()> {
+    let slot = record["slot"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("record has no slot"))?;
+    let block_time = record["blockTime"].as_i64();
+
+    match record["transactions"].as_array() {
+        Some(block_transactions) => {
+            for transaction in block_transactions {
+                out.push(parse_transaction(transaction, slot, block_time)?);
+            }
+        }
+        None => out.push(parse_transaction(record, slot, block_time)?),
+    }
+    Ok(())
+}
+
+fn parse_transaction(
+    value: &Value,
+    slot: u64,
+    block_time: Option<i64>,
+) -> Result<DumpedTransaction> {
+    let signature = match value["transaction"]["signatures"][0].as_str() {
+        Some(signature) => signature.to_string(),
+        None => bail!("transaction in slot {slot} has no signature; dump with json encoding"),
+    };
+    let meta = &value["meta"];
+    let logs = meta["logMessages"]
+        .as_array()
+        .map(|logs| {
+            logs.iter()
+                .filter_map(|line| line.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(DumpedTransaction {
+        signature,
+        slot,
+        block_time,
+        failed: !meta["err"].is_null(),
+        logs,
+        event_instructions: event_instructions(value)?,
+    })
+}
+
+// Inner instructions that invoke SpaceMoney signed by its event authority
+fn event_instructions(value: &Value) -> Result<Vec<Vec<u8>>> {
+    let meta = &value["meta"];
+
+    // Static keys, then keys loaded from lookup tables (writable first)
+    let mut account_keys: Vec<&str> = Vec::new();
+    for key in value["transaction"]["message"]["accountKeys"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .chain(
+            meta["loadedAddresses"]["writable"]
+                .as_array()
+                .into_iter()
+                .flatten(),
+        )
+        .chain(
+            meta["loadedAddresses"]["readonly"]
+                .as_array()
+                .into_iter()
+                .flatten(),
+        )
+    {
+        // jsonParsed encoding wraps each key in an object
+        account_keys.push(key.as_str().or(key["pubkey"].as_str()).unwrap_or_default());
+    }
+
+    let program_id = PROGRAM_ID.to_string();
+    let event_authority = event_authority().to_string();
+    let key_at = |index: &Value| {
+        index
+            .as_u64()
+            .and_then(|index| account_keys.get(index as usize).copied())
+    };
+
+    let mut events = Vec::new();
+    for group in meta["innerInstructions"].as_array().into_iter().flatten() {
+        for instruction in group["instructions"].as_array().into_iter().flatten() {
+            if key_at(&instruction["programIdIndex"]) != Some(program_id.as_str())
+                || key_at(&instruction["accounts"][0]) != Some(event_authority.as_str())
+            {
+                continue;
+            }
+            let data = instruction["data"]
+                .as_str()
+                .ok_or_else(|| anyhow!("inner instruction has no data"))?;
+            events.push(
+                bs58::decode(data)
+                    .into_vec()
+                    .context("decoding inner instruction data")?,
+            );
+        }
+    }
+    Ok(events)
+}
@@ -0,0 +1,5 @@
+pub mod db;
+pub mod dump;
+
+pub use db::{IndexStats, Indexer};
+pub use dump::{load_dump, DumpedTransaction};
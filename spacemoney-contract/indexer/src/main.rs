@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use spacemoney_indexer::{load_dump, Indexer};
+
+#[derive(Parser)]
+#[command(
+    name = "spacemoney-indexer",
+    version,
+    about = "Index SpaceMoney events from transaction dumps into SQLite"
+)]
+struct Cli {
+    /// SQLite database to create or update
+    #[arg(long, short = 'd', default_value = "spacemoney.sqlite")]
+    database: PathBuf,
+
+    /// JSON dumps of transactions or blocks
+    #[arg(required = true)]
+    dumps: Vec<PathBuf>,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    let mut transactions = Vec::new();
+    for path in &cli.dumps {
+        transactions.extend(load_dump(path)?);
+    }
+
+    let mut indexer = Indexer::open(&cli.database)?;
+    let stats = indexer.index(&mut transactions)?;
+    println!(
+        "Indexed {} transactions ({} already indexed), {} events into {}",
+        stats.transactions,
+        stats.skipped,
+        stats.events,
+        cli.database.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use std::io::Write;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::json;
+use spacemoney_events::*;
+use spacemoney_indexer::{load_dump, Indexer};
+
+fn record(signature: &str, slot: u64, err: bool, mut meta: serde_json::Value) -> serde_json::Value {
+    meta["err"] = if err {
+        json!({ "InstructionError": [0, "Custom"] })
+    } else {
+        json!(null)
+    };
+    json!({
+        "slot": slot,
+        "blockTime": 1_700_000_000 + slot as i64,
+        "transaction": {
+            "signatures": [signature],
+            "message": {
+                "accountKeys": [
+                    Pubkey::new_unique().to_string(),
+                    PROGRAM_ID.to_string(),
+                    event_authority().to_string(),
+                ],
+            },
+        },
+        "meta": meta,
+    })
+}
+
+// An event delivered through the self-CPI, as the default build does
+fn cpi_event(event: &[u8]) -> serde_json::Value {
+    let mut data = anchor_lang::event::EVENT_IX_TAG_LE.to_vec();
+    data.extend_from_slice(event);
+    json!({
+        "logMessages": [],
+        "innerInstructions": [{
+            "index": 0,
+            "instructions": [{
+                "programIdIndex": 1,
+                "accounts": [2],
+                "data": bs58::encode(data).into_string(),
+            }],
+        }],
+    })
+}
+
+// An event written to the logs by a `log-events` build
+fn logged_event(event: &[u8]) -> serde_json::Value {
+    let id = PROGRAM_ID;
+    json!({
+        "logMessages": [
+            format!("Program {id} invoke [1]"),
+            format!("Program data: {}", STANDARD.encode(event)),
+            format!("Program {id} success"),
+        ],
+    })
+}
+
+fn totals(total_staked_sol: u64) -> PlatformTotals {
+    PlatformTotals {
+        total_staked_sol,
+        total_staked_usdt: 0,
+        treasury_sol: 200_000_000,
+        treasury_usdt: 0,
+        liquid_staked_sol: 0,
+    }
+}
+
+#[test]
+fn builds_user_and_tier_histories() {
+    let user = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let deposit = DepositedEvent {
+        user,
+        amount: 9_800_000_000,
+        tier: 1,
+        token_type: TokenType::SOL,
+        fee: 200_000_000,
+        lock_until: 1_800_000_000,
+        timestamp: 1_700_000_001,
+        stake_index: 0,
+        active_stakes: 1,
+        loyalty_bonus_bps: 0,
+        campaign_boost_bps: 0,
+        totals: totals(9_800_000_000),
+    }
+    .data();
+    let split = SplitStakeEvent {
+        user,
+        stake_index: 0,
+        new_stake_index: 1,
+        amount: 4_000_000_000,
+        claimed_rewards: 0,
+        timestamp: 1_700_000_002,
+        active_stakes: 2,
+        totals: totals(9_800_000_000),
+    }
+    .data();
+    let transfer = TransferredStakeEvent {
+        from: user,
+        to: recipient,
+        stake_index: 1,
+        new_stake_index: 0,
+        amount: 4_000_000_000,
+        claimed_rewards: 0,
+        token_type: TokenType::SOL,
+        timestamp: 1_700_000_003,
+        from_active_stakes: 1,
+        to_active_stakes: 1,
+        totals: totals(9_800_000_000),
+    }
+    .data();
+    let withdraw = WithdrewEvent {
+        user: recipient,
+        stake_index: 0,
+        principal: 4_000_000_000,
+        rewards: 720_000_000,
+        token_type: TokenType::SOL,
+        timestamp: 1_700_000_004,
+        active_stakes: 0,
+        totals: totals(5_800_000_000),
+    }
+    .data();
+
+    // Out of slot order, one failed transaction, a repeated record, and one
+    // event from a `log-events` build
+    let records = json!([
+        record("sig4", 4, false, logged_event(&withdraw)),
+        record("sig1", 1, false, cpi_event(&deposit)),
+        record("sig2", 2, false, cpi_event(&split)),
+        record("failed", 3, true, cpi_event(&deposit)),
+        record("sig3", 3, false, cpi_event(&transfer)),
+        record("sig1", 1, false, cpi_event(&deposit)),
+    ]);
+    let mut file = tempfile();
+    writeln!(file.1, "{records}").unwrap();
+
+    let mut transactions = load_dump(&file.0).unwrap();
+    let mut indexer = Indexer::in_memory().unwrap();
+    let stats = indexer.index(&mut transactions).unwrap();
+    assert_eq!(stats.transactions, 5);
+    assert_eq!(stats.skipped, 1);
+    assert_eq!(stats.events, 4);
+
+    let db = indexer.connection();
+    let kinds: Vec<(String, String)> = db
+        .prepare("SELECT user, kind FROM user_history ORDER BY slot, kind")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let (user, recipient) = (user.to_string(), recipient.to_string());
+    assert_eq!(
+        kinds,
+        vec![
+            (user.clone(), "deposit".to_string()),
+            (user.clone(), "split".to_string()),
+            (recipient.clone(), "transfer_in".to_string()),
+            (user.clone(), "transfer_out".to_string()),
+            (recipient.clone(), "withdraw".to_string()),
+        ]
+    );
+
+    // Tier 1 gained 9.8 SOL and lost the 4 SOL withdrawn by the recipient
+    let tier_rows: Vec<(String, i64, i64, i64)> = db
+        .prepare("SELECT kind, amount_change, total_staked, active_stakes FROM tier_history WHERE tier = 1 ORDER BY slot")
+        .unwrap()
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        tier_rows,
+        vec![
+            ("deposit".to_string(), 9_800_000_000, 9_800_000_000, 1),
+            ("withdraw".to_string(), -4_000_000_000, 5_800_000_000, 1),
+        ]
+    );
+
+    // The platform totals snapshot follows the events
+    let staked: Vec<i64> = db
+        .prepare("SELECT total_staked_sol FROM platform_totals ORDER BY slot")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        staked,
+        vec![9_800_000_000, 9_800_000_000, 9_800_000_000, 5_800_000_000]
+    );
+}
+
+fn tempfile() -> (std::path::PathBuf, std::fs::File) {
+    let path = std::env::temp_dir().join(format!("spacemoney-indexer-{}.json", std::process::id()));
+    let file = std::fs::File::create(&path).unwrap();
+    (path, file)
+}
@@ -0,0 +1,88 @@
+use crate::constants::*;
+use crate::rewards::{calculate_accrued_rewards, calculate_daily_reward};
+use crate::{MathError, MathResult};
+
+// How rewards build up between deposit and the end of the lock
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AccrualMode {
+    // Whole days only, so nothing is earned until the first day has passed
+    #[default]
+    Daily,
+    // Every second earns its share of the daily reward
+    PerSecond,
+}
+
+// How the single division in per-second accrual is rounded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    #[default]
+    Down,
+    // Halves round up
+    Nearest,
+    Up,
+}
+
+impl Rounding {
+    // numerator / denominator, rounded once
+    pub fn divide(self, numerator: u128, denominator: u128) -> MathResult<u128> {
+        if denominator == 0 {
+            return Err(MathError::Calculation);
+        }
+
+        let adjustment = match self {
+            Rounding::Down => 0,
+            Rounding::Nearest => denominator / 2,
+            Rounding::Up => denominator - 1,
+        };
+
+        numerator
+            .checked_add(adjustment)
+            .map(|v| v / denominator)
+            .ok_or(MathError::Calculation)
+    }
+}
+
+// Formula: daily reward × elapsed_seconds / SECONDS_PER_DAY, with the product
+// kept in u128 and rounded once. Prorating the same daily reward as daily
+// accrual means both modes agree at every whole day, lock expiry included.
+pub fn calculate_per_second_rewards(
+    principal: u64,
+    multiplier: u64,
+    elapsed_seconds: i64,
+    rounding: Rounding,
+) -> MathResult<u64> {
+    if elapsed_seconds <= 0 {
+        return Ok(0);
+    }
+
+    let daily_reward = calculate_daily_reward(principal, multiplier)?;
+    let numerator = (daily_reward as u128)
+        .checked_mul(elapsed_seconds as u128)
+        .ok_or(MathError::Calculation)?;
+    let rewards = rounding.divide(numerator, SECONDS_PER_DAY as u128)?;
+
+    u64::try_from(rewards).map_err(|_| MathError::Overflow)
+}
+
+// Rewards accrued between `start_time` and `current_time` under `mode`.
+// Uncapped; callers cap at the lock period's rewards.
+pub fn calculate_accrued_rewards_with(
+    mode: AccrualMode,
+    rounding: Rounding,
+    principal: u64,
+    multiplier: u64,
+    start_time: i64,
+    current_time: i64,
+) -> MathResult<u64> {
+    match mode {
+        AccrualMode::Daily => {
+            calculate_accrued_rewards(principal, multiplier, start_time, current_time)
+        }
+        AccrualMode::PerSecond => {
+            let elapsed_seconds = current_time
+                .checked_sub(start_time)
+                .ok_or(MathError::Calculation)?;
+            calculate_per_second_rewards(principal, multiplier, elapsed_seconds, rounding)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+// Fee constants (basis points)
+pub const DEPOSIT_FEE_BPS: u64 = 200; // 2%
+pub const FORCE_WITHDRAW_PENALTY_BPS: u64 = 2000; // 20%
+pub const BPS_DENOMINATOR: u64 = 10000;
+// Most steps a tier's penalty schedule can have
+pub const MAX_PENALTY_STEPS: usize = 4;
+
+// Reward constants
+pub const DAILY_YIELD_BPS: u64 = 100; // 1% daily
+pub const SECONDS_PER_DAY: i64 = 86400;
+// How long an auto-rolled stake stays withdrawable after each lock ends
+pub const AUTO_ROLL_WINDOW_SECONDS: i64 = SECONDS_PER_DAY;
+
+// Loyalty constants: the top level and the most it adds to a stake's rewards
+pub const MAX_LOYALTY_STAKE_DAYS: u64 = 365;
+pub const MAX_LOYALTY_BONUS_BPS: u64 = 1500; // 15%
+// The amount that earns one stake-day per day held, per token
+pub const LOYALTY_UNIT_SOL: u64 = 1_000_000_000; // 1 SOL
+pub const LOYALTY_UNIT_USDT: u64 = 100_000_000; // 100 USDT
+
+// Campaign constants: the largest boost a promotional campaign can give
+pub const MAX_CAMPAIGN_BOOST_BPS: u64 = 10000; // 2x
+
+// Tier constants
+pub const BOOT_MIN_STAKE: u64 = 1_000_000_000; // 1 SOL
+pub const SYMBIOTIC_MIN_STAKE: u64 = 5_000_000_000; // 5 SOL
+pub const SPACE_MIN_STAKE: u64 = 25_000_000_000; // 25 SOL
+
+pub const BOOT_MULTIPLIER: u64 = 1;
+pub const SYMBIOTIC_MULTIPLIER: u64 = 2;
+pub const SPACE_MULTIPLIER: u64 = 3;
+
+pub const BOOT_LOCK_DAYS: i64 = 30;
+pub const SYMBIOTIC_LOCK_DAYS: i64 = 90;
+pub const SPACE_LOCK_DAYS: i64 = 180;
@@ -0,0 +1,43 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod accrual;
+pub mod constants;
+pub mod loyalty;
+pub mod penalty;
+pub mod projection;
+pub mod rewards;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use accrual::*;
+pub use constants::*;
+pub use loyalty::*;
+pub use penalty::*;
+pub use projection::*;
+pub use rewards::*;
+
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    // An amount left the u64 range
+    Overflow,
+    // Intermediate arithmetic failed or the inputs are inconsistent
+    Calculation,
+    InvalidTier,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::Overflow => write!(f, "numerical overflow"),
+            MathError::Calculation => write!(f, "calculation error"),
+            MathError::InvalidTier => write!(f, "invalid tier"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MathError {}
+
+pub type MathResult<T> = Result<T, MathError>;
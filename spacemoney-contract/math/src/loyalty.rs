@@ -0,0 +1,74 @@
+use crate::constants::*;
+use crate::{MathError, MathResult};
+
+// A loyalty level: the stake-days needed and the bonus it grants
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoyaltyLevel {
+    pub min_stake_days: u64,
+    pub bonus_bps: u64,
+}
+
+// Levels 1-4, lowest first; level 0 has no bonus
+pub const LOYALTY_LEVELS: [LoyaltyLevel; 4] = [
+    LoyaltyLevel {
+        min_stake_days: 30,
+        bonus_bps: 250,
+    },
+    LoyaltyLevel {
+        min_stake_days: 90,
+        bonus_bps: 500,
+    },
+    LoyaltyLevel {
+        min_stake_days: 180,
+        bonus_bps: 1000,
+    },
+    LoyaltyLevel {
+        min_stake_days: MAX_LOYALTY_STAKE_DAYS,
+        bonus_bps: MAX_LOYALTY_BONUS_BPS,
+    },
+];
+
+// Days a stake was held, weighted by its amount in loyalty units (see
+// LOYALTY_UNIT_SOL) and rounded down once at the end. The credit is linear in
+// the amount, so splitting or merging a stake leaves it unchanged.
+pub fn calculate_stake_days(amount: u64, unit: u64, deposited_at: i64, closed_at: i64) -> u64 {
+    if closed_at <= deposited_at || unit == 0 {
+        return 0;
+    }
+    let held_seconds = (closed_at - deposited_at) as u128;
+    let stake_days = (amount as u128) * held_seconds / (unit as u128 * SECONDS_PER_DAY as u128);
+    u64::try_from(stake_days).unwrap_or(u64::MAX)
+}
+
+// The highest level `stake_days` reaches, 0 when below the first
+pub fn loyalty_level(stake_days: u64) -> u8 {
+    LOYALTY_LEVELS
+        .iter()
+        .filter(|level| stake_days >= level.min_stake_days)
+        .count() as u8
+}
+
+// Extra reward rate for new stakes, in bps of what the stake would earn
+pub fn loyalty_bonus_bps(stake_days: u64) -> u64 {
+    match loyalty_level(stake_days) {
+        0 => 0,
+        level => LOYALTY_LEVELS[level as usize - 1].bonus_bps,
+    }
+}
+
+// Stake-days still needed for the next level, 0 at the top
+pub fn stake_days_to_next_level(stake_days: u64) -> u64 {
+    LOYALTY_LEVELS
+        .iter()
+        .find(|level| stake_days < level.min_stake_days)
+        .map_or(0, |level| level.min_stake_days - stake_days)
+}
+
+// `rewards` raised by a loyalty bonus, rounded down
+pub fn apply_loyalty_bonus(rewards: u64, bonus_bps: u64) -> MathResult<u64> {
+    let boosted = (rewards as u128)
+        .checked_mul(BPS_DENOMINATOR as u128 + bonus_bps as u128)
+        .map(|v| v / BPS_DENOMINATOR as u128)
+        .ok_or(MathError::Calculation)?;
+    u64::try_from(boosted).map_err(|_| MathError::Overflow)
+}
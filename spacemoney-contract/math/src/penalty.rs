@@ -0,0 +1,136 @@
+use crate::constants::*;
+use crate::{MathError, MathResult};
+
+// How the early-exit rate moves through a lock
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PenaltyCurve {
+    // FORCE_WITHDRAW_PENALTY_BPS for the whole lock
+    #[default]
+    Flat,
+    // rate_bps at the start of the lock, falling linearly to 0 at its end
+    Linear,
+    // The rate of the last step the lock has reached
+    Step,
+}
+
+// A step's rate applies once `from_bps` of the lock has elapsed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PenaltyStep {
+    pub from_bps: u64,
+    pub rate_bps: u64,
+}
+
+// The default is the original flat 20% of unclaimed rewards
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PenaltyPolicy {
+    pub curve: PenaltyCurve,
+    // Starting rate of a Linear curve
+    pub rate_bps: u64,
+    // A Step curve's schedule, in ascending `from_bps`; only the first
+    // `step_count` are used
+    pub steps: [PenaltyStep; MAX_PENALTY_STEPS],
+    pub step_count: usize,
+    // Charge the rate on principal too
+    pub on_principal: bool,
+    // Take back the rate on rewards already claimed from the stake
+    pub clawback_claimed: bool,
+}
+
+// What an early exit forfeits, by what it was charged on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PenaltyBreakdown {
+    pub rate_bps: u64,
+    pub on_rewards: u64,
+    pub on_principal: u64,
+    pub clawback: u64,
+}
+
+impl PenaltyBreakdown {
+    pub fn total(&self) -> MathResult<u64> {
+        self.on_rewards
+            .checked_add(self.on_principal)
+            .and_then(|v| v.checked_add(self.clawback))
+            .ok_or(MathError::Overflow)
+    }
+}
+
+impl PenaltyPolicy {
+    // The rate in bps `elapsed` seconds into a lock of `lock_length` seconds,
+    // never above 100%
+    pub fn rate_at(&self, elapsed: i64, lock_length: i64) -> MathResult<u64> {
+        if lock_length <= 0 {
+            return Ok(0);
+        }
+        let elapsed = elapsed.clamp(0, lock_length);
+
+        let rate = match self.curve {
+            PenaltyCurve::Flat => FORCE_WITHDRAW_PENALTY_BPS,
+            PenaltyCurve::Linear => {
+                let rate = (self.rate_bps as u128)
+                    .checked_mul((lock_length - elapsed) as u128)
+                    .ok_or(MathError::Calculation)?
+                    / lock_length as u128;
+                u64::try_from(rate).map_err(|_| MathError::Overflow)?
+            }
+            PenaltyCurve::Step => {
+                let elapsed_bps =
+                    (elapsed as u128) * (BPS_DENOMINATOR as u128) / lock_length as u128;
+                self.steps[..self.step_count.min(MAX_PENALTY_STEPS)]
+                    .iter()
+                    .rev()
+                    .find(|step| step.from_bps as u128 <= elapsed_bps)
+                    .map_or(0, |step| step.rate_bps)
+            }
+        };
+
+        Ok(rate.min(BPS_DENOMINATOR))
+    }
+}
+
+fn apply_rate(amount: u64, rate_bps: u64) -> MathResult<u64> {
+    let penalty = (amount as u128)
+        .checked_mul(rate_bps as u128)
+        .ok_or(MathError::Calculation)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(penalty).map_err(|_| MathError::Overflow)
+}
+
+// Formula: the rate at this point of the lock × unclaimed rewards, plus the
+// same rate × principal and × claimed rewards where the policy asks for it.
+// The total never exceeds principal plus unclaimed rewards, so a clawback
+// can take the payout to zero but no further.
+pub fn calculate_force_withdraw_penalty(
+    policy: &PenaltyPolicy,
+    elapsed: i64,
+    lock_length: i64,
+    principal: u64,
+    unclaimed_rewards: u64,
+    claimed_rewards: u64,
+) -> MathResult<PenaltyBreakdown> {
+    let rate_bps = policy.rate_at(elapsed, lock_length)?;
+
+    let on_rewards = apply_rate(unclaimed_rewards, rate_bps)?;
+    let on_principal = if policy.on_principal {
+        apply_rate(principal, rate_bps)?
+    } else {
+        0
+    };
+
+    let clawback = if policy.clawback_claimed {
+        let payable = principal
+            .checked_add(unclaimed_rewards)
+            .and_then(|v| v.checked_sub(on_rewards))
+            .and_then(|v| v.checked_sub(on_principal))
+            .ok_or(MathError::Calculation)?;
+        apply_rate(claimed_rewards, rate_bps)?.min(payable)
+    } else {
+        0
+    };
+
+    Ok(PenaltyBreakdown {
+        rate_bps,
+        on_rewards,
+        on_principal,
+        clawback,
+    })
+}
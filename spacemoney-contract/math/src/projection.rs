@@ -0,0 +1,265 @@
+use crate::accrual::*;
+use crate::constants::*;
+use crate::loyalty::*;
+use crate::penalty::*;
+use crate::rewards::*;
+use crate::{MathError, MathResult};
+
+// The reward-relevant parts of a stake, independent of how it is stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakeTerms {
+    pub amount: u64,
+    pub multiplier: u64,
+    pub lock_days: i64,
+    pub deposited_at: i64,
+    pub lock_until: i64,
+    pub claimed_rewards: u64,
+    pub accrual: AccrualMode,
+    pub rounding: Rounding,
+    pub post_lock: PostLock,
+    // Share of the locked rate a Flexible stake keeps earning, in bps
+    pub post_lock_rate_bps: u64,
+    // When an unstake was requested; the stake's schedule and rewards stop there
+    pub stopped_at: Option<i64>,
+    // What an early exit costs
+    pub penalty: PenaltyPolicy,
+    // Extra share of every reward, fixed by the owner's loyalty level at deposit
+    pub loyalty_bonus_bps: u64,
+    // Extra share of the first lock's rewards from a promotional campaign
+    pub campaign_boost_bps: u64,
+}
+
+// What a stake does once its lock has run out
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PostLock {
+    // Rewards stop at the end of the lock
+    #[default]
+    None,
+    // The stake relocks for the same length at every expiry, and can only be
+    // withdrawn in the AUTO_ROLL_WINDOW_SECONDS after one
+    AutoRoll,
+    // Rewards continue at post_lock_rate_bps of the locked rate
+    Flexible,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForceWithdrawQuote {
+    pub unclaimed_rewards: u64,
+    // Everything forfeited: the breakdown's parts added up
+    pub penalty: u64,
+    pub breakdown: PenaltyBreakdown,
+    pub rewards_after_penalty: u64,
+    pub total_withdrawal: u64,
+}
+
+impl StakeTerms {
+    pub fn from_tier(
+        amount: u64,
+        tier: u8,
+        deposited_at: i64,
+        lock_until: i64,
+        claimed_rewards: u64,
+    ) -> MathResult<Self> {
+        let (_, multiplier, lock_days) = get_tier_config(tier)?;
+        Ok(Self {
+            amount,
+            multiplier,
+            lock_days,
+            deposited_at,
+            lock_until,
+            claimed_rewards,
+            accrual: AccrualMode::Daily,
+            rounding: Rounding::Down,
+            post_lock: PostLock::None,
+            post_lock_rate_bps: 0,
+            stopped_at: None,
+            penalty: PenaltyPolicy::default(),
+            loyalty_bonus_bps: 0,
+            campaign_boost_bps: 0,
+        })
+    }
+
+    pub fn is_locked_at(&self, timestamp: i64) -> bool {
+        let timestamp = self.stop(timestamp);
+        if self.completed_locks(timestamp) > 0 {
+            timestamp - self.current_lock_start(timestamp) >= AUTO_ROLL_WINDOW_SECONDS
+        } else {
+            timestamp < self.lock_until
+        }
+    }
+
+    // When the lock running at `timestamp` ends; auto-rolled stakes move on
+    // to the next lock at each expiry
+    pub fn lock_until_at(&self, timestamp: i64) -> i64 {
+        let timestamp = self.stop(timestamp);
+        if self.completed_locks(timestamp) > 0 {
+            self.current_lock_start(timestamp) + self.lock_length()
+        } else {
+            self.lock_until
+        }
+    }
+
+    fn stop(&self, timestamp: i64) -> i64 {
+        self.stopped_at
+            .map_or(timestamp, |stopped_at| timestamp.min(stopped_at))
+    }
+
+    fn lock_length(&self) -> i64 {
+        self.lock_until - self.deposited_at
+    }
+
+    // Locks an auto-rolled stake has run through by `timestamp`, 0 otherwise
+    fn completed_locks(&self, timestamp: i64) -> i64 {
+        if self.post_lock != PostLock::AutoRoll
+            || self.lock_length() <= 0
+            || timestamp < self.lock_until
+        {
+            return 0;
+        }
+        (timestamp - self.deposited_at) / self.lock_length()
+    }
+
+    fn current_lock_start(&self, timestamp: i64) -> i64 {
+        self.deposited_at + self.completed_locks(timestamp) * self.lock_length()
+    }
+
+    // Rewards for the full (first) lock period, which both accrual modes
+    // reach at expiry
+    pub fn max_rewards(&self) -> MathResult<u64> {
+        apply_loyalty_bonus(self.base_rewards()?, self.loyalty_bonus_bps)?
+            .checked_add(self.campaign_rewards()?)
+            .ok_or(MathError::Overflow)
+    }
+
+    // What the campaign boost adds over the first lock; the campaign's budget
+    // reserves this much when the stake is made
+    pub fn campaign_rewards(&self) -> MathResult<u64> {
+        calculate_campaign_boost(self.base_rewards()?, self.campaign_boost_bps)
+    }
+
+    // One lock period's rewards before the loyalty bonus
+    fn base_rewards(&self) -> MathResult<u64> {
+        calculate_rewards(self.amount, self.multiplier, self.lock_days)
+    }
+
+    // Rewards earned by `timestamp`: each lock's accrual is capped at the full
+    // lock period, the post-lock policy decides what comes after, the
+    // loyalty bonus applies to the total and the campaign boost to the first lock
+    pub fn earned_at(&self, timestamp: i64) -> MathResult<u64> {
+        let first_lock = self
+            .accrued_between(self.deposited_at, self.stop(timestamp))?
+            .min(self.base_rewards()?);
+        apply_loyalty_bonus(self.base_earned_at(timestamp)?, self.loyalty_bonus_bps)?
+            .checked_add(calculate_campaign_boost(
+                first_lock,
+                self.campaign_boost_bps,
+            )?)
+            .ok_or(MathError::Overflow)
+    }
+
+    fn base_earned_at(&self, timestamp: i64) -> MathResult<u64> {
+        let timestamp = self.stop(timestamp);
+        let max_rewards = self.base_rewards()?;
+        let completed_locks = self.completed_locks(timestamp);
+        let accrued = self
+            .accrued_between(self.current_lock_start(timestamp), timestamp)?
+            .min(max_rewards);
+        let earned = max_rewards
+            .checked_mul(completed_locks as u64)
+            .and_then(|v| v.checked_add(accrued))
+            .ok_or(MathError::Overflow)?;
+
+        if self.post_lock != PostLock::Flexible || timestamp <= self.lock_until {
+            return Ok(earned);
+        }
+
+        let flexible_accrued = self.accrued_between(self.lock_until, timestamp)?;
+        let flexible = self.rounding.divide(
+            (flexible_accrued as u128) * (self.post_lock_rate_bps as u128),
+            BPS_DENOMINATOR as u128,
+        )?;
+        let flexible = u64::try_from(flexible).map_err(|_| MathError::Overflow)?;
+        earned.checked_add(flexible).ok_or(MathError::Overflow)
+    }
+
+    fn accrued_between(&self, start_time: i64, timestamp: i64) -> MathResult<u64> {
+        calculate_accrued_rewards_with(
+            self.accrual,
+            self.rounding,
+            self.amount,
+            self.multiplier,
+            start_time,
+            timestamp,
+        )
+    }
+
+    // What claim_rewards would pay out at `timestamp`
+    pub fn claimable_at(&self, timestamp: i64) -> MathResult<u64> {
+        self.earned_at(timestamp)?
+            .checked_sub(self.claimed_rewards)
+            .ok_or(MathError::Calculation)
+    }
+
+    // Rewards still owed for a single lock period
+    pub fn unclaimed_at_maturity(&self) -> MathResult<u64> {
+        self.max_rewards()?
+            .checked_sub(self.claimed_rewards)
+            .ok_or(MathError::Calculation)
+    }
+
+    // What withdraw_sol / withdraw_usdt pay on top of principal at `timestamp`,
+    // once unlocked. Stakes that keep earning after their lock pay what they've
+    // earned so far.
+    pub fn unclaimed_at_withdrawal(&self, timestamp: i64) -> MathResult<u64> {
+        match self.post_lock {
+            PostLock::None => self.unclaimed_at_maturity(),
+            PostLock::AutoRoll | PostLock::Flexible => self.claimable_at(timestamp),
+        }
+    }
+
+    // Principal plus rewards not yet claimed, as of `timestamp`
+    pub fn value_at(&self, timestamp: i64) -> MathResult<u64> {
+        self.amount
+            .checked_add(self.claimable_at(timestamp)?)
+            .ok_or(MathError::Overflow)
+    }
+
+    // What force_withdraw would pay at `timestamp`: principal plus unclaimed
+    // rewards less the early-exit penalty for that point of the running lock.
+    // Only meaningful while locked.
+    pub fn force_withdraw_at(&self, timestamp: i64) -> MathResult<ForceWithdrawQuote> {
+        let unclaimed_rewards = self
+            .earned_at(timestamp)?
+            .checked_sub(self.claimed_rewards)
+            .ok_or(MathError::Calculation)?;
+
+        let timestamp = self.stop(timestamp);
+        let lock_start = self.current_lock_start(timestamp);
+        let breakdown = calculate_force_withdraw_penalty(
+            &self.penalty,
+            timestamp - lock_start,
+            self.lock_until_at(timestamp) - lock_start,
+            self.amount,
+            unclaimed_rewards,
+            self.claimed_rewards,
+        )?;
+        let penalty = breakdown.total()?;
+        let rewards_after_penalty = unclaimed_rewards
+            .checked_sub(breakdown.on_rewards)
+            .ok_or(MathError::Calculation)?;
+
+        let total_withdrawal = self
+            .amount
+            .checked_add(unclaimed_rewards)
+            .and_then(|v| v.checked_sub(penalty))
+            .ok_or(MathError::Calculation)?;
+
+        Ok(ForceWithdrawQuote {
+            unclaimed_rewards,
+            penalty,
+            breakdown,
+            rewards_after_penalty,
+            total_withdrawal,
+        })
+    }
+}
@@ -0,0 +1,203 @@
+use crate::constants::*;
+use crate::{MathError, MathResult};
+
+pub fn calculate_deposit_fee(amount: u64) -> MathResult<u64> {
+    amount
+        .checked_mul(DEPOSIT_FEE_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .ok_or(MathError::Overflow)
+}
+
+pub fn calculate_net_deposit(amount: u64) -> MathResult<(u64, u64)> {
+    let fee = calculate_deposit_fee(amount)?;
+    let net_amount = amount.checked_sub(fee).ok_or(MathError::Overflow)?;
+    Ok((net_amount, fee))
+}
+
+// The referrer's cut of a deposit fee; the treasury keeps the rest
+pub fn calculate_referral_reward(fee: u64, share_bps: u64) -> MathResult<u64> {
+    if share_bps > BPS_DENOMINATOR {
+        return Err(MathError::Calculation);
+    }
+
+    fee.checked_mul(share_bps)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .ok_or(MathError::Overflow)
+}
+
+// The insurance fund's cut of a fee or penalty; the treasury keeps the rest
+pub fn calculate_insurance_share(amount: u64, share_bps: u64) -> MathResult<u64> {
+    if share_bps > BPS_DENOMINATOR {
+        return Err(MathError::Calculation);
+    }
+
+    let share = (amount as u128) * (share_bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(share).map_err(|_| MathError::Overflow)
+}
+
+// The insurance fund's balance as a share of what is staked, in basis points.
+// With nothing staked there is nothing to cover, which counts as fully covered.
+pub fn calculate_coverage_bps(fund_balance: u64, total_staked: u64) -> u64 {
+    if total_staked == 0 {
+        return BPS_DENOMINATOR;
+    }
+
+    let coverage = (fund_balance as u128) * (BPS_DENOMINATOR as u128) / (total_staked as u128);
+    u64::try_from(coverage).unwrap_or(u64::MAX)
+}
+
+// Extra rewards a campaign boost adds to `rewards`, rounded down
+pub fn calculate_campaign_boost(rewards: u64, boost_bps: u64) -> MathResult<u64> {
+    if boost_bps > MAX_CAMPAIGN_BOOST_BPS {
+        return Err(MathError::Calculation);
+    }
+
+    let boost = (rewards as u128) * (boost_bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(boost).map_err(|_| MathError::Overflow)
+}
+
+pub fn calculate_rewards(principal: u64, multiplier: u64, days_locked: i64) -> MathResult<u64> {
+    // Formula: principal × multiplier × 1% × days
+    let daily_reward = calculate_daily_reward(principal, multiplier)?;
+
+    daily_reward
+        .checked_mul(days_locked as u64)
+        .ok_or(MathError::Calculation)
+}
+
+pub fn calculate_daily_reward(principal: u64, multiplier: u64) -> MathResult<u64> {
+    // Breaking it down to avoid overflow
+    let base_daily = principal
+        .checked_mul(multiplier)
+        .ok_or(MathError::Calculation)?;
+
+    base_daily
+        .checked_mul(DAILY_YIELD_BPS)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .ok_or(MathError::Calculation)
+}
+
+// Share of claimed rewards attributable to a part earning part_daily out of total_daily.
+// Rounds down, so the remainder stays with the other part.
+pub fn calculate_claimed_share(claimed: u64, part_daily: u64, total_daily: u64) -> MathResult<u64> {
+    if total_daily == 0 {
+        return Ok(0);
+    }
+
+    let share = (claimed as u128)
+        .checked_mul(part_daily as u128)
+        .and_then(|v| v.checked_div(total_daily as u128))
+        .ok_or(MathError::Calculation)?;
+
+    u64::try_from(share).map_err(|_| MathError::Overflow)
+}
+
+pub fn calculate_accrued_rewards(
+    principal: u64,
+    multiplier: u64,
+    start_time: i64,
+    current_time: i64,
+) -> MathResult<u64> {
+    let elapsed_seconds = current_time
+        .checked_sub(start_time)
+        .ok_or(MathError::Calculation)?;
+
+    let elapsed_days = elapsed_seconds / SECONDS_PER_DAY;
+
+    if elapsed_days <= 0 {
+        return Ok(0);
+    }
+
+    calculate_rewards(principal, multiplier, elapsed_days)
+}
+
+// The liquid pool's share of staking yield collected from validators, pro rata
+// with locked stakes. The pool only grows by yield that actually reached the
+// vault, so the spSOL rate stays backed by SOL the platform holds.
+pub fn calculate_liquid_yield(
+    staking_yield: u64,
+    liquid_staked: u64,
+    total_staked: u64,
+) -> MathResult<u64> {
+    let pool = (liquid_staked as u128)
+        .checked_add(total_staked as u128)
+        .ok_or(MathError::Calculation)?;
+
+    if pool == 0 {
+        return Ok(0);
+    }
+
+    let share = (staking_yield as u128)
+        .checked_mul(liquid_staked as u128)
+        .and_then(|v| v.checked_div(pool))
+        .ok_or(MathError::Calculation)?;
+
+    u64::try_from(share).map_err(|_| MathError::Overflow)
+}
+
+pub fn calculate_spsol_to_mint(
+    sol_amount: u64,
+    liquid_staked: u64,
+    spsol_supply: u64,
+) -> MathResult<u64> {
+    // First depositor (or an emptied pool) mints 1:1
+    if spsol_supply == 0 || liquid_staked == 0 {
+        return Ok(sol_amount);
+    }
+
+    let spsol = (sol_amount as u128)
+        .checked_mul(spsol_supply as u128)
+        .and_then(|v| v.checked_div(liquid_staked as u128))
+        .ok_or(MathError::Calculation)?;
+
+    u64::try_from(spsol).map_err(|_| MathError::Overflow)
+}
+
+pub fn calculate_sol_for_spsol(
+    spsol_amount: u64,
+    liquid_staked: u64,
+    spsol_supply: u64,
+) -> MathResult<u64> {
+    if spsol_supply == 0 {
+        return Err(MathError::Calculation);
+    }
+
+    let sol = (spsol_amount as u128)
+        .checked_mul(liquid_staked as u128)
+        .and_then(|v| v.checked_div(spsol_supply as u128))
+        .ok_or(MathError::Calculation)?;
+
+    u64::try_from(sol).map_err(|_| MathError::Overflow)
+}
+
+// Per-validator delegation target: a share of staked SOL split evenly
+// across the validators that are not being removed
+pub fn calculate_validator_target(
+    total_staked: u64,
+    target_delegation_bps: u64,
+    validator_count: u64,
+) -> MathResult<u64> {
+    if validator_count == 0 {
+        return Ok(0);
+    }
+
+    total_staked
+        .checked_mul(target_delegation_bps)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+        .and_then(|v| v.checked_div(validator_count))
+        .ok_or(MathError::Calculation)
+}
+
+// (min_stake, multiplier, lock_days) for tier 0 = Boot, 1 = Symbiotic, 2 = Space
+pub fn get_tier_config(tier: u8) -> MathResult<(u64, u64, i64)> {
+    match tier {
+        0 => Ok((BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS)),
+        1 => Ok((
+            SYMBIOTIC_MIN_STAKE,
+            SYMBIOTIC_MULTIPLIER,
+            SYMBIOTIC_LOCK_DAYS,
+        )),
+        2 => Ok((SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS)),
+        _ => Err(MathError::InvalidTier),
+    }
+}
@@ -0,0 +1,67 @@
+use wasm_bindgen::prelude::*;
+
+use crate::{MathError, StakeTerms};
+
+fn to_js(err: MathError) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+fn terms(
+    amount: u64,
+    tier: u8,
+    deposited_at: i64,
+    lock_until: i64,
+    claimed_rewards: u64,
+) -> Result<StakeTerms, JsError> {
+    StakeTerms::from_tier(amount, tier, deposited_at, lock_until, claimed_rewards).map_err(to_js)
+}
+
+#[wasm_bindgen(js_name = valueAt)]
+pub fn value_at(
+    amount: u64,
+    tier: u8,
+    deposited_at: i64,
+    lock_until: i64,
+    claimed_rewards: u64,
+    timestamp: i64,
+) -> Result<u64, JsError> {
+    terms(amount, tier, deposited_at, lock_until, claimed_rewards)?
+        .value_at(timestamp)
+        .map_err(to_js)
+}
+
+#[wasm_bindgen(js_name = claimableAt)]
+pub fn claimable_at(
+    amount: u64,
+    tier: u8,
+    deposited_at: i64,
+    lock_until: i64,
+    claimed_rewards: u64,
+    timestamp: i64,
+) -> Result<u64, JsError> {
+    terms(amount, tier, deposited_at, lock_until, claimed_rewards)?
+        .claimable_at(timestamp)
+        .map_err(to_js)
+}
+
+#[wasm_bindgen(js_name = forceWithdrawNetAt)]
+pub fn force_withdraw_net_at(
+    amount: u64,
+    tier: u8,
+    deposited_at: i64,
+    lock_until: i64,
+    claimed_rewards: u64,
+    timestamp: i64,
+) -> Result<u64, JsError> {
+    terms(amount, tier, deposited_at, lock_until, claimed_rewards)?
+        .force_withdraw_at(timestamp)
+        .map(|quote| quote.total_withdrawal)
+        .map_err(to_js)
+}
+
+#[wasm_bindgen(js_name = netDeposit)]
+pub fn net_deposit(amount: u64) -> Result<u64, JsError> {
+    crate::calculate_net_deposit(amount)
+        .map(|(net_amount, _)| net_amount)
+        .map_err(to_js)
+}
@@ -0,0 +1,469 @@
+use spacemoney_math::*;
+
+const DAY: i64 = SECONDS_PER_DAY;
+const SOL: u64 = 1_000_000_000;
+
+fn boot_stake(claimed_rewards: u64) -> StakeTerms {
+    StakeTerms::from_tier(10 * SOL, 0, 0, BOOT_LOCK_DAYS * DAY, claimed_rewards).unwrap()
+}
+
+#[test]
+fn claimable_accrues_daily_and_caps_at_lock_period() {
+    let stake = boot_stake(0);
+    // 1% of 10 SOL per day
+    assert_eq!(stake.claimable_at(DAY - 1).unwrap(), 0);
+    assert_eq!(stake.claimable_at(DAY).unwrap(), SOL / 10);
+    assert_eq!(stake.claimable_at(10 * DAY + 5).unwrap(), SOL);
+    assert_eq!(stake.claimable_at(365 * DAY).unwrap(), 3 * SOL);
+    assert_eq!(
+        stake.claimable_at(365 * DAY).unwrap(),
+        stake.unclaimed_at_maturity().unwrap()
+    );
+
+    let claimed = boot_stake(SOL);
+    assert_eq!(claimed.claimable_at(10 * DAY).unwrap(), 0);
+    assert_eq!(claimed.value_at(20 * DAY).unwrap(), 11 * SOL);
+    assert_eq!(claimed.claimable_at(DAY), Err(MathError::Calculation));
+}
+
+fn per_second_stake(amount: u64, rounding: Rounding) -> StakeTerms {
+    StakeTerms {
+        accrual: AccrualMode::PerSecond,
+        rounding,
+        ..StakeTerms::from_tier(amount, 0, 0, BOOT_LOCK_DAYS * DAY, 0).unwrap()
+    }
+}
+
+#[test]
+fn per_second_accrual_earns_partial_days() {
+    let stake = per_second_stake(10 * SOL, Rounding::Down);
+    // 0.1 SOL per day, so a bit under 0.1 SOL just before the first day ends
+    assert_eq!(stake.claimable_at(DAY / 2).unwrap(), SOL / 20);
+    assert_eq!(stake.claimable_at(DAY - 1).unwrap(), 99_998_842);
+    assert_eq!(boot_stake(0).claimable_at(DAY - 1).unwrap(), 0);
+
+    // Whole days match daily accrual
+    assert_eq!(stake.claimable_at(10 * DAY).unwrap(), SOL);
+    assert_eq!(
+        stake.claimable_at(10 * DAY).unwrap(),
+        boot_stake(0).claimable_at(10 * DAY).unwrap()
+    );
+}
+
+#[test]
+fn rounding_policy_applies_to_the_final_division() {
+    // 0.1 SOL a day is 1157.4 lamports a second
+    let at = |rounding, timestamp| {
+        per_second_stake(10 * SOL, rounding)
+            .earned_at(timestamp)
+            .unwrap()
+    };
+    assert_eq!(at(Rounding::Down, 1), 1157);
+    assert_eq!(at(Rounding::Nearest, 1), 1157);
+    assert_eq!(at(Rounding::Up, 1), 1158);
+    assert_eq!(at(Rounding::Down, 2), 2314);
+    assert_eq!(at(Rounding::Nearest, 2), 2315);
+    assert_eq!(at(Rounding::Up, 0), 0);
+    assert_eq!(at(Rounding::Up, DAY), SOL / 10);
+
+    // 170 lamports earn 1 lamport a day, the same as daily accrual
+    let small = per_second_stake(170, Rounding::Up);
+    assert_eq!(small.earned_at(DAY / 4).unwrap(), 1);
+    assert_eq!(small.earned_at(10 * DAY).unwrap(), 10);
+    assert_eq!(small.earned_at(30 * DAY - 1).unwrap(), 30);
+    assert_eq!(small.max_rewards().unwrap(), 30);
+
+    assert_eq!(Rounding::Nearest.divide(5, 10), Ok(1));
+    assert_eq!(Rounding::Down.divide(19, 10), Ok(1));
+    assert_eq!(Rounding::Up.divide(11, 10), Ok(2));
+    assert_eq!(Rounding::Up.divide(1, 0), Err(MathError::Calculation));
+}
+
+#[test]
+fn accrual_modes_reach_the_same_total_at_expiry() {
+    for amount in [10 * SOL, 7, 123_456_789, 1_000_000_007] {
+        let daily = StakeTerms::from_tier(amount, 1, 0, SYMBIOTIC_LOCK_DAYS * DAY, 0).unwrap();
+        let expiry = daily.lock_until;
+        for rounding in [Rounding::Down, Rounding::Nearest, Rounding::Up] {
+            let per_second = StakeTerms {
+                accrual: AccrualMode::PerSecond,
+                rounding,
+                ..daily
+            };
+            assert_eq!(
+                per_second.earned_at(expiry).unwrap(),
+                daily.earned_at(expiry).unwrap()
+            );
+            assert_eq!(
+                per_second.earned_at(expiry).unwrap(),
+                per_second.max_rewards().unwrap()
+            );
+            assert!(per_second.earned_at(expiry - 1).unwrap() <= per_second.max_rewards().unwrap());
+        }
+    }
+}
+
+fn post_lock_stake(post_lock: PostLock, post_lock_rate_bps: u64) -> StakeTerms {
+    StakeTerms {
+        post_lock,
+        post_lock_rate_bps,
+        ..boot_stake(0)
+    }
+}
+
+#[test]
+fn rewards_stop_at_expiry_without_a_post_lock_policy() {
+    let stake = post_lock_stake(PostLock::None, 0);
+    assert_eq!(stake.earned_at(400 * DAY).unwrap(), 3 * SOL);
+    assert_eq!(stake.unclaimed_at_withdrawal(400 * DAY).unwrap(), 3 * SOL);
+    assert!(!stake.is_locked_at(400 * DAY));
+    assert_eq!(stake.lock_until_at(400 * DAY), 30 * DAY);
+}
+
+#[test]
+fn flexible_stakes_keep_earning_at_a_reduced_rate() {
+    // A quarter of Boot's 0.1 SOL a day after the 30-day lock
+    let stake = post_lock_stake(PostLock::Flexible, 2_500);
+    assert_eq!(stake.earned_at(30 * DAY).unwrap(), 3 * SOL);
+    assert_eq!(stake.earned_at(34 * DAY).unwrap(), 3 * SOL + SOL / 10);
+    assert_eq!(
+        stake.earned_at(34 * DAY + DAY / 2).unwrap(),
+        3 * SOL + SOL / 10
+    );
+    assert!(!stake.is_locked_at(34 * DAY));
+
+    // Withdrawing pays everything earned so far
+    let claimed = StakeTerms {
+        claimed_rewards: 3 * SOL,
+        ..stake
+    };
+    assert_eq!(claimed.unclaimed_at_withdrawal(34 * DAY).unwrap(), SOL / 10);
+}
+
+#[test]
+fn zero_lock_stakes_earn_from_deposit_until_an_unstake_request() {
+    // Half of Boot's rate with no lock at all
+    let stake = StakeTerms {
+        lock_days: 0,
+        lock_until: 0,
+        ..post_lock_stake(PostLock::Flexible, 5_000)
+    };
+    assert!(!stake.is_locked_at(0));
+    assert_eq!(stake.max_rewards().unwrap(), 0);
+    assert_eq!(stake.earned_at(10 * DAY).unwrap(), SOL / 2);
+
+    // Nothing more is earned once unbonding starts
+    let unbonding = StakeTerms {
+        stopped_at: Some(10 * DAY),
+        ..stake
+    };
+    assert_eq!(unbonding.earned_at(20 * DAY).unwrap(), SOL / 2);
+    assert_eq!(
+        unbonding.unclaimed_at_withdrawal(20 * DAY).unwrap(),
+        SOL / 2
+    );
+
+    // An auto-rolled stake stays unlocked instead of rolling into a new lock
+    let rolled = StakeTerms {
+        stopped_at: Some(30 * DAY),
+        ..post_lock_stake(PostLock::AutoRoll, 0)
+    };
+    assert!(!rolled.is_locked_at(45 * DAY));
+    assert_eq!(rolled.earned_at(45 * DAY).unwrap(), 3 * SOL);
+}
+
+#[test]
+fn auto_rolled_stakes_relock_and_open_a_window_at_each_expiry() {
+    let stake = post_lock_stake(PostLock::AutoRoll, 0);
+    assert!(stake.is_locked_at(30 * DAY - 1));
+
+    // Withdrawable for a day after each expiry, then locked again
+    assert!(!stake.is_locked_at(30 * DAY));
+    assert!(!stake.is_locked_at(31 * DAY - 1));
+    assert!(stake.is_locked_at(31 * DAY));
+    assert_eq!(stake.lock_until_at(31 * DAY), 60 * DAY);
+    assert!(!stake.is_locked_at(60 * DAY));
+    assert_eq!(stake.lock_until_at(75 * DAY), 90 * DAY);
+
+    // Each lock earns the full period's rewards
+    assert_eq!(stake.earned_at(30 * DAY).unwrap(), 3 * SOL);
+    assert_eq!(stake.earned_at(40 * DAY).unwrap(), 4 * SOL);
+    assert_eq!(stake.earned_at(90 * DAY + 5).unwrap(), 9 * SOL);
+    assert_eq!(stake.unclaimed_at_withdrawal(60 * DAY).unwrap(), 6 * SOL);
+
+    // Force withdrawing from a rolled lock only forfeits part of that lock's rewards
+    let claimed = StakeTerms {
+        claimed_rewards: 3 * SOL,
+        ..stake
+    };
+    let quote = claimed.force_withdraw_at(40 * DAY).unwrap();
+    assert_eq!(quote.unclaimed_rewards, SOL);
+    assert_eq!(quote.penalty, SOL / 5);
+}
+
+#[test]
+fn force_withdraw_applies_penalty_to_unclaimed_rewards() {
+    let quote = boot_stake(0).force_withdraw_at(10 * DAY).unwrap();
+    assert_eq!(quote.unclaimed_rewards, SOL);
+    assert_eq!(quote.penalty, SOL / 5);
+    assert_eq!(quote.rewards_after_penalty, SOL * 4 / 5);
+    assert_eq!(quote.total_withdrawal, 10 * SOL + SOL * 4 / 5);
+    assert!(boot_stake(0).is_locked_at(10 * DAY));
+}
+
+fn penalised_stake(penalty: PenaltyPolicy) -> StakeTerms {
+    StakeTerms {
+        penalty,
+        ..boot_stake(0)
+    }
+}
+
+#[test]
+fn linear_penalty_decays_to_zero_at_lock_end() {
+    let stake = penalised_stake(PenaltyPolicy {
+        curve: PenaltyCurve::Linear,
+        rate_bps: 3000,
+        ..Default::default()
+    });
+    assert_eq!(stake.penalty.rate_at(0, 30 * DAY).unwrap(), 3000);
+    assert_eq!(stake.penalty.rate_at(30 * DAY, 30 * DAY).unwrap(), 0);
+
+    // A third of the way in, 20% of the 1 SOL earned so far
+    let quote = stake.force_withdraw_at(10 * DAY).unwrap();
+    assert_eq!(quote.breakdown.rate_bps, 2000);
+    assert_eq!(quote.penalty, SOL / 5);
+    assert_eq!(quote.total_withdrawal, 10 * SOL + SOL * 4 / 5);
+
+    // Two thirds in, 10% of 2 SOL
+    let quote = stake.force_withdraw_at(20 * DAY).unwrap();
+    assert_eq!(quote.breakdown.rate_bps, 1000);
+    assert_eq!(quote.penalty, SOL / 5);
+    assert_eq!(quote.rewards_after_penalty, SOL * 9 / 5);
+}
+
+#[test]
+fn step_penalty_follows_its_schedule() {
+    let mut steps = [PenaltyStep::default(); MAX_PENALTY_STEPS];
+    steps[0] = PenaltyStep {
+        from_bps: 0,
+        rate_bps: 5000,
+    };
+    steps[1] = PenaltyStep {
+        from_bps: 5000,
+        rate_bps: 2500,
+    };
+    steps[2] = PenaltyStep {
+        from_bps: 9000,
+        rate_bps: 0,
+    };
+    let stake = penalised_stake(PenaltyPolicy {
+        curve: PenaltyCurve::Step,
+        steps,
+        step_count: 3,
+        ..Default::default()
+    });
+
+    assert_eq!(stake.force_withdraw_at(10 * DAY).unwrap().penalty, SOL / 2);
+    // Half way through the lock the second step starts
+    assert_eq!(
+        stake
+            .force_withdraw_at(15 * DAY)
+            .unwrap()
+            .breakdown
+            .rate_bps,
+        2500
+    );
+    assert_eq!(stake.force_withdraw_at(20 * DAY).unwrap().penalty, SOL / 2);
+    assert_eq!(stake.force_withdraw_at(27 * DAY).unwrap().penalty, 0);
+
+    // Unused steps are ignored
+    let short = PenaltyPolicy {
+        step_count: 1,
+        ..stake.penalty
+    };
+    assert_eq!(short.rate_at(29 * DAY, 30 * DAY).unwrap(), 5000);
+}
+
+#[test]
+fn penalty_can_reach_principal_and_claimed_rewards() {
+    let policy = PenaltyPolicy {
+        curve: PenaltyCurve::Linear,
+        rate_bps: 3000,
+        on_principal: true,
+        clawback_claimed: true,
+        ..Default::default()
+    };
+    let stake = StakeTerms {
+        claimed_rewards: SOL / 2,
+        ..penalised_stake(policy)
+    };
+
+    // A third of the way in: 20% of the unclaimed half SOL, of the 10 SOL
+    // principal and of the half SOL already claimed
+    let quote = stake.force_withdraw_at(10 * DAY).unwrap();
+    assert_eq!(quote.unclaimed_rewards, SOL / 2);
+    assert_eq!(quote.breakdown.on_rewards, SOL / 10);
+    assert_eq!(quote.breakdown.on_principal, 2 * SOL);
+    assert_eq!(quote.breakdown.clawback, SOL / 10);
+    assert_eq!(quote.penalty, 2 * SOL + SOL / 5);
+    assert_eq!(quote.total_withdrawal, 10 * SOL + SOL / 2 - quote.penalty);
+
+    // A full-rate clawback stops at an empty payout
+    let breakdown = calculate_force_withdraw_penalty(
+        &PenaltyPolicy {
+            curve: PenaltyCurve::Linear,
+            rate_bps: 10_000,
+            on_principal: true,
+            clawback_claimed: true,
+            ..Default::default()
+        },
+        0,
+        30 * DAY,
+        10 * SOL,
+        SOL,
+        5 * SOL,
+    )
+    .unwrap();
+    assert_eq!(breakdown.clawback, 0);
+    assert_eq!(breakdown.total().unwrap(), 11 * SOL);
+}
+
+#[test]
+fn fees_and_tiers_match_program_constants() {
+    assert_eq!(
+        calculate_net_deposit(100 * SOL).unwrap(),
+        (98 * SOL, 2 * SOL)
+    );
+    assert_eq!(
+        get_tier_config(2).unwrap(),
+        (SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS)
+    );
+    assert_eq!(get_tier_config(3), Err(MathError::InvalidTier));
+    assert_eq!(calculate_deposit_fee(u64::MAX), Err(MathError::Overflow));
+}
+
+#[test]
+fn liquid_pool_earns_its_share_of_collected_yield() {
+    // 25 liquid SOL next to 75 locked SOL earn a quarter of the yield
+    assert_eq!(calculate_liquid_yield(4 * SOL, 25 * SOL, 75 * SOL).unwrap(), SOL);
+    assert_eq!(calculate_liquid_yield(4 * SOL, 0, 75 * SOL).unwrap(), 0);
+    assert_eq!(calculate_liquid_yield(4 * SOL, 0, 0).unwrap(), 0);
+
+    // The rate only moves with that yield: the pool backs each spSOL
+    let supply = 25 * SOL;
+    assert_eq!(calculate_sol_for_spsol(SOL, 25 * SOL, supply).unwrap(), SOL);
+    assert_eq!(calculate_sol_for_spsol(SOL, 26 * SOL, supply).unwrap(), 1_040_000_000);
+    assert_eq!(calculate_spsol_to_mint(26 * SOL, 26 * SOL, supply).unwrap(), supply);
+}
+
+#[test]
+fn loyalty_levels_grant_a_bounded_bonus() {
+    assert_eq!(
+        calculate_stake_days(SOL, LOYALTY_UNIT_SOL, 0, 30 * DAY - 1),
+        29
+    );
+    assert_eq!(calculate_stake_days(SOL, LOYALTY_UNIT_SOL, 10 * DAY, 0), 0);
+    assert_eq!(
+        calculate_stake_days(SOL / 2, LOYALTY_UNIT_SOL, 0, 61 * DAY),
+        30
+    );
+    assert_eq!(
+        calculate_stake_days(250_000_000, LOYALTY_UNIT_USDT, 0, 10 * DAY),
+        25
+    );
+
+    // Splitting a stake, or merging one back, keeps its credit
+    let whole = calculate_stake_days(10 * SOL, LOYALTY_UNIT_SOL, 0, 45 * DAY);
+    let parts = calculate_stake_days(4 * SOL, LOYALTY_UNIT_SOL, 0, 45 * DAY)
+        + calculate_stake_days(6 * SOL, LOYALTY_UNIT_SOL, 0, 45 * DAY);
+    assert_eq!((whole, parts), (450, 450));
+    assert_eq!(calculate_stake_days(u64::MAX, 1, 0, 1_000 * DAY), u64::MAX);
+
+    assert_eq!((loyalty_level(29), loyalty_bonus_bps(29)), (0, 0));
+    assert_eq!((loyalty_level(30), loyalty_bonus_bps(30)), (1, 250));
+    assert_eq!((loyalty_level(200), loyalty_bonus_bps(200)), (3, 1000));
+    assert_eq!(loyalty_bonus_bps(u64::MAX), MAX_LOYALTY_BONUS_BPS);
+    assert_eq!(stake_days_to_next_level(100), 80);
+    assert_eq!(stake_days_to_next_level(MAX_LOYALTY_STAKE_DAYS), 0);
+
+    // 10% more on every reward, the full-lock total included
+    let boosted = StakeTerms {
+        loyalty_bonus_bps: 1000,
+        ..boot_stake(0)
+    };
+    assert_eq!(boosted.claimable_at(DAY).unwrap(), SOL / 10 + SOL / 100);
+    assert_eq!(boosted.max_rewards().unwrap(), 3 * SOL + 3 * SOL / 10);
+    assert_eq!(
+        boosted.claimable_at(365 * DAY).unwrap(),
+        boosted.unclaimed_at_maturity().unwrap()
+    );
+}
+
+#[test]
+fn campaign_boost_covers_the_first_lock() {
+    assert_eq!(calculate_campaign_boost(3 * SOL, 10_000).unwrap(), 3 * SOL);
+    assert_eq!(
+        calculate_campaign_boost(3 * SOL, MAX_CAMPAIGN_BOOST_BPS + 1),
+        Err(MathError::Calculation)
+    );
+
+    // 2x over the Boot lock, reserved up front
+    let boosted = StakeTerms {
+        campaign_boost_bps: 10_000,
+        ..boot_stake(0)
+    };
+    assert_eq!(boosted.campaign_rewards().unwrap(), 3 * SOL);
+    assert_eq!(boosted.claimable_at(DAY).unwrap(), 2 * SOL / 10);
+    assert_eq!(boosted.max_rewards().unwrap(), 6 * SOL);
+    assert_eq!(
+        boosted.claimable_at(30 * DAY).unwrap(),
+        boosted.unclaimed_at_maturity().unwrap()
+    );
+
+    // Later auto-rolled locks earn at the base rate
+    let rolled = StakeTerms {
+        post_lock: PostLock::AutoRoll,
+        ..boosted
+    };
+    assert_eq!(rolled.claimable_at(60 * DAY).unwrap(), 9 * SOL);
+
+    // The loyalty bonus applies to the base rewards only
+    let both = StakeTerms {
+        loyalty_bonus_bps: 1000,
+        ..boosted
+    };
+    assert_eq!(
+        both.max_rewards().unwrap(),
+        3 * SOL + 3 * SOL / 10 + 3 * SOL
+    );
+}
+
+#[test]
+fn referral_reward_is_a_share_of_the_deposit_fee() {
+    let (_, fee) = calculate_net_deposit(100 * SOL).unwrap();
+    assert_eq!(calculate_referral_reward(fee, 2500).unwrap(), SOL / 2);
+    assert_eq!(calculate_referral_reward(fee, 0).unwrap(), 0);
+    assert_eq!(calculate_referral_reward(fee, 10_000).unwrap(), fee);
+    assert_eq!(
+        calculate_referral_reward(fee, 10_001),
+        Err(MathError::Calculation)
+    );
+}
+
+#[test]
+fn insurance_takes_a_share_and_reports_coverage() {
+    let (_, fee) = calculate_net_deposit(100 * SOL).unwrap();
+    assert_eq!(calculate_insurance_share(fee, 5000).unwrap(), SOL);
+    assert_eq!(
+        calculate_insurance_share(u64::MAX, 10_000).unwrap(),
+        u64::MAX
+    );
+    assert_eq!(
+        calculate_insurance_share(fee, 10_001),
+        Err(MathError::Calculation)
+    );
+
+    assert_eq!(calculate_coverage_bps(5 * SOL, 100 * SOL), 500);
+    assert_eq!(calculate_coverage_bps(200 * SOL, 100 * SOL), 20_000);
+    assert_eq!(calculate_coverage_bps(0, 0), 10_000);
+}
@@ -3,25 +3,37 @@ pub const PLATFORM_SEED: &[u8] = b"platform";
 pub const USER_SEED: &[u8] = b"user";
 pub const TIERS_SEED: &[u8] = b"tiers";
 pub const VAULT_SEED: &[u8] = b"vault";
+pub const RECEIPT_SEED: &[u8] = b"receipt";
+pub const RECEIPT_METADATA_SEED: &[u8] = b"receipt_metadata";
+pub const SPSOL_MINT_SEED: &[u8] = b"spsol_mint";
+pub const VALIDATOR_LIST_SEED: &[u8] = b"validators";
+pub const VALIDATOR_STAKE_SEED: &[u8] = b"validator_stake";
+pub const WITHDRAWAL_QUEUE_SEED: &[u8] = b"withdrawal_queue";
+pub const REFERRAL_SEED: &[u8] = b"referral";
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+pub const DISTRIBUTOR_SEED: &[u8] = b"distributor";
+pub const CLAIMED_BITMAP_SEED: &[u8] = b"claimed_bitmap";
+pub const INSURANCE_FUND_SEED: &[u8] = b"insurance_fund";
+pub const INSURANCE_VAULT_SEED: &[u8] = b"insurance_vault";
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority"; // fixed by #[event_cpi]
 
-// Fee constants (basis points)
-pub const DEPOSIT_FEE_BPS: u64 = 200; // 2%
-pub const FORCE_WITHDRAW_PENALTY_BPS: u64 = 2000; // 20%
-pub const BPS_DENOMINATOR: u64 = 10000;
+// Fee, reward and tier constants live in spacemoney-math so clients share them
+pub use spacemoney_math::constants::*;
 
-// Reward constants
-pub const DAILY_YIELD_BPS: u64 = 100; // 1% daily
-pub const SECONDS_PER_DAY: i64 = 86400;
+// Tier list constants
+pub const MAX_TIERS: usize = 16;
+pub const MAX_TIER_NAME_LEN: usize = 32;
 
-// Tier constants
-pub const BOOT_MIN_STAKE: u64 = 1_000_000_000; // 1 SOL
-pub const SYMBIOTIC_MIN_STAKE: u64 = 5_000_000_000; // 5 SOL
-pub const SPACE_MIN_STAKE: u64 = 25_000_000_000; // 25 SOL
+// Liquid staking constants
+pub const SPSOL_DECIMALS: u8 = 9;
 
-pub const BOOT_MULTIPLIER: u64 = 1;
-pub const SYMBIOTIC_MULTIPLIER: u64 = 2;
-pub const SPACE_MULTIPLIER: u64 = 3;
+// Validator delegation constants
+pub const MAX_VALIDATORS: usize = 10;
+pub const MIN_VALIDATOR_DELEGATION: u64 = 1_000_000_000; // 1 SOL
+pub const REBALANCE_THRESHOLD_BPS: u64 = 1000; // 10% over target before unstaking
 
-pub const BOOT_LOCK_DAYS: i64 = 30;
-pub const SYMBIOTIC_LOCK_DAYS: i64 = 90;
-pub const SPACE_LOCK_DAYS: i64 = 180;
+// Withdrawal queue constants
+pub const MAX_QUEUED_WITHDRAWALS: usize = 64;
+
+// Bonus distributor constants
+pub const MAX_DISTRIBUTOR_CLAIMS: u32 = 65_536; // an 8 KiB claimed bitmap
@@ -49,4 +49,160 @@ pub enum SpaceMoneyError {
     
     #[msg("No rewards available to claim")]
     NoRewardsAvailable,
+    
+    #[msg("Invalid split amount")]
+    InvalidSplitAmount,
+    
+    #[msg("Stakes have incompatible token types or lock schedules")]
+    IncompatibleStakes,
+    
+    #[msg("Operation would not preserve reward accounting exactly")]
+    RewardPrecisionLoss,
+    
+    #[msg("Stake transfers are disabled")]
+    StakeTransfersDisabled,
+    
+    #[msg("Invalid stake transfer recipient")]
+    InvalidRecipient,
+    
+    #[msg("Stake is represented by a receipt NFT")]
+    StakeHasReceipt,
+    
+    #[msg("Receipt accounts are missing")]
+    MissingReceiptAccounts,
+    
+    #[msg("Liquid staking is not enabled")]
+    LiquidStakingDisabled,
+    
+    #[msg("Invalid spSOL mint")]
+    InvalidSpsolMint,
+    
+    #[msg("Liquid deposits cannot mint a stake receipt")]
+    InvalidLiquidDeposit,
+    
+    #[msg("Validator is already in the list")]
+    ValidatorAlreadyExists,
+    
+    #[msg("Validator not found")]
+    ValidatorNotFound,
+    
+    #[msg("Validator list is full")]
+    ValidatorListFull,
+    
+    #[msg("Invalid vote account")]
+    InvalidVoteAccount,
+    
+    #[msg("Validator was already rebalanced this epoch")]
+    RebalanceTooSoon,
+    
+    #[msg("Invalid delegation target")]
+    InvalidDelegationTarget,
+    
+    #[msg("Account type cannot be migrated")]
+    UnknownAccountType,
+    
+    #[msg("Account is already on the current version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Tier is disabled")]
+    TierDisabled,
+    
+    #[msg("Tier has reached its stake capacity")]
+    TierFull,
+    
+    #[msg("Tier list is full")]
+    TierListFull,
+    
+    #[msg("Invalid tier parameters")]
+    InvalidTierParams,
+    
+    #[msg("Tier is not open for deposits yet")]
+    TierNotOpen,
+    
+    #[msg("Tier is closed for deposits")]
+    TierClosed,
+    
+    #[msg("Deposit would exceed the tier's total stake cap")]
+    TierStakeCapExceeded,
+    
+    #[msg("Deposit would exceed the tier's per-user stake cap")]
+    UserStakeCapExceeded,
+    
+    #[msg("Stake is unbonding")]
+    StakeUnbonding,
+    
+    #[msg("Stake is not unbonding")]
+    StakeNotUnbonding,
+    
+    #[msg("Unbonding period has not ended")]
+    UnbondingNotComplete,
+    
+    #[msg("Tier has an unbonding period; request an unstake first")]
+    UnbondingRequired,
+    
+    #[msg("Withdrawal queue is full")]
+    WithdrawalQueueFull,
+    
+    #[msg("Account does not match the queued ticket's destination")]
+    InvalidQueueDestination,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Cannot refer your own deposit")]
+    SelfReferral,
+    
+    #[msg("Referral share cannot exceed 100%")]
+    InvalidReferralShare,
+    
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+    
+    #[msg("Campaign must end after it starts and after now")]
+    InvalidCampaignWindow,
+    
+    #[msg("Campaign boost must be between 0.01% and 100%")]
+    InvalidCampaignBoost,
+    
+    #[msg("Campaign needs an eligible tier and a budget")]
+    EmptyCampaign,
+    
+    #[msg("Campaign is not running")]
+    CampaignNotActive,
+    
+    #[msg("Campaign does not cover this tier or token")]
+    CampaignNotEligible,
+    
+    #[msg("Campaign has not reached its end time")]
+    CampaignStillRunning,
+    
+    #[msg("Campaign has already ended")]
+    CampaignEnded,
+    
+    #[msg("Distributor needs an amount and between 1 and 65536 claims")]
+    InvalidDistributor,
+    
+    #[msg("Claim index is outside the distributor")]
+    InvalidClaimIndex,
+    
+    #[msg("Merkle proof does not match the distributor's root")]
+    InvalidMerkleProof,
+    
+    #[msg("Bonus has already been claimed")]
+    BonusAlreadyClaimed,
+    
+    #[msg("Claim exceeds the distributor's remaining amount")]
+    DistributorOverdrawn,
+    
+    #[msg("Insurance share cannot exceed 100%")]
+    InvalidInsuranceShare,
+    
+    #[msg("Insurance fund accounts are required while an insurance share is set")]
+    InsuranceFundRequired,
+    
+    #[msg("Tier still has active stakes")]
+    TierHasActiveStakes,
+    
+    #[msg("No queued withdrawal ticket with this id belongs to the signer")]
+    TicketNotFound,
 }
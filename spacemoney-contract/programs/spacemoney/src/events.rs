@@ -1,9 +1,29 @@
 use anchor_lang::prelude::*;
+use crate::states::{AccountKind, PlatformState};
+
+pub use crate::states::{AccrualMode, CampaignEndReason, InsuranceSource, PenaltyCurve, PenaltyStep, PostLock, Rounding, TokenType};
+
+// Emits an event through a self-CPI (the accounts added by #[event_cpi]) so it
+// shows up in inner instructions, where logs can't truncate or spoof it. With
+// the `log-events` feature it falls back to writing the event to the logs.
+macro_rules! emit_event {
+    ($ctx:ident, $event:expr) => {
+        #[cfg(not(feature = "log-events"))]
+        $crate::utils::emit_cpi_event(
+            &$ctx.accounts.event_authority,
+            $ctx.bumps.event_authority,
+            &$event,
+        )?;
+        #[cfg(feature = "log-events")]
+        emit!($event);
+    };
+}
 
 #[event]
 pub struct InitializedEvent {
     pub admin: Pubkey,
     pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -15,6 +35,11 @@ pub struct DepositedEvent {
     pub fee: u64,
     pub lock_until: i64,
     pub timestamp: i64,
+    pub stake_index: u64,
+    pub active_stakes: u64,
+    pub loyalty_bonus_bps: u16,
+    pub campaign_boost_bps: u16,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -25,6 +50,82 @@ pub struct WithdrewEvent {
     pub rewards: u64,
     pub token_type: TokenType,
     pub timestamp: i64,
+    pub active_stakes: u64,
+    pub totals: PlatformTotals,
+}
+
+// complete_unstake pays out with a WithdrewEvent once the cooldown is over
+#[event]
+pub struct UnstakeRequestedEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub amount: u64,
+    pub token_type: TokenType,
+    pub unbonding_until: i64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+// The stake is closed when queued; its payout arrives with a
+// WithdrawalFilledEvent once process_queue fills the ticket
+#[event]
+pub struct WithdrawalQueuedEvent {
+    pub user: Pubkey,
+    pub ticket_id: u64,
+    pub stake_index: u64,
+    pub principal: u64,
+    pub rewards: u64,
+    pub token_type: TokenType,
+    pub tickets_ahead: u64,
+    pub timestamp: i64,
+    pub active_stakes: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct WithdrawalFilledEvent {
+    pub user: Pubkey,
+    pub ticket_id: u64,
+    pub stake_index: u64,
+    pub destination: Pubkey,
+    pub principal: u64,
+    pub rewards: u64,
+    pub token_type: TokenType,
+    pub queued_at: i64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+// The ticket's token account was closed, frozen or is no longer USDT; the
+// ticket stays queued until its owner redirects it
+#[event]
+pub struct WithdrawalSkippedEvent {
+    pub user: Pubkey,
+    pub ticket_id: u64,
+    pub destination: Pubkey,
+    pub token_type: TokenType,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct WithdrawalRedirectedEvent {
+    pub user: Pubkey,
+    pub ticket_id: u64,
+    pub previous_destination: Pubkey,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct RewardsFundedEvent {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub token_type: TokenType,
+    pub queued_withdrawals: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -34,6 +135,9 @@ pub struct ClaimedRewardsEvent {
     pub amount: u64,
     pub token_type: TokenType,
     pub timestamp: i64,
+    pub stake_claimed_rewards: u64,
+    pub active_stakes: u64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -42,9 +146,15 @@ pub struct ForceWithdrewEvent {
     pub stake_index: u64,
     pub principal: u64,
     pub rewards_after_penalty: u64,
+    // Everything forfeited; the principal penalty and clawback are part of it
     pub penalty: u64,
+    pub penalty_rate_bps: u64,
+    pub principal_penalty: u64,
+    pub clawback: u64,
     pub token_type: TokenType,
     pub timestamp: i64,
+    pub active_stakes: u64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -54,6 +164,7 @@ pub struct AdminTransferredEvent {
     pub amount: u64,
     pub token_type: TokenType,
     pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -61,18 +172,21 @@ pub struct AdminChangedEvent {
     pub old_admin: Pubkey,
     pub new_admin: Pubkey,
     pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
 pub struct ProgramPausedEvent {
     pub admin: Pubkey,
     pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
 pub struct ProgramResumedEvent {
     pub admin: Pubkey,
     pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
 #[event]
@@ -80,12 +194,445 @@ pub struct TierConfigUpdatedEvent {
     pub tier: u8,
     pub min_stake: u64,
     pub multiplier: u64,
-    pub lock_days: i64,
+    pub lock_seconds: i64,
+    pub max_stakes: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct SplitStakeEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub new_stake_index: u64,
+    pub amount: u64,
+    pub claimed_rewards: u64,
+    pub timestamp: i64,
+    pub active_stakes: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct MergedStakesEvent {
+    pub user: Pubkey,
+    pub target_index: u64,
+    pub source_indices: Vec<u64>,
+    pub amount: u64,
+    pub claimed_rewards: u64,
+    pub timestamp: i64,
+    pub active_stakes: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TransferredStakeEvent {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub stake_index: u64,
+    pub new_stake_index: u64,
+    pub amount: u64,
+    pub claimed_rewards: u64,
+    pub token_type: TokenType,
+    pub timestamp: i64,
+    pub from_active_stakes: u64,
+    pub to_active_stakes: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct StakeTransfersUpdatedEvent {
+    pub admin: Pubkey,
+    pub enabled: bool,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct MintedReceiptEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct LiquidStakingInitializedEvent {
+    pub admin: Pubkey,
+    pub spsol_mint: Pubkey,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct MintedSpsolEvent {
+    pub user: Pubkey,
+    pub sol_amount: u64,
+    pub spsol_amount: u64,
+    pub fee: u64,
+    pub liquid_staked_sol: u64,
+    pub timestamp: i64,
+    pub spsol_supply: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct RedeemedSpsolEvent {
+    pub user: Pubkey,
+    pub spsol_amount: u64,
+    pub sol_amount: u64,
+    pub liquid_staked_sol: u64,
+    pub timestamp: i64,
+    pub spsol_supply: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct ValidatorAddedEvent {
+    pub admin: Pubkey,
+    pub vote_account: Pubkey,
+    pub timestamp: i64,
+    pub validator_count: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct ValidatorRemovedEvent {
+    pub vote_account: Pubkey,
+    pub timestamp: i64,
+    pub validator_count: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct DelegationTargetUpdatedEvent {
+    pub admin: Pubkey,
+    pub target_delegation_bps: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct DelegatedStakeEvent {
+    pub vote_account: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+    pub total_delegated: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct DeactivatedStakeEvent {
+    pub vote_account: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+    pub total_delegated: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct WithdrewStakeEvent {
+    pub vote_account: Pubkey,
+    pub stake_account: Pubkey,
+    pub principal: u64,
+    pub staking_yield: u64,
+    pub epoch: u64,
+    pub timestamp: i64,
+    pub total_delegated: u64,
+    pub total_yield: u64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct UsdtMintChangedEvent {
+    pub admin: Pubkey,
+    pub old_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct AccountMigratedEvent {
+    pub account: Pubkey,
+    pub kind: AccountKind,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub payer: Pubkey,
     pub timestamp: i64,
+    pub totals: PlatformTotals,
 }
 
+#[event]
+pub struct TierAddedEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub name: String,
+    pub min_stake: u64,
+    pub multiplier: u64,
+    pub lock_seconds: i64,
+    pub max_stakes: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TierDisabledEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TierLimitsUpdatedEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub max_total_stake: u64,
+    pub max_user_stake: u64,
+    pub open_at: i64,
+    pub close_at: i64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TierAccrualUpdatedEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub accrual_mode: AccrualMode,
+    pub rounding: Rounding,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TierPostLockUpdatedEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub post_lock: PostLock,
+    pub post_lock_rate_bps: u16,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TierUnbondingUpdatedEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub unbonding_seconds: i64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct TierPenaltyUpdatedEvent {
+    pub admin: Pubkey,
+    pub tier: u8,
+    pub curve: PenaltyCurve,
+    pub rate_bps: u16,
+    pub steps: Vec<PenaltyStep>,
+    pub on_principal: bool,
+    pub clawback: bool,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct LoyaltyUpdatedEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    // Stake-days the closed stake added to the score
+    pub stake_days_added: u64,
+    pub stake_days: u64,
+    pub level: u8,
+    pub bonus_bps: u16,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct ReferrerRegisteredEvent {
+    pub referrer: Pubkey,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct ReferralCreditedEvent {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub token_type: TokenType,
+    // Gross deposit and the referrer's share of its fee
+    pub volume: u64,
+    pub reward: u64,
+    pub pending: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct ReferralRewardsClaimedEvent {
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub token_type: TokenType,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct ReferralShareUpdatedEvent {
+    pub admin: Pubkey,
+    pub share_bps: u16,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct CampaignCreatedEvent {
+    pub admin: Pubkey,
+    pub campaign_id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub tier_mask: u16,
+    pub boost_bps: u16,
+    pub budget_sol: u64,
+    pub budget_usdt: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct CampaignBoostedEvent {
+    pub campaign_id: u64,
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub token_type: TokenType,
+    pub boost_bps: u16,
+    // Boost rewards the stake reserved, and the campaign's total in the token
+    pub reserved: u64,
+    pub spent: u64,
+    pub budget: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct CampaignEndedEvent {
+    pub campaign_id: u64,
+    pub reason: CampaignEndReason,
+    pub spent_sol: u64,
+    pub spent_usdt: u64,
+    pub boosted_deposits: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct DistributorCreatedEvent {
+    pub admin: Pubkey,
+    pub distributor_id: u64,
+    pub merkle_root: [u8; 32],
+    pub token_type: TokenType,
+    pub total_amount: u64,
+    pub max_claims: u32,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct BonusClaimedEvent {
+    pub distributor_id: u64,
+    pub claimant: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+    pub token_type: TokenType,
+    // The distributor's running total after this claim
+    pub claimed_amount: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct DistributorClosedEvent {
+    pub admin: Pubkey,
+    pub distributor_id: u64,
+    pub token_type: TokenType,
+    pub claimed_amount: u64,
+    pub claimed_count: u32,
+    // Unclaimed amount credited back to the treasury
+    pub returned_amount: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct InsuranceFundInitializedEvent {
+    pub admin: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub insurance_vault: Pubkey,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct InsuranceShareUpdatedEvent {
+    pub admin: Pubkey,
+    pub share_bps: u16,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct InsuranceContributedEvent {
+    pub user: Pubkey,
+    pub source: InsuranceSource,
+    pub token_type: TokenType,
+    pub amount: u64,
+    // The fund's balance in the token afterwards, and as a share of the staked total
+    pub balance: u64,
+    pub coverage_bps: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+#[event]
+pub struct InsuranceBackstopEvent {
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub token_type: TokenType,
+    // What the vault was short of the withdrawal, paid in by the fund
+    pub shortfall: u64,
+    pub balance: u64,
+    pub coverage_bps: u64,
+    pub timestamp: i64,
+    pub totals: PlatformTotals,
+}
+
+// Platform totals after the instruction, carried by every event so indexers
+// can stay consistent without reading accounts
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum TokenType {
-    SOL,
-    USDT,
+pub struct PlatformTotals {
+    pub total_staked_sol: u64,
+    pub total_staked_usdt: u64,
+    pub treasury_sol: u64,
+    pub treasury_usdt: u64,
+    pub liquid_staked_sol: u64,
+}
+
+impl PlatformTotals {
+    pub fn of(platform_state: &PlatformState) -> Self {
+        Self {
+            total_staked_sol: platform_state.total_staked_sol,
+            total_staked_usdt: platform_state.total_staked_usdt,
+            treasury_sol: platform_state.treasury_sol,
+            treasury_usdt: platform_state.treasury_usdt,
+            liquid_staked_sol: platform_state.liquid_staked_sol,
+        }
+    }
 }
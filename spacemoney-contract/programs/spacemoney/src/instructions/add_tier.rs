@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierAddedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct AddTier<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<AddTier>,
+    name: String,
+    min_stake: u64,
+    multiplier: u64,
+    lock_seconds: i64,
+    max_stakes: u64,
+) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    require!(
+        tier_config.tiers.len() < MAX_TIERS,
+        SpaceMoneyError::TierListFull
+    );
+    require!(
+        !name.is_empty()
+            && name.len() <= MAX_TIER_NAME_LEN
+            && TierInfo::valid_terms(multiplier, lock_seconds),
+        SpaceMoneyError::InvalidTierParams
+    );
+    
+    // New tiers always go on the end; stakes refer to tiers by position
+    let tier = tier_config.tiers.len() as u8;
+    tier_config.tiers.push(TierInfo {
+        name: name.clone(),
+        min_stake,
+        multiplier,
+        lock_seconds,
+        enabled: true,
+        max_stakes,
+        active_stakes: 0,
+        max_total_stake: 0,
+        max_user_stake: 0,
+        open_at: 0,
+        close_at: 0,
+        total_staked_sol: 0,
+        total_staked_usdt: 0,
+        accrual_mode: AccrualMode::Daily,
+        rounding: Rounding::Down,
+        post_lock: PostLock::None,
+        post_lock_rate_bps: 0,
+        unbonding_seconds: 0,
+        penalty_curve: PenaltyCurve::Flat,
+        penalty_rate_bps: 0,
+        penalty_steps: [PenaltyStep::default(); MAX_PENALTY_STEPS],
+        penalty_step_count: 0,
+        penalty_on_principal: false,
+        penalty_clawback: false,
+        reserved: [0; TierInfo::RESERVED],
+    });
+    
+    emit_event!(ctx, TierAddedEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        name,
+        min_stake,
+        multiplier,
+        lock_seconds,
+        max_stakes,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::vote;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, ValidatorAddedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct AddValidator<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ValidatorList::LEN,
+        seeds = [VALIDATOR_LIST_SEED],
+        bump
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    
+    /// CHECK: Validated as a vote program account
+    #[account(
+        constraint = *vote_account.owner == vote::program::ID @ SpaceMoneyError::InvalidVoteAccount
+    )]
+    pub vote_account: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddValidator>) -> Result<()> {
+    let validator_list = &mut ctx.accounts.validator_list;
+    let vote_account = ctx.accounts.vote_account.key();
+    let clock = Clock::get()?;
+    
+    validator_list.bump = ctx.bumps.validator_list;
+    
+    require!(
+        validator_list.find(&vote_account).is_none(),
+        SpaceMoneyError::ValidatorAlreadyExists
+    );
+    require!(
+        validator_list.validators.len() < MAX_VALIDATORS,
+        SpaceMoneyError::ValidatorListFull
+    );
+    
+    validator_list.validators.push(ValidatorEntry {
+        vote_account,
+        status: ValidatorStatus::Inactive,
+        active_stake: 0,
+        next_update_epoch: 0,
+        pending_removal: false,
+    });
+    
+    emit_event!(ctx, ValidatorAddedEvent {
+        admin: ctx.accounts.admin.key(),
+        vote_account,
+        timestamp: clock.unix_timestamp,
+        validator_count: validator_list.validators.len() as u64,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -2,9 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::{AdminTransferredEvent, TokenType};
+use crate::events::{AdminTransferredEvent, PlatformTotals};
 use crate::states::*;
+use crate::utils::vault_sol_balance;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct AdminTransfer<'info> {
     #[account(
@@ -63,10 +65,10 @@ pub fn handler(
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
-            // Check platform account balance
-            let platform_balance = platform_state.to_account_info().lamports();
+            // Check platform account balance above its rent
+            let platform_balance = vault_sol_balance(&platform_state.to_account_info())?;
             require!(
-                platform_balance >= amount,
+                platform_state.available_for_payouts(TokenType::SOL, platform_balance) >= amount,
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
@@ -95,7 +97,7 @@ pub fn handler(
             
             // Check token account balance
             require!(
-                platform_token_account.amount >= amount,
+                platform_state.available_for_payouts(TokenType::USDT, platform_token_account.amount) >= amount,
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
@@ -123,12 +125,13 @@ pub fn handler(
         },
     }
     
-    emit!(AdminTransferredEvent {
+    emit_event!(ctx, AdminTransferredEvent {
         admin: ctx.accounts.admin.key(),
         recipient: ctx.accounts.recipient.key(),
         amount,
         token_type: token_type_enum,
         timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{BonusClaimedEvent, PlatformTotals};
+use crate::states::*;
+use crate::utils::{bonus_leaf, vault_sol_balance, verify_merkle_proof};
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimBonus<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [DISTRIBUTOR_SEED, &distributor.id.to_le_bytes()],
+        bump = distributor.bump
+    )]
+    pub distributor: Account<'info, Distributor>,
+    
+    #[account(
+        mut,
+        seeds = [CLAIMED_BITMAP_SEED, distributor.key().as_ref()],
+        bump = claimed_bitmap.bump
+    )]
+    pub claimed_bitmap: Account<'info, ClaimedBitmap>,
+    
+    // USDT only
+    #[account(
+        mut,
+        constraint = claimant_token_account.owner == claimant.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = claimant_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub claimant_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = platform_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub platform_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: PDA authority for token transfers
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Pays the claimant's leaf of the distributor's tree, once
+pub fn handler(
+    ctx: Context<ClaimBonus>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let distributor = &mut ctx.accounts.distributor;
+    let claimed_bitmap = &mut ctx.accounts.claimed_bitmap;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    require!(index < distributor.max_claims, SpaceMoneyError::InvalidClaimIndex);
+    require!(!claimed_bitmap.is_claimed(index), SpaceMoneyError::BonusAlreadyClaimed);
+    
+    let leaf = bonus_leaf(index, &ctx.accounts.claimant.key(), amount);
+    require!(
+        verify_merkle_proof(&proof, &distributor.merkle_root, leaf),
+        SpaceMoneyError::InvalidMerkleProof
+    );
+    require!(amount <= distributor.remaining(), SpaceMoneyError::DistributorOverdrawn);
+    
+    let token_type = distributor.token_type;
+    match token_type {
+        TokenType::SOL => {
+            // Check balance above the vault's rent
+            let platform_balance = vault_sol_balance(&platform_state.to_account_info())?;
+            require!(
+                platform_state.available_for_payouts(TokenType::SOL, platform_balance) >= amount,
+                SpaceMoneyError::InsufficientTreasuryBalance
+            );
+            
+            **platform_state.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **ctx.accounts.claimant.to_account_info().try_borrow_mut_lamports()? += amount;
+        }
+        TokenType::USDT => {
+            let platform_token_account = ctx.accounts.platform_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let claimant_token_account = ctx.accounts.claimant_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let token_program = ctx.accounts.token_program.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            
+            // Check balance
+            require!(
+                platform_state.available_for_payouts(TokenType::USDT, platform_token_account.amount) >= amount,
+                SpaceMoneyError::InsufficientTreasuryBalance
+            );
+            
+            let vault_bump = ctx.bumps.vault_authority;
+            let seeds = &[VAULT_SEED, &[vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = Transfer {
+                from: platform_token_account.to_account_info(),
+                to: claimant_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, amount)?;
+        }
+    }
+    
+    claimed_bitmap.set_claimed(index);
+    distributor.claimed_amount = distributor.claimed_amount
+        .checked_add(amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    distributor.claimed_count = distributor.claimed_count
+        .checked_add(1)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    emit_event!(ctx, BonusClaimedEvent {
+        distributor_id: distributor.id,
+        claimant: ctx.accounts.claimant.key(),
+        index,
+        amount,
+        token_type,
+        claimed_amount: distributor.claimed_amount,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, ReferralRewardsClaimedEvent};
+use crate::states::*;
+use crate::utils::vault_sol_balance;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, referrer.key().as_ref()],
+        bump = referral_account.bump
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+    
+    // USDT only
+    #[account(
+        mut,
+        constraint = referrer_token_account.owner == referrer.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = referrer_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub referrer_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = platform_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub platform_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: PDA authority for token transfers
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Pays out the referrer's pending fee share in one token
+pub fn handler(ctx: Context<ClaimReferralRewards>, token_type: TokenType) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let referral_account = &mut ctx.accounts.referral_account;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    let amount = referral_account.pending(token_type);
+    require!(amount > 0, SpaceMoneyError::NoReferralRewards);
+    
+    match token_type {
+        TokenType::SOL => {
+            // Check balance above the vault's rent
+            let platform_balance = vault_sol_balance(&platform_state.to_account_info())?;
+            require!(
+                platform_state.available_for_payouts(TokenType::SOL, platform_balance) >= amount,
+                SpaceMoneyError::InsufficientTreasuryBalance
+            );
+            
+            **platform_state.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **ctx.accounts.referrer.to_account_info().try_borrow_mut_lamports()? += amount;
+        }
+        TokenType::USDT => {
+            let platform_token_account = ctx.accounts.platform_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let referrer_token_account = ctx.accounts.referrer_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let token_program = ctx.accounts.token_program.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            
+            // Check balance
+            require!(
+                platform_state.available_for_payouts(TokenType::USDT, platform_token_account.amount) >= amount,
+                SpaceMoneyError::InsufficientTreasuryBalance
+            );
+            
+            let vault_bump = ctx.bumps.vault_authority;
+            let seeds = &[VAULT_SEED, &[vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = Transfer {
+                from: platform_token_account.to_account_info(),
+                to: referrer_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, amount)?;
+        }
+    }
+    
+    referral_account.settle(token_type)?;
+    
+    emit_event!(ctx, ReferralRewardsClaimedEvent {
+        referrer: ctx.accounts.referrer.key(),
+        amount,
+        token_type,
+        total_claimed: match token_type {
+            TokenType::SOL => referral_account.total_claimed_sol,
+            TokenType::USDT => referral_account.total_claimed_usdt,
+        },
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -2,10 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::{ClaimedRewardsEvent, TokenType};
+use crate::events::{ClaimedRewardsEvent, InsuranceBackstopEvent, PlatformTotals};
 use crate::states::*;
 use crate::utils::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
     #[account(
@@ -17,9 +18,8 @@ pub struct ClaimRewards<'info> {
     
     #[account(
         mut,
-        seeds = [USER_SEED, user.key().as_ref()],
-        bump = user_account.bump,
-        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
     )]
     pub user_account: Account<'info, UserAccount>,
     
@@ -45,6 +45,16 @@ pub struct ClaimRewards<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
     
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Covers what the vault is short of a SOL claim, if passed
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -67,46 +77,49 @@ pub fn handler(ctx: Context<ClaimRewards>, stake_index: u64) -> Result<()> {
         SpaceMoneyError::InvalidStakeIndex
     );
     
-    let stake = &mut user_account.stakes[index];
-    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
-    
-    // Calculate accrued rewards
-    let (_, multiplier, lock_days) = match stake.tier {
-        Tier::Boot => (BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS),
-        Tier::Symbiotic => (SYMBIOTIC_MIN_STAKE, SYMBIOTIC_MULTIPLIER, SYMBIOTIC_LOCK_DAYS),
-        Tier::Space => (SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS),
-    };
-    
-    // Calculate rewards up to current time (but cap at max rewards)
-    let max_rewards = calculate_rewards(stake.amount, multiplier, lock_days)?;
-    let accrued_rewards = calculate_accrued_rewards(
-        stake.amount,
-        multiplier,
-        stake.deposited_at,
-        clock.unix_timestamp,
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &user_account.stakes[index],
+        user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
     )?;
     
-    // Cap at max rewards
-    let total_available_rewards = if accrued_rewards > max_rewards {
-        max_rewards
-    } else {
-        accrued_rewards
-    };
+    let stake = &mut user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
     
-    // Calculate claimable amount
-    let claimable = total_available_rewards
-        .checked_sub(stake.claimed_rewards)
-        .ok_or(SpaceMoneyError::CalculationError)?;
+    // Rewards accrued up to now, capped at the full lock period, less what was claimed
+    let claimable = stake
+        .terms(ctx.accounts.tier_config.tier(stake.tier)?)
+        .claimable_at(clock.unix_timestamp)
+        .map_err(SpaceMoneyError::from)?;
     
     require!(claimable > 0, SpaceMoneyError::NoRewardsAvailable);
     
     // Transfer rewards based on token type
     match stake.token_type {
         TokenType::SOL => {
-            // Check balance
-            let platform_balance = platform_state.to_account_info().lamports();
+            // Draw any shortfall from the insurance fund, which must then be passed
+            let platform_info = platform_state.to_account_info();
+            let available = platform_state.available_for_stakes(TokenType::SOL, vault_sol_balance(&platform_info)?);
+            let shortfall = backstop_sol(&platform_info, ctx.accounts.insurance_fund.as_mut(), available, claimable)?;
+            if let Some(insurance_fund) = ctx.accounts.insurance_fund.as_ref().filter(|_| shortfall > 0) {
+                emit_event!(ctx, InsuranceBackstopEvent {
+                    user: ctx.accounts.user.key(),
+                    stake_index,
+                    token_type: TokenType::SOL,
+                    shortfall,
+                    balance: insurance_fund.balance_sol,
+                    coverage_bps: insurance_fund.coverage_bps(TokenType::SOL, platform_state.total_staked_sol),
+                    timestamp: clock.unix_timestamp,
+                    totals: PlatformTotals::of(platform_state),
+                });
+            }
+            
+            // Check balance above the vault's rent
+            let platform_balance = vault_sol_balance(&platform_info)?;
             require!(
-                platform_balance >= claimable,
+                platform_state.available_for_stakes(TokenType::SOL, platform_balance) >= claimable,
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
@@ -129,7 +142,7 @@ pub fn handler(ctx: Context<ClaimRewards>, stake_index: u64) -> Result<()> {
             
             // Check balance
             require!(
-                platform_token_account.amount >= claimable,
+                platform_state.available_for_stakes(TokenType::USDT, platform_token_account.amount) >= claimable,
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
@@ -165,12 +178,18 @@ pub fn handler(ctx: Context<ClaimRewards>, stake_index: u64) -> Result<()> {
     // Update last claim time
     user_account.last_claim_time = clock.unix_timestamp;
     
-    emit!(ClaimedRewardsEvent {
+    // Copy out the stake so the event can read the account totals too
+    let stake = user_account.stakes[index];
+    
+    emit_event!(ctx, ClaimedRewardsEvent {
         user: ctx.accounts.user.key(),
         stake_index,
         amount: claimable,
         token_type: stake.token_type,
         timestamp: clock.unix_timestamp,
+        stake_claimed_rewards: stake.claimed_rewards,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{DistributorClosedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CloseDistributor<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [DISTRIBUTOR_SEED, &distributor.id.to_le_bytes()],
+        bump = distributor.bump
+    )]
+    pub distributor: Account<'info, Distributor>,
+    
+    #[account(
+        mut,
+        close = admin,
+        seeds = [CLAIMED_BITMAP_SEED, distributor.key().as_ref()],
+        bump = claimed_bitmap.bump
+    )]
+    pub claimed_bitmap: Account<'info, ClaimedBitmap>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+// Returns what is left unclaimed to the treasury and closes the distributor,
+// so its leaves can no longer be claimed
+pub fn handler(ctx: Context<CloseDistributor>) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let distributor = &ctx.accounts.distributor;
+    let clock = Clock::get()?;
+    
+    // The funds never left the vault, so only the treasury's share moves back
+    let returned_amount = distributor.remaining();
+    let treasury = match distributor.token_type {
+        TokenType::SOL => &mut platform_state.treasury_sol,
+        TokenType::USDT => &mut platform_state.treasury_usdt,
+    };
+    *treasury = treasury
+        .checked_add(returned_amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    emit_event!(ctx, DistributorClosedEvent {
+        admin: ctx.accounts.admin.key(),
+        distributor_id: distributor.id,
+        token_type: distributor.token_type,
+        claimed_amount: distributor.claimed_amount,
+        claimed_count: distributor.claimed_count,
+        returned_amount,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{InsuranceBackstopEvent, LoyaltyUpdatedEvent, PlatformTotals, WithdrewEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CompleteUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    // USDT stakes only
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = user_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = platform_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub platform_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: PDA authority for token transfers
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Covers what the vault is short of a SOL payout, if passed
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CompleteUnstake>, stake_index: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!ctx.accounts.platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Get stake
+    let index = stake_index as usize;
+    require!(
+        index < ctx.accounts.user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &ctx.accounts.user_account.stakes[index],
+        ctx.accounts.user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
+    )?;
+    
+    let stake = ctx.accounts.user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(stake.is_unbonding, SpaceMoneyError::StakeNotUnbonding);
+    require!(
+        clock.unix_timestamp >= stake.unbonding_until,
+        SpaceMoneyError::UnbondingNotComplete
+    );
+    
+    // Rewards stopped when the unstake was requested
+    let unclaimed_rewards = stake
+        .terms(ctx.accounts.tier_config.tier(stake.tier)?)
+        .unclaimed_at_withdrawal(clock.unix_timestamp)
+        .map_err(SpaceMoneyError::from)?;
+    
+    let total_withdrawal = stake.amount
+        .checked_add(unclaimed_rewards)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    match stake.token_type {
+        TokenType::SOL => {
+            let platform_state = &mut ctx.accounts.platform_state;
+            
+            // Draw any shortfall from the insurance fund, which must then be passed
+            let platform_info = platform_state.to_account_info();
+            let available = platform_state.available_for_stakes(TokenType::SOL, vault_sol_balance(&platform_info)?);
+            let shortfall = backstop_sol(&platform_info, ctx.accounts.insurance_fund.as_mut(), available, total_withdrawal)?;
+            if let Some(insurance_fund) = ctx.accounts.insurance_fund.as_ref().filter(|_| shortfall > 0) {
+                emit_event!(ctx, InsuranceBackstopEvent {
+                    user: ctx.accounts.user.key(),
+                    stake_index,
+                    token_type: TokenType::SOL,
+                    shortfall,
+                    balance: insurance_fund.balance_sol,
+                    coverage_bps: insurance_fund.coverage_bps(TokenType::SOL, platform_state.total_staked_sol),
+                    timestamp: clock.unix_timestamp,
+                    totals: PlatformTotals::of(platform_state),
+                });
+            }
+            
+            // Check platform has enough balance above its rent
+            let platform_balance = vault_sol_balance(&platform_info)?;
+            require!(
+                platform_state.available_for_stakes(TokenType::SOL, platform_balance) >= total_withdrawal,
+                SpaceMoneyError::InsufficientTreasuryBalance
+            );
+            
+            // Transfer SOL from platform to user
+            **platform_state.to_account_info().try_borrow_mut_lamports()? -= total_withdrawal;
+            **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += total_withdrawal;
+            
+            platform_state.total_staked_sol = platform_state.total_staked_sol
+                .checked_sub(stake.amount)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            
+            let user_account = &mut ctx.accounts.user_account;
+            user_account.total_claimed_sol = user_account.total_claimed_sol
+                .checked_add(unclaimed_rewards)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        }
+        TokenType::USDT => {
+            let platform_token_account = ctx.accounts.platform_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let user_token_account = ctx.accounts.user_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let token_program = ctx.accounts.token_program.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            
+            // Check platform has enough USDT
+            require!(
+                ctx.accounts.platform_state.available_for_stakes(TokenType::USDT, platform_token_account.amount) >= total_withdrawal,
+                SpaceMoneyError::InsufficientTreasuryBalance
+            );
+            
+            // Transfer USDT from platform to user
+            let vault_bump = ctx.bumps.vault_authority;
+            let seeds = &[VAULT_SEED, &[vault_bump]];
+            let signer_seeds = &[&seeds[..]];
+            
+            let cpi_accounts = Transfer {
+                from: platform_token_account.to_account_info(),
+                to: user_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token::transfer(cpi_ctx, total_withdrawal)?;
+            
+            let platform_state = &mut ctx.accounts.platform_state;
+            platform_state.total_staked_usdt = platform_state.total_staked_usdt
+                .checked_sub(stake.amount)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            
+            let user_account = &mut ctx.accounts.user_account;
+            user_account.total_claimed_usdt = user_account.total_claimed_usdt
+                .checked_add(unclaimed_rewards)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        }
+    }
+    
+    // The stake counts towards the owner's loyalty score
+    let user_account = &mut ctx.accounts.user_account;
+    let stake_days_added = user_account.record_loyalty(index, clock.unix_timestamp)?;
+    
+    // Mark stake as inactive
+    let completed = &mut user_account.stakes[index];
+    completed.is_active = false;
+    completed.is_unbonding = false;
+    
+    // Free the stake's place in its tier
+    ctx.accounts.tier_config
+        .tier_mut(stake.tier)?
+        .remove_stake(stake.token_type, stake.amount);
+    
+    emit_event!(ctx, WithdrewEvent {
+        user: ctx.accounts.user.key(),
+        stake_index,
+        principal: stake.amount,
+        rewards: unclaimed_rewards,
+        token_type: stake.token_type,
+        timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    emit_event!(ctx, LoyaltyUpdatedEvent {
+        user: user_account.user,
+        stake_index,
+        stake_days_added,
+        stake_days: user_account.loyalty_stake_days,
+        level: user_account.loyalty_level(),
+        bonus_bps: user_account.loyalty_bonus_bps(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
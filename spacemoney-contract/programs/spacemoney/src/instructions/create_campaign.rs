@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{CampaignCreatedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct CreateCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    // Campaigns are numbered in order of creation
+    #[account(
+        init,
+        payer = admin,
+        space = Campaign::LEN,
+        seeds = [CAMPAIGN_SEED, &platform_state.campaign_count.to_le_bytes()],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateCampaign>,
+    start_time: i64,
+    end_time: i64,
+    tier_mask: u16,
+    boost_bps: u16,
+    budget_sol: u64,
+    budget_usdt: u64,
+) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    require!(
+        start_time < end_time && end_time > clock.unix_timestamp,
+        SpaceMoneyError::InvalidCampaignWindow
+    );
+    require!(
+        boost_bps > 0 && boost_bps as u64 <= MAX_CAMPAIGN_BOOST_BPS,
+        SpaceMoneyError::InvalidCampaignBoost
+    );
+    require!(
+        tier_mask != 0 && (budget_sol > 0 || budget_usdt > 0),
+        SpaceMoneyError::EmptyCampaign
+    );
+    
+    let campaign_id = platform_state.campaign_count;
+    ctx.accounts.campaign.set_inner(Campaign {
+        id: campaign_id,
+        start_time,
+        end_time,
+        tier_mask,
+        boost_bps,
+        budget_sol,
+        budget_usdt,
+        spent_sol: 0,
+        spent_usdt: 0,
+        boosted_deposits: 0,
+        sol_exhausted: false,
+        usdt_exhausted: false,
+        ended_at: 0,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.campaign,
+        version: Campaign::VERSION,
+        reserved: [0; Campaign::RESERVED],
+    });
+    
+    platform_state.campaign_count = campaign_id
+        .checked_add(1)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    emit_event!(ctx, CampaignCreatedEvent {
+        admin: ctx.accounts.admin.key(),
+        campaign_id,
+        start_time,
+        end_time,
+        tier_mask,
+        boost_bps,
+        budget_sol,
+        budget_usdt,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{DistributorCreatedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], token_type: TokenType, total_amount: u64, max_claims: u32)]
+pub struct CreateDistributor<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    // Distributors are numbered in order of creation
+    #[account(
+        init,
+        payer = admin,
+        space = Distributor::LEN,
+        seeds = [DISTRIBUTOR_SEED, &platform_state.distributor_count.to_le_bytes()],
+        bump
+    )]
+    pub distributor: Account<'info, Distributor>,
+    
+    // Capped so an oversized request fails in the handler, not in the allocation
+    #[account(
+        init,
+        payer = admin,
+        space = ClaimedBitmap::space_for(max_claims.min(MAX_DISTRIBUTOR_CLAIMS)),
+        seeds = [CLAIMED_BITMAP_SEED, distributor.key().as_ref()],
+        bump
+    )]
+    pub claimed_bitmap: Account<'info, ClaimedBitmap>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// Sets aside `total_amount` of the treasury for claims against `merkle_root`
+pub fn handler(
+    ctx: Context<CreateDistributor>,
+    merkle_root: [u8; 32],
+    token_type: TokenType,
+    total_amount: u64,
+    max_claims: u32,
+) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    require!(
+        total_amount > 0 && max_claims > 0 && max_claims <= MAX_DISTRIBUTOR_CLAIMS,
+        SpaceMoneyError::InvalidDistributor
+    );
+    
+    // The bonus comes out of the treasury, so admin_transfer can't spend it twice
+    let treasury = match token_type {
+        TokenType::SOL => &mut platform_state.treasury_sol,
+        TokenType::USDT => &mut platform_state.treasury_usdt,
+    };
+    require!(*treasury >= total_amount, SpaceMoneyError::InsufficientTreasuryBalance);
+    *treasury -= total_amount;
+    
+    let distributor_id = platform_state.distributor_count;
+    ctx.accounts.distributor.set_inner(Distributor {
+        id: distributor_id,
+        merkle_root,
+        token_type,
+        total_amount,
+        claimed_amount: 0,
+        max_claims,
+        claimed_count: 0,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.distributor,
+        version: Distributor::VERSION,
+        reserved: [0; Distributor::RESERVED],
+    });
+    ctx.accounts.claimed_bitmap.set_inner(ClaimedBitmap {
+        distributor: ctx.accounts.distributor.key(),
+        bump: ctx.bumps.claimed_bitmap,
+        bits: vec![0; ClaimedBitmap::bytes_for(max_claims)],
+    });
+    
+    platform_state.distributor_count = distributor_id
+        .checked_add(1)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    emit_event!(ctx, DistributorCreatedEvent {
+        admin: ctx.accounts.admin.key(),
+        distributor_id,
+        merkle_root,
+        token_type,
+        total_amount,
+        max_claims,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::{DepositedEvent, TokenType};
+use crate::events::{
+    CampaignBoostedEvent, CampaignEndedEvent, DepositedEvent, InsuranceContributedEvent,
+    MintedReceiptEvent, MintedSpsolEvent, PlatformTotals, ReferralCreditedEvent,
+};
 use crate::states::*;
 use crate::utils::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct DepositSol<'info> {
     #[account(
@@ -24,15 +30,100 @@ pub struct DepositSol<'info> {
     pub user_account: Account<'info, UserAccount>,
     
     #[account(
+        mut,
         seeds = [TIERS_SEED],
         bump = tier_config.bump
     )]
     pub tier_config: Account<'info, TierConfig>,
     
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            RECEIPT_SEED,
+            user_account.key().as_ref(),
+            &(user_account.stakes.len() as u64).to_le_bytes()
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = vault_authority,
+    )]
+    pub receipt_mint: Option<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = user,
+        space = StakeReceipt::LEN,
+        seeds = [
+            RECEIPT_METADATA_SEED,
+            user_account.key().as_ref(),
+            &(user_account.stakes.len() as u64).to_le_bytes()
+        ],
+        bump
+    )]
+    pub receipt_metadata: Option<Account<'info, StakeReceipt>>,
+    
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = user,
+    )]
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [SPSOL_MINT_SEED],
+        bump,
+        constraint = spsol_mint.key() == platform_state.spsol_mint @ SpaceMoneyError::InvalidSpsolMint,
+    )]
+    pub spsol_mint: Option<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_spsol_account.owner == user.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = user_spsol_account.mint == platform_state.spsol_mint @ SpaceMoneyError::InvalidSpsolMint,
+    )]
+    pub user_spsol_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: PDA authority for receipt and spSOL minting
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: Option<AccountInfo<'info>>,
+    
+    // The depositor's referrer, if any
+    #[account(
+        mut,
+        seeds = [REFERRAL_SEED, referrer.referrer.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.referrer != user.key() @ SpaceMoneyError::SelfReferral,
+    )]
+    pub referrer: Option<Account<'info, ReferralAccount>>,
+    
+    // A running campaign to boost the stake with
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, &campaign.id.to_le_bytes()],
+        bump = campaign.bump
+    )]
+    pub campaign: Option<Account<'info, Campaign>>,
+    
+    // Required while an insurance share is set
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
     pub system_program: Program<'info, System>,
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
 }
 
 pub fn handler(ctx: Context<DepositSol>, amount: u64, tier: u8) -> Result<()> {
@@ -44,16 +135,23 @@ pub fn handler(ctx: Context<DepositSol>, amount: u64, tier: u8) -> Result<()> {
     require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
     
     // Validate tier and amount
-    validate_stake_amount(amount, tier)?;
+    let tier_info = ctx.accounts.tier_config.tier(tier)?;
+    require!(tier_info.enabled, SpaceMoneyError::TierDisabled);
+    tier_info.check_open(clock.unix_timestamp)?;
+    require!(
+        amount >= tier_info.min_stake,
+        SpaceMoneyError::InsufficientStakeAmount
+    );
     
     // Calculate fee and net deposit
     let (net_amount, fee) = calculate_net_deposit(amount)?;
     
-    // Get tier configuration
-    let (_, multiplier, lock_days) = get_tier_config(tier)?;
     let lock_until = clock.unix_timestamp
-        .checked_add(lock_days * SECONDS_PER_DAY)
+        .checked_add(tier_info.lock_seconds)
         .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    let (post_lock, post_lock_rate_bps) = (tier_info.post_lock, tier_info.post_lock_rate_bps);
+    let (multiplier, lock_days) = tier_info.fixed_terms()?;
+    let (accrual_mode, rounding) = (tier_info.accrual_mode, tier_info.rounding);
     
     // Transfer SOL from user to platform
     let ix = anchor_lang::system_program::Transfer {
@@ -66,12 +164,50 @@ pub fn handler(ctx: Context<DepositSol>, amount: u64, tier: u8) -> Result<()> {
     );
     anchor_lang::system_program::transfer(cpi_ctx, amount)?;
     
+    // Credit the referrer's share of the fee; the treasury keeps the rest
+    let mut treasury_fee = fee;
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        let reward = calculate_referral_reward(fee, platform_state.referral_share_bps)?;
+        referrer.credit(TokenType::SOL, amount, reward)?;
+        treasury_fee -= reward;
+        
+        emit_event!(ctx, ReferralCreditedEvent {
+            referrer: referrer.referrer,
+            user: ctx.accounts.user.key(),
+            token_type: TokenType::SOL,
+            volume: amount,
+            reward,
+            pending: referrer.pending(TokenType::SOL),
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(platform_state),
+        });
+    }
+    
+    // The insurance fund takes its share of what the treasury would keep
+    let insurance_share = calculate_insurance_share(treasury_fee, platform_state.insurance_share_bps)?;
+    if insurance_share > 0 {
+        let insurance_fund = ctx.accounts.insurance_fund.as_mut()
+            .ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+        **platform_state.to_account_info().try_borrow_mut_lamports()? -= insurance_share;
+        **insurance_fund.to_account_info().try_borrow_mut_lamports()? += insurance_share;
+        insurance_fund.contribute(TokenType::SOL, insurance_share)?;
+        treasury_fee -= insurance_share;
+        
+        emit_event!(ctx, InsuranceContributedEvent {
+            user: ctx.accounts.user.key(),
+            source: InsuranceSource::DepositFee,
+            token_type: TokenType::SOL,
+            amount: insurance_share,
+            balance: insurance_fund.balance_sol,
+            coverage_bps: insurance_fund.coverage_bps(TokenType::SOL, platform_state.total_staked_sol),
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(platform_state),
+        });
+    }
+    
     // Update platform state
     platform_state.treasury_sol = platform_state.treasury_sol
-        .checked_add(fee)
-        .ok_or(SpaceMoneyError::NumericalOverflow)?;
-    platform_state.total_staked_sol = platform_state.total_staked_sol
-        .checked_add(net_amount)
+        .checked_add(treasury_fee)
         .ok_or(SpaceMoneyError::NumericalOverflow)?;
     
     // Initialize user account if needed
@@ -82,23 +218,196 @@ pub fn handler(ctx: Context<DepositSol>, amount: u64, tier: u8) -> Result<()> {
         user_account.total_claimed_usdt = 0;
         user_account.last_claim_time = clock.unix_timestamp;
         user_account.bump = ctx.bumps.user_account;
+        user_account.version = UserAccount::VERSION;
+    }
+    
+    // Liquid mode mints spSOL instead of creating a locked stake
+    if let Some(spsol_mint) = ctx.accounts.spsol_mint.as_ref() {
+        require!(
+            ctx.accounts.receipt_mint.is_none() && ctx.accounts.campaign.is_none(),
+            SpaceMoneyError::InvalidLiquidDeposit
+        );
+        
+        let user_spsol_account = ctx.accounts.user_spsol_account.as_ref()
+            .ok_or(SpaceMoneyError::LiquidStakingDisabled)?;
+        let vault_authority = ctx.accounts.vault_authority.as_ref()
+            .ok_or(SpaceMoneyError::LiquidStakingDisabled)?;
+        let token_program = ctx.accounts.token_program.as_ref()
+            .ok_or(SpaceMoneyError::LiquidStakingDisabled)?;
+        
+        let spsol_amount = calculate_spsol_to_mint(
+            net_amount,
+            platform_state.liquid_staked_sol,
+            spsol_mint.supply,
+        )?;
+        
+        platform_state.liquid_staked_sol = platform_state.liquid_staked_sol
+            .checked_add(net_amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        
+        let vault_bump = ctx.bumps.vault_authority;
+        let seeds = &[VAULT_SEED, &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = MintTo {
+            mint: spsol_mint.to_account_info(),
+            to: user_spsol_account.to_account_info(),
+            authority: vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::mint_to(cpi_ctx, spsol_amount)?;
+        
+        // The mint account isn't reloaded after the CPI
+        let spsol_supply = spsol_mint.supply
+            .checked_add(spsol_amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        
+        emit_event!(ctx, MintedSpsolEvent {
+            user: ctx.accounts.user.key(),
+            sol_amount: net_amount,
+            spsol_amount,
+            fee,
+            liquid_staked_sol: platform_state.liquid_staked_sol,
+            timestamp: clock.unix_timestamp,
+            spsol_supply,
+            totals: PlatformTotals::of(platform_state),
+        });
+        
+        return Ok(());
     }
     
+    // Update staked amount
+    platform_state.total_staked_sol = platform_state.total_staked_sol
+        .checked_add(net_amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    // Count the stake against the tier's caps
+    let user_staked = user_account.staked_in_tier(tier, TokenType::SOL)?;
+    ctx.accounts.tier_config
+        .tier_mut(tier)?
+        .add_stake(TokenType::SOL, net_amount, user_staked)?;
+    
     // Create stake
-    let tier_enum = Tier::from_u8(tier).ok_or(SpaceMoneyError::InvalidTier)?;
-    let stake = UserStake {
+    let stake_index = user_account.stakes.len() as u64;
+    let loyalty_bonus_bps = user_account.loyalty_bonus_bps();
+    let receipt_mint = ctx.accounts.receipt_mint.as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+    let mut stake = UserStake {
         amount: net_amount,
-        tier: tier_enum,
+        tier,
         token_type: TokenType::SOL,
         deposited_at: clock.unix_timestamp,
         lock_until,
         claimed_rewards: 0,
         is_active: true,
+        receipt_mint,
+        post_lock,
+        post_lock_rate_bps,
+        is_unbonding: false,
+        unstake_requested_at: 0,
+        unbonding_until: 0,
+        loyalty_bonus_bps,
+        campaign_boost_bps: 0,
+        terms_fixed: true,
+        multiplier,
+        lock_days,
+        accrual_mode,
+        rounding,
+        reserved: [0; UserStake::RESERVED],
     };
     
+    // Boost the stake if the campaign covers it and its budget still can.
+    // A spent budget doesn't fail the deposit, so running out is recorded.
+    if let Some(campaign) = ctx.accounts.campaign.as_mut() {
+        campaign.check_eligible(&stake, clock.unix_timestamp)?;
+        let tier_info = ctx.accounts.tier_config.tier(tier)?;
+        if let Some(reserved) = campaign.reserve(&stake, tier_info)? {
+            stake.campaign_boost_bps = campaign.boost_bps;
+            
+            emit_event!(ctx, CampaignBoostedEvent {
+                campaign_id: campaign.id,
+                user: ctx.accounts.user.key(),
+                stake_index,
+                token_type: TokenType::SOL,
+                boost_bps: campaign.boost_bps,
+                reserved,
+                spent: campaign.spent(TokenType::SOL),
+                budget: campaign.budget(TokenType::SOL),
+                timestamp: clock.unix_timestamp,
+                totals: PlatformTotals::of(platform_state),
+            });
+        } else if campaign.is_exhausted() {
+            campaign.ended_at = clock.unix_timestamp;
+            
+            emit_event!(ctx, CampaignEndedEvent {
+                campaign_id: campaign.id,
+                reason: CampaignEndReason::BudgetExhausted,
+                spent_sol: campaign.spent_sol,
+                spent_usdt: campaign.spent_usdt,
+                boosted_deposits: campaign.boosted_deposits,
+                timestamp: clock.unix_timestamp,
+                totals: PlatformTotals::of(platform_state),
+            });
+        }
+    }
+    
+    reserve_stake_space(
+        user_account,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
     user_account.stakes.push(stake);
     
-    emit!(DepositedEvent {
+    // Mint a receipt NFT for the stake if requested
+    if let Some(receipt_mint) = ctx.accounts.receipt_mint.as_ref() {
+        let receipt_metadata = ctx.accounts.receipt_metadata.as_mut()
+            .ok_or(SpaceMoneyError::MissingReceiptAccounts)?;
+        let receipt_token_account = ctx.accounts.receipt_token_account.as_ref()
+            .ok_or(SpaceMoneyError::MissingReceiptAccounts)?;
+        let vault_authority = ctx.accounts.vault_authority.as_ref()
+            .ok_or(SpaceMoneyError::MissingReceiptAccounts)?;
+        let token_program = ctx.accounts.token_program.as_ref()
+            .ok_or(SpaceMoneyError::MissingReceiptAccounts)?;
+        
+        receipt_metadata.set_inner(StakeReceipt {
+            mint: receipt_mint.key(),
+            user_account: user_account.key(),
+            stake_index,
+            amount: stake.amount,
+            tier: stake.tier,
+            token_type: stake.token_type,
+            deposited_at: stake.deposited_at,
+            lock_until: stake.lock_until,
+            bump: ctx.bumps.receipt_metadata,
+        });
+        
+        let vault_bump = ctx.bumps.vault_authority;
+        let seeds = &[VAULT_SEED, &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        mint_stake_receipt(
+            receipt_mint,
+            receipt_token_account,
+            vault_authority,
+            token_program,
+            signer_seeds,
+        )?;
+        
+        emit_event!(ctx, MintedReceiptEvent {
+            user: ctx.accounts.user.key(),
+            stake_index,
+            mint: receipt_mint.key(),
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(platform_state),
+        });
+    }
+    
+    emit_event!(ctx, DepositedEvent {
         user: ctx.accounts.user.key(),
         amount: net_amount,
         tier,
@@ -106,6 +415,11 @@ pub fn handler(ctx: Context<DepositSol>, amount: u64, tier: u8) -> Result<()> {
         fee,
         lock_until,
         timestamp: clock.unix_timestamp,
+        stake_index,
+        active_stakes: user_account.active_stakes(),
+        loyalty_bonus_bps,
+        campaign_boost_bps: stake.campaign_boost_bps,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierDisabledEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct DisableTier<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DisableTier>, tier: u8) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    // Only closes the tier to new deposits; its stakes still claim and withdraw
+    let tier_info = tier_config.tier_mut(tier)?;
+    require!(tier_info.enabled, SpaceMoneyError::TierDisabled);
+    tier_info.enabled = false;
+    
+    emit_event!(ctx, TierDisabledEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{CampaignEndedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct EndCampaign<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, &campaign.id.to_le_bytes()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+    
+    pub authority: Signer<'info>,
+}
+
+// Anyone can close out a campaign past its end time; the admin can also
+// cancel one early
+pub fn handler(ctx: Context<EndCampaign>) -> Result<()> {
+    let campaign = &mut ctx.accounts.campaign;
+    let clock = Clock::get()?;
+    
+    require!(campaign.ended_at == 0, SpaceMoneyError::CampaignEnded);
+    
+    let reason = if clock.unix_timestamp >= campaign.end_time {
+        CampaignEndReason::Expired
+    } else {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.platform_state.admin,
+            SpaceMoneyError::CampaignStillRunning
+        );
+        CampaignEndReason::Cancelled
+    };
+    campaign.ended_at = clock.unix_timestamp;
+    
+    emit_event!(ctx, CampaignEndedEvent {
+        campaign_id: campaign.id,
+        reason,
+        spent_sol: campaign.spent_sol,
+        spent_usdt: campaign.spent_usdt,
+        boosted_deposits: campaign.boosted_deposits,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -2,10 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::{ForceWithdrewEvent, TokenType};
+use crate::events::{ForceWithdrewEvent, InsuranceBackstopEvent, InsuranceContributedEvent, LoyaltyUpdatedEvent, PlatformTotals};
 use crate::states::*;
 use crate::utils::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct ForceWithdraw<'info> {
     #[account(
@@ -17,13 +18,13 @@ pub struct ForceWithdraw<'info> {
     
     #[account(
         mut,
-        seeds = [USER_SEED, user.key().as_ref()],
-        bump = user_account.bump,
-        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
     )]
     pub user_account: Account<'info, UserAccount>,
     
     #[account(
+        mut,
         seeds = [TIERS_SEED],
         bump = tier_config.bump
     )]
@@ -45,6 +46,24 @@ pub struct ForceWithdraw<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
     
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Required while an insurance share is set or the vault is short of a SOL
+    // payout; the vault for USDT stakes only
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump
+    )]
+    pub insurance_vault: Option<Account<'info, TokenAccount>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -67,50 +86,67 @@ pub fn handler(ctx: Context<ForceWithdraw>, stake_index: u64) -> Result<()> {
         SpaceMoneyError::InvalidStakeIndex
     );
     
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &user_account.stakes[index],
+        user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
+    )?;
+    
     let stake = &mut user_account.stakes[index];
     require!(stake.is_active, SpaceMoneyError::StakeNotFound);
     
     // Can only force withdraw if still locked
+    let terms = stake.terms(ctx.accounts.tier_config.tier(stake.tier)?);
     require!(
-        clock.unix_timestamp < stake.lock_until,
+        terms.is_locked_at(clock.unix_timestamp),
         SpaceMoneyError::StakeLocked // Use normal withdraw if unlocked
     );
     
-    // Calculate accrued rewards
-    let (_, multiplier, _) = match stake.tier {
-        Tier::Boot => (BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS),
-        Tier::Symbiotic => (SYMBIOTIC_MIN_STAKE, SYMBIOTIC_MULTIPLIER, SYMBIOTIC_LOCK_DAYS),
-        Tier::Space => (SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS),
-    };
-    
-    let accrued_rewards = calculate_accrued_rewards(
-        stake.amount,
-        multiplier,
-        stake.deposited_at,
-        clock.unix_timestamp,
-    )?;
-    
-    let unclaimed_rewards = accrued_rewards
-        .checked_sub(stake.claimed_rewards)
-        .ok_or(SpaceMoneyError::CalculationError)?;
+    // Unclaimed rewards less the tier's penalty, which may also take principal
+    // and claw back rewards already claimed
+    let quote = terms
+        .force_withdraw_at(clock.unix_timestamp)
+        .map_err(SpaceMoneyError::from)?;
+    let rewards_after_penalty = quote.rewards_after_penalty;
+    let penalty = quote.penalty;
+    let clawback = quote.breakdown.clawback;
+    let total_withdrawal = quote.total_withdrawal;
     
-    // Apply 20% penalty
-    let penalty = calculate_force_withdraw_penalty(unclaimed_rewards)?;
-    let rewards_after_penalty = unclaimed_rewards
-        .checked_sub(penalty)
-        .ok_or(SpaceMoneyError::CalculationError)?;
+    // The insurance fund takes its share of the penalty; the treasury keeps the rest
+    let insurance_share = calculate_insurance_share(penalty, platform_state.insurance_share_bps)?;
+    let treasury_penalty = penalty - insurance_share;
     
-    let total_withdrawal = stake.amount
-        .checked_add(rewards_after_penalty)
+    // Both leave the vault
+    let vault_outflow = total_withdrawal
+        .checked_add(insurance_share)
         .ok_or(SpaceMoneyError::NumericalOverflow)?;
     
     // Handle withdrawal based on token type
     match stake.token_type {
         TokenType::SOL => {
-            // Check balance
-            let platform_balance = platform_state.to_account_info().lamports();
+            // Draw any shortfall from the insurance fund, which must then be passed
+            let platform_info = platform_state.to_account_info();
+            let available = platform_state.available_for_stakes(TokenType::SOL, vault_sol_balance(&platform_info)?);
+            let shortfall = backstop_sol(&platform_info, ctx.accounts.insurance_fund.as_mut(), available, vault_outflow)?;
+            if let Some(insurance_fund) = ctx.accounts.insurance_fund.as_ref().filter(|_| shortfall > 0) {
+                emit_event!(ctx, InsuranceBackstopEvent {
+                    user: ctx.accounts.user.key(),
+                    stake_index,
+                    token_type: TokenType::SOL,
+                    shortfall,
+                    balance: insurance_fund.balance_sol,
+                    coverage_bps: insurance_fund.coverage_bps(TokenType::SOL, platform_state.total_staked_sol),
+                    timestamp: clock.unix_timestamp,
+                    totals: PlatformTotals::of(platform_state),
+                });
+            }
+            
+            // Check balance above the vault's rent
+            let platform_balance = vault_sol_balance(&platform_info)?;
             require!(
-                platform_balance >= total_withdrawal,
+                platform_state.available_for_stakes(TokenType::SOL, platform_balance) >= vault_outflow,
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
@@ -118,9 +154,17 @@ pub fn handler(ctx: Context<ForceWithdraw>, stake_index: u64) -> Result<()> {
             **platform_state.to_account_info().try_borrow_mut_lamports()? -= total_withdrawal;
             **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += total_withdrawal;
             
+            // Move the insurance fund's share out of the vault
+            if insurance_share > 0 {
+                let insurance_fund = ctx.accounts.insurance_fund.as_ref()
+                    .ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+                **platform_state.to_account_info().try_borrow_mut_lamports()? -= insurance_share;
+                **insurance_fund.to_account_info().try_borrow_mut_lamports()? += insurance_share;
+            }
+            
             // Add penalty to treasury
             platform_state.treasury_sol = platform_state.treasury_sol
-                .checked_add(penalty)
+                .checked_add(treasury_penalty)
                 .ok_or(SpaceMoneyError::NumericalOverflow)?;
             
             // Update staked amount
@@ -128,10 +172,11 @@ pub fn handler(ctx: Context<ForceWithdraw>, stake_index: u64) -> Result<()> {
                 .checked_sub(stake.amount)
                 .ok_or(SpaceMoneyError::NumericalOverflow)?;
             
-            // Update user claimed
+            // Update user claimed, less anything clawed back
             user_account.total_claimed_sol = user_account.total_claimed_sol
                 .checked_add(rewards_after_penalty)
-                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+                .ok_or(SpaceMoneyError::NumericalOverflow)?
+                .saturating_sub(clawback);
         },
         TokenType::USDT => {
             let platform_token_account = ctx.accounts.platform_token_account.as_ref()
@@ -143,7 +188,7 @@ pub fn handler(ctx: Context<ForceWithdraw>, stake_index: u64) -> Result<()> {
             
             // Check balance
             require!(
-                platform_token_account.amount >= total_withdrawal,
+                platform_state.available_for_stakes(TokenType::USDT, platform_token_account.amount) >= vault_outflow,
                 SpaceMoneyError::InsufficientTreasuryBalance
             );
             
@@ -164,29 +209,99 @@ pub fn handler(ctx: Context<ForceWithdraw>, stake_index: u64) -> Result<()> {
             );
             token::transfer(cpi_ctx, total_withdrawal)?;
             
+            // Move the insurance fund's share out of the vault
+            if insurance_share > 0 {
+                let insurance_vault = ctx.accounts.insurance_vault.as_ref()
+                    .ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+                let cpi_accounts = Transfer {
+                    from: platform_token_account.to_account_info(),
+                    to: insurance_vault.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, insurance_share)?;
+            }
+            
             // Add penalty to treasury
             platform_state.treasury_usdt = platform_state.treasury_usdt
-                .checked_add(penalty)
+                .checked_add(treasury_penalty)
                 .ok_or(SpaceMoneyError::NumericalOverflow)?;
             
-            // Update user claimed
+            // Update staked amount
+            platform_state.total_staked_usdt = platform_state.total_staked_usdt
+                .checked_sub(stake.amount)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            
+            // Update user claimed, less anything clawed back
             user_account.total_claimed_usdt = user_account.total_claimed_usdt
                 .checked_add(rewards_after_penalty)
-                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+                .ok_or(SpaceMoneyError::NumericalOverflow)?
+                .saturating_sub(clawback);
         },
     }
     
     // Mark stake as inactive
     stake.is_active = false;
     
-    emit!(ForceWithdrewEvent {
+    // Copy out the stake so the event can read the account totals too
+    let stake = user_account.stakes[index];
+    
+    // The closed stake counts towards the owner's loyalty score
+    let stake_days_added = user_account.record_loyalty(index, clock.unix_timestamp)?;
+    
+    // Free the stake's place in its tier
+    ctx.accounts.tier_config
+        .tier_mut(stake.tier)?
+        .remove_stake(stake.token_type, stake.amount);
+    
+    if insurance_share > 0 {
+        let insurance_fund = ctx.accounts.insurance_fund.as_mut()
+            .ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+        insurance_fund.contribute(stake.token_type, insurance_share)?;
+        
+        emit_event!(ctx, InsuranceContributedEvent {
+            user: ctx.accounts.user.key(),
+            source: InsuranceSource::Penalty,
+            token_type: stake.token_type,
+            amount: insurance_share,
+            balance: insurance_fund.balance(stake.token_type),
+            coverage_bps: insurance_fund.coverage_bps(
+                stake.token_type,
+                platform_state.total_staked(stake.token_type),
+            ),
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(platform_state),
+        });
+    }
+    
+    emit_event!(ctx, ForceWithdrewEvent {
         user: ctx.accounts.user.key(),
         stake_index,
         principal: stake.amount,
         rewards_after_penalty,
         penalty,
+        penalty_rate_bps: quote.breakdown.rate_bps,
+        principal_penalty: quote.breakdown.on_principal,
+        clawback,
         token_type: stake.token_type,
         timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    emit_event!(ctx, LoyaltyUpdatedEvent {
+        user: user_account.user,
+        stake_index,
+        stake_days_added,
+        stake_days: user_account.loyalty_stake_days,
+        level: user_account.loyalty_level(),
+        bonus_bps: user_account.loyalty_bonus_bps(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
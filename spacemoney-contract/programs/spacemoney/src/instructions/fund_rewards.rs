@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, RewardsFundedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    // USDT only
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = funder_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub funder_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = platform_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub platform_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Anyone can top up the vault
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+}
+
+// Adds reward liquidity to the vault. It isn't treasury income, so the
+// treasury is unchanged; queued withdrawals are filled by process_queue.
+pub fn handler(ctx: Context<FundRewards>, amount: u64, token_type: TokenType) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(amount > 0, SpaceMoneyError::InvalidAmount);
+    
+    match token_type {
+        TokenType::SOL => {
+            let ix = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.platform_state.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                ix,
+            );
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        }
+        TokenType::USDT => {
+            let funder_token_account = ctx.accounts.funder_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let platform_token_account = ctx.accounts.platform_token_account.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            let token_program = ctx.accounts.token_program.as_ref()
+                .ok_or(SpaceMoneyError::InvalidTokenType)?;
+            
+            let cpi_accounts = Transfer {
+                from: funder_token_account.to_account_info(),
+                to: platform_token_account.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, amount)?;
+        }
+    }
+    
+    let platform_state = &ctx.accounts.platform_state;
+    emit_event!(ctx, RewardsFundedEvent {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        token_type,
+        queued_withdrawals: platform_state.queued_withdrawals(token_type),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct GetClaimable<'info> {
+    #[account(
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+}
+
+// Returned through return data; read it by simulating the instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ClaimableView {
+    pub stake_index: u64,
+    pub claimable: u64,
+    pub earned: u64,
+    pub claimed_rewards: u64,
+    pub max_rewards: u64,
+    // What withdrawing now would pay: force_withdraw while locked, withdraw after
+    pub withdrawable: u64,
+    pub is_locked: bool,
+    // End of the current lock, which moves on at each expiry for auto-rolled stakes
+    pub lock_until: i64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<GetClaimable>, stake_index: u64) -> Result<ClaimableView> {
+    let user_account = &ctx.accounts.user_account;
+    let tier_config = &ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    let index = stake_index as usize;
+    require!(
+        index < user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    let stake = &user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    
+    let terms = stake.terms(tier_config.tier(stake.tier)?);
+    let is_locked = terms.is_locked_at(clock.unix_timestamp);
+    let withdrawable = if is_locked {
+        terms
+            .force_withdraw_at(clock.unix_timestamp)
+            .map_err(SpaceMoneyError::from)?
+            .total_withdrawal
+    } else {
+        stake.amount
+            .checked_add(
+                terms
+                    .unclaimed_at_withdrawal(clock.unix_timestamp)
+                    .map_err(SpaceMoneyError::from)?,
+            )
+            .ok_or(SpaceMoneyError::NumericalOverflow)?
+    };
+    
+    Ok(ClaimableView {
+        stake_index,
+        claimable: terms.claimable_at(clock.unix_timestamp).map_err(SpaceMoneyError::from)?,
+        earned: terms.earned_at(clock.unix_timestamp).map_err(SpaceMoneyError::from)?,
+        claimed_rewards: stake.claimed_rewards,
+        max_rewards: terms.max_rewards().map_err(SpaceMoneyError::from)?,
+        withdrawable,
+        is_locked,
+        lock_until: terms.lock_until_at(clock.unix_timestamp),
+        timestamp: clock.unix_timestamp,
+    })
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct GetLoyalty<'info> {
+    #[account(
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+}
+
+// Returned through return data; read it by simulating the instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct LoyaltyView {
+    pub user: Pubkey,
+    pub stake_days: u64,
+    pub level: u8,
+    // Bonus the user's next stake would get
+    pub bonus_bps: u16,
+    // Stake-days still needed for the next level, 0 at the top
+    pub next_level_stake_days: u64,
+    // Stake-days built up so far by active stakes, counted once they close
+    pub pending_stake_days: u64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<GetLoyalty>) -> Result<LoyaltyView> {
+    let user_account = &ctx.accounts.user_account;
+    let clock = Clock::get()?;
+    
+    let pending_stake_days = user_account.stakes.iter()
+        .filter(|stake| stake.is_active)
+        .map(|stake| stake.stake_days_at(clock.unix_timestamp))
+        .try_fold(0u64, |total, stake_days| total.checked_add(stake_days))
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    Ok(LoyaltyView {
+        user: user_account.user,
+        stake_days: user_account.loyalty_stake_days,
+        level: user_account.loyalty_level(),
+        bonus_bps: user_account.loyalty_bonus_bps(),
+        next_level_stake_days: user_account.stake_days_to_next_level(),
+        pending_stake_days,
+        timestamp: clock.unix_timestamp,
+    })
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::PlatformTotals;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct GetPlatformStats<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    // Only needed for the spSOL supply once liquid staking is initialized
+    #[account(
+        constraint = spsol_mint.key() == platform_state.spsol_mint @ SpaceMoneyError::InvalidSpsolMint,
+    )]
+    pub spsol_mint: Option<Account<'info, Mint>>,
+}
+
+// Returned through return data; read it by simulating the instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PlatformStats {
+    pub totals: PlatformTotals,
+    pub sol_balance: u64,
+    pub spsol_supply: u64,
+    pub is_paused: bool,
+    pub stake_transfers_enabled: bool,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<GetPlatformStats>) -> Result<PlatformStats> {
+    let platform_state = &ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    Ok(PlatformStats {
+        totals: PlatformTotals::of(platform_state),
+        sol_balance: platform_state.to_account_info().lamports(),
+        spsol_supply: ctx.accounts.spsol_mint.as_ref().map_or(0, |mint| mint.supply),
+        is_paused: platform_state.is_paused,
+        stake_transfers_enabled: platform_state.stake_transfers_enabled,
+        timestamp: clock.unix_timestamp,
+    })
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::*;
+
+#[derive(Accounts)]
+pub struct GetUserSummary<'info> {
+    #[account(
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+}
+
+// Returned through return data; read it by simulating the instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct UserSummary {
+    pub user: Pubkey,
+    pub active_stakes: u64,
+    pub staked_sol: u64,
+    pub staked_usdt: u64,
+    pub claimable_sol: u64,
+    pub claimable_usdt: u64,
+    pub total_claimed_sol: u64,
+    pub total_claimed_usdt: u64,
+    // Earliest lock_until still in the future, or 0 when nothing is locked
+    pub next_unlock: i64,
+    pub timestamp: i64,
+}
+
+pub fn handler(ctx: Context<GetUserSummary>) -> Result<UserSummary> {
+    let user_account = &ctx.accounts.user_account;
+    let tier_config = &ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    let mut summary = UserSummary {
+        user: user_account.user,
+        active_stakes: user_account.active_stakes(),
+        staked_sol: 0,
+        staked_usdt: 0,
+        claimable_sol: 0,
+        claimable_usdt: 0,
+        total_claimed_sol: user_account.total_claimed_sol,
+        total_claimed_usdt: user_account.total_claimed_usdt,
+        next_unlock: 0,
+        timestamp: clock.unix_timestamp,
+    };
+    
+    for stake in user_account.stakes.iter().filter(|stake| stake.is_active) {
+        let terms = stake.terms(tier_config.tier(stake.tier)?);
+        let claimable = terms
+            .claimable_at(clock.unix_timestamp)
+            .map_err(SpaceMoneyError::from)?;
+        
+        let (staked, claimable_total) = match stake.token_type {
+            TokenType::SOL => (&mut summary.staked_sol, &mut summary.claimable_sol),
+            TokenType::USDT => (&mut summary.staked_usdt, &mut summary.claimable_usdt),
+        };
+        *staked = staked
+            .checked_add(stake.amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        *claimable_total = claimable_total
+            .checked_add(claimable)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        
+        let lock_until = terms.lock_until_at(clock.unix_timestamp);
+        if lock_until > clock.unix_timestamp
+            && (summary.next_unlock == 0 || lock_until < summary.next_unlock)
+        {
+            summary.next_unlock = lock_until;
+        }
+    }
+    
+    Ok(summary)
+}
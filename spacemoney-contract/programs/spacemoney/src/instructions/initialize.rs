@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
-use crate::events::InitializedEvent;
+use crate::events::{InitializedEvent, PlatformTotals};
 use crate::states::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(
@@ -44,25 +45,18 @@ pub fn handler(ctx: Context<Initialize>, usdt_mint: Pubkey) -> Result<()> {
     platform_state.is_paused = false;
     platform_state.created_at = clock.unix_timestamp;
     platform_state.bump = ctx.bumps.platform_state;
+    platform_state.stake_transfers_enabled = true;
+    platform_state.version = PlatformState::VERSION;
     
-    // Initialize tier config with default values
-    tier_config.boot_min_stake = BOOT_MIN_STAKE;
-    tier_config.boot_multiplier = BOOT_MULTIPLIER;
-    tier_config.boot_lock_days = BOOT_LOCK_DAYS;
-    
-    tier_config.symbiotic_min_stake = SYMBIOTIC_MIN_STAKE;
-    tier_config.symbiotic_multiplier = SYMBIOTIC_MULTIPLIER;
-    tier_config.symbiotic_lock_days = SYMBIOTIC_LOCK_DAYS;
-    
-    tier_config.space_min_stake = SPACE_MIN_STAKE;
-    tier_config.space_multiplier = SPACE_MULTIPLIER;
-    tier_config.space_lock_days = SPACE_LOCK_DAYS;
-    
+    // Initialize tier config with the default tiers
+    tier_config.tiers = TierConfig::default_tiers();
     tier_config.bump = ctx.bumps.tier_config;
+    tier_config.version = TierConfig::VERSION;
     
-    emit!(InitializedEvent {
+    emit_event!(ctx, InitializedEvent {
         admin: ctx.accounts.admin.key(),
         timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
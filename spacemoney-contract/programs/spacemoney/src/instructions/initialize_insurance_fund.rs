@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{InsuranceFundInitializedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct InitializeInsuranceFund<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = InsuranceFund::LEN,
+        seeds = [INSURANCE_FUND_SEED],
+        bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    // Holds the fund's USDT; only the fund can move it
+    #[account(
+        init,
+        payer = admin,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump,
+        token::mint = usdt_mint,
+        token::authority = insurance_fund,
+    )]
+    pub insurance_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        constraint = usdt_mint.key() == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint
+    )]
+    pub usdt_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Creates the empty fund; set_insurance_share starts paying into it
+pub fn handler(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    ctx.accounts.insurance_fund.set_inner(InsuranceFund {
+        balance_sol: 0,
+        balance_usdt: 0,
+        contributed_sol: 0,
+        contributed_usdt: 0,
+        backstopped_sol: 0,
+        backstopped_usdt: 0,
+        backstop_count: 0,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.insurance_fund,
+        version: InsuranceFund::VERSION,
+        reserved: [0; InsuranceFund::RESERVED],
+    });
+    
+    emit_event!(ctx, InsuranceFundInitializedEvent {
+        admin: ctx.accounts.admin.key(),
+        insurance_fund: ctx.accounts.insurance_fund.key(),
+        insurance_vault: ctx.accounts.insurance_vault.key(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{LiquidStakingInitializedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct InitializeLiquidStaking<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        init,
+        payer = admin,
+        seeds = [SPSOL_MINT_SEED],
+        bump,
+        mint::decimals = SPSOL_DECIMALS,
+        mint::authority = vault_authority,
+    )]
+    pub spsol_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA authority for spSOL minting
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeLiquidStaking>) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    platform_state.spsol_mint = ctx.accounts.spsol_mint.key();
+    platform_state.liquid_staked_sol = 0;
+    platform_state.liquid_last_accrual = clock.unix_timestamp;
+    
+    emit_event!(ctx, LiquidStakingInitializedEvent {
+        admin: ctx.accounts.admin.key(),
+        spsol_mint: ctx.accounts.spsol_mint.key(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{MergedStakesEvent, PlatformTotals};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct MergeStakes<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<MergeStakes>,
+    target_index: u64,
+    source_indices: Vec<u64>,
+) -> Result<()> {
+    let platform_state = &ctx.accounts.platform_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Get target stake
+    let target = target_index as usize;
+    require!(
+        target < user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    let target_stake = user_account.stakes[target];
+    require!(target_stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(!target_stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
+    require!(!target_stake.has_receipt(), SpaceMoneyError::StakeHasReceipt);
+    require!(!source_indices.is_empty(), SpaceMoneyError::InvalidStakeIndex);
+    
+    let target_matured = clock.unix_timestamp >= target_stake.lock_until;
+    let tier_info = ctx.accounts.tier_config.tier(target_stake.tier)?;
+    let target_terms = target_stake.terms(tier_info);
+    let multiplier = target_terms.multiplier;
+    
+    let mut merged_amount = target_stake.amount;
+    let mut merged_claimed = target_stake.claimed_rewards;
+    let mut merged_daily = calculate_daily_reward(target_stake.amount, multiplier)?;
+    let mut merged_lock_until = target_stake.lock_until;
+    let mut weighted_deposited_at = target_stake.deposited_at as i128 * target_stake.amount as i128;
+    
+    for (position, &source_index) in source_indices.iter().enumerate() {
+        let source = source_index as usize;
+        require!(
+            source < user_account.stakes.len()
+                && source != target
+                && !source_indices[..position].contains(&source_index),
+            SpaceMoneyError::InvalidStakeIndex
+        );
+        
+        let source_stake = user_account.stakes[source];
+        require!(source_stake.is_active, SpaceMoneyError::StakeNotFound);
+        require!(!source_stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
+        require!(!source_stake.has_receipt(), SpaceMoneyError::StakeHasReceipt);
+        
+        // Same asset, tier, multiplier, lock length and accrual, made under
+        // the same post-lock policy, loyalty bonus and campaign boost
+        let source_terms = source_stake.terms(tier_info);
+        require!(
+            source_stake.token_type == target_stake.token_type
+                && source_stake.tier == target_stake.tier
+                && source_terms.multiplier == target_terms.multiplier
+                && source_terms.lock_days == target_terms.lock_days
+                && source_terms.accrual == target_terms.accrual
+                && source_terms.rounding == target_terms.rounding
+                && source_stake.post_lock == target_stake.post_lock
+                && source_stake.post_lock_rate_bps == target_stake.post_lock_rate_bps
+                && source_stake.loyalty_bonus_bps == target_stake.loyalty_bonus_bps
+                && source_stake.campaign_boost_bps == target_stake.campaign_boost_bps,
+            SpaceMoneyError::IncompatibleStakes
+        );
+        
+        // Locked stakes must share the exact schedule; matured stakes have
+        // fully vested rewards, so only the lock length matters for them.
+        // Stakes that keep earning after their lock always need the same schedule.
+        let same_schedule = source_stake.deposited_at == target_stake.deposited_at
+            && source_stake.lock_until == target_stake.lock_until;
+        let both_matured = target_matured
+            && clock.unix_timestamp >= source_stake.lock_until
+            && target_stake.post_lock == PostLock::None;
+        require!(
+            same_schedule || both_matured,
+            SpaceMoneyError::IncompatibleStakes
+        );
+        
+        merged_amount = merged_amount
+            .checked_add(source_stake.amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        merged_claimed = merged_claimed
+            .checked_add(source_stake.claimed_rewards)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        merged_daily = merged_daily
+            .checked_add(calculate_daily_reward(source_stake.amount, multiplier)?)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        merged_lock_until = merged_lock_until.max(source_stake.lock_until);
+        weighted_deposited_at = weighted_deposited_at
+            .checked_add(source_stake.deposited_at as i128 * source_stake.amount as i128)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    }
+    
+    // The merged position must earn exactly what the parts did
+    require!(
+        calculate_daily_reward(merged_amount, multiplier)? == merged_daily,
+        SpaceMoneyError::RewardPrecisionLoss
+    );
+    
+    for &source_index in source_indices.iter() {
+        let source_stake = &mut user_account.stakes[source_index as usize];
+        source_stake.amount = 0;
+        source_stake.claimed_rewards = 0;
+        source_stake.is_active = false;
+    }
+    
+    let target_stake = &mut user_account.stakes[target];
+    target_stake.amount = merged_amount;
+    target_stake.claimed_rewards = merged_claimed;
+    target_stake.lock_until = merged_lock_until;
+    
+    // Each part keeps the stake-days it has held so far: the merged stake
+    // dates from the amount-weighted deposit time, rounded later so merging
+    // never adds credit. Matured parts have vested all their rewards, so the
+    // later date doesn't cut them.
+    if merged_amount > 0 {
+        let amount = merged_amount as i128;
+        target_stake.deposited_at = ((weighted_deposited_at + amount - 1) / amount) as i64;
+    }
+    
+    // The sources no longer count as active stakes in the tier
+    let tier_info = ctx.accounts.tier_config.tier_mut(target_stake.tier)?;
+    tier_info.active_stakes = tier_info.active_stakes.saturating_sub(source_indices.len() as u64);
+    
+    emit_event!(ctx, MergedStakesEvent {
+        user: ctx.accounts.user.key(),
+        target_index,
+        source_indices,
+        amount: merged_amount,
+        claimed_rewards: merged_claimed,
+        timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountSerialize;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{AccountMigratedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: Platform state, user account or tier config; the type comes from its discriminator
+    #[account(
+        mut,
+        owner = crate::ID @ SpaceMoneyError::UnknownAccountType
+    )]
+    pub account: UncheckedAccount<'info>,
+    
+    /// CHECK: Only read for the event totals, and may itself be on an old layout
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump
+    )]
+    pub platform_state: UncheckedAccount<'info>,
+    
+    /// CHECK: Only read when migrating a user account, and must be current by then
+    #[account(
+        seeds = [TIERS_SEED],
+        bump
+    )]
+    pub tier_config: UncheckedAccount<'info>,
+    
+    // Pays for the larger account; migrating changes no balances, so anyone can
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    let clock = Clock::get()?;
+    
+    // Re-encode the account at its current version. The version byte follows
+    // the version 0 fields, so a missing or zero byte means a legacy layout.
+    let (kind, from_version, migrated) = {
+        let data = account.try_borrow_data()?;
+        let kind = AccountKind::from_discriminator(&data)
+            .ok_or(SpaceMoneyError::UnknownAccountType)?;
+        
+        // Tier lists start with the list instead, and are always allocated
+        // at full size, so their length tells the layouts apart
+        require!(
+            kind != AccountKind::TierConfig || data.len() < TierConfig::LEN,
+            SpaceMoneyError::AccountAlreadyMigrated
+        );
+        
+        // User accounts have to be tried against each stake layout, newest first
+        let current_user = match kind {
+            AccountKind::UserAccount => UserAccount::try_deserialize(&mut &data[..]).ok(),
+            _ => None,
+        };
+        let is_current_user = current_user.as_ref()
+            .is_some_and(|user_account| user_account.version == UserAccount::VERSION);
+        require!(!is_current_user, SpaceMoneyError::AccountAlreadyMigrated);
+        
+        let mut body = &data[8..];
+        let mut migrated = Vec::new();
+        if kind == AccountKind::UserAccount {
+            // Version 3 stakes have the current layout, only without their terms
+            let version_3 = current_user.filter(|user_account| user_account.version == 3);
+            let (mut user_account, from_version) = if let Some(user_account) = version_3 {
+                (user_account, 3)
+            } else if let Some(legacy) = UserAccountV2::decode(&data) {
+                (legacy.upgrade(), 2)
+            } else if let Some(legacy) = UserAccountBaseline::decode(&data) {
+                (legacy.upgrade().upgrade(), 0)
+            } else {
+                let legacy = decode_legacy::<UserAccountV0>(&mut body)?;
+                (legacy.upgrade(), body.first().copied().unwrap_or(0))
+            };
+            user_account.version = UserAccount::VERSION;
+            
+            // Existing stakes keep the terms their tier has now, so the tier
+            // config has to be migrated first
+            let tier_data = ctx.accounts.tier_config.try_borrow_data()?;
+            let tier_config = TierConfig::try_deserialize(&mut &tier_data[..])?;
+            for stake in user_account.stakes.iter_mut().filter(|stake| !stake.terms_fixed) {
+                stake.fix_terms(tier_config.tier(stake.tier)?)?;
+            }
+            
+            user_account.try_serialize(&mut migrated)?;
+            (kind, from_version, migrated)
+        } else if kind == AccountKind::TierConfig && data.len() == TierConfigV2::LEN {
+            let legacy = decode_legacy::<TierConfigV2>(&mut body)?;
+            let from_version = legacy.version;
+            legacy.upgrade().try_serialize(&mut migrated)?;
+            (kind, from_version, migrated)
+        } else {
+            match kind {
+                AccountKind::PlatformState => PlatformStateV0::decode(&mut body)?
+                    .upgrade()
+                    .try_serialize(&mut migrated)?,
+                // Version 1 only added the version byte and padding, so both
+                // fixed-field layouts upgrade from their version 0 fields
+                _ => decode_legacy::<TierConfigV0>(&mut body)?
+                    .upgrade()
+                    .upgrade()
+                    .try_serialize(&mut migrated)?,
+            }
+            (kind, body.first().copied().unwrap_or(0), migrated)
+        }
+    };
+    require!(from_version < kind.version(), SpaceMoneyError::AccountAlreadyMigrated);
+    
+    // Keep any spare room (user accounts are allocated for several stakes up front)
+    let new_len = kind
+        .migrated_len(account.data_len(), from_version)
+        .max(migrated.len());
+    
+    // Top up rent before resizing
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        let ix = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            ix,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, rent_due)?;
+    }
+    
+    account.realloc(new_len, true)?;
+    account.try_borrow_mut_data()?[..migrated.len()].copy_from_slice(&migrated);
+    
+    // Leading platform fields decode the same on every layout
+    let platform_data = ctx.accounts.platform_state.try_borrow_data()?;
+    let platform_state = PlatformStateV0::decode(&mut &platform_data[8..])?.upgrade();
+    drop(platform_data);
+    
+    emit_event!(ctx, AccountMigratedEvent {
+        account: account.key(),
+        kind,
+        from_version,
+        to_version: kind.version(),
+        payer: ctx.accounts.payer.key(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&platform_state),
+    });
+    
+    Ok(())
+}
+
+// Reads the version 0 fields, leaving `data` at whatever follows them
+fn decode_legacy<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T> {
+    T::deserialize(data).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
@@ -11,6 +11,44 @@ pub mod update_tier_config;
 pub mod set_usdt_mint;
 pub mod pause_program;
 pub mod resume_program;
+pub mod split_stake;
+pub mod merge_stakes;
+pub mod transfer_stake;
+pub mod set_stake_transfers;
+pub mod initialize_liquid_staking;
+pub mod redeem_spsol;
+pub mod add_validator;
+pub mod remove_validator;
+pub mod set_delegation_target;
+pub mod rebalance_validator;
+pub mod get_claimable;
+pub mod get_user_summary;
+pub mod get_platform_stats;
+pub mod get_loyalty;
+pub mod migrate_account;
+pub mod add_tier;
+pub mod disable_tier;
+pub mod set_tier_limits;
+pub mod set_tier_accrual;
+pub mod set_tier_post_lock;
+pub mod set_tier_unbonding;
+pub mod set_tier_penalty;
+pub mod request_unstake;
+pub mod complete_unstake;
+pub mod queue_withdrawal;
+pub mod process_queue;
+pub mod fund_rewards;
+pub mod register_referrer;
+pub mod claim_referral_rewards;
+pub mod set_referral_share;
+pub mod create_campaign;
+pub mod end_campaign;
+pub mod create_distributor;
+pub mod claim_bonus;
+pub mod close_distributor;
+pub mod initialize_insurance_fund;
+pub mod set_insurance_share;
+pub mod redirect_ticket;
 
 pub use initialize::*;
 pub use deposit_sol::*;
@@ -25,3 +63,41 @@ pub use update_tier_config::*;
 pub use set_usdt_mint::*;
 pub use pause_program::*;
 pub use resume_program::*;
+pub use split_stake::*;
+pub use merge_stakes::*;
+pub use transfer_stake::*;
+pub use set_stake_transfers::*;
+pub use initialize_liquid_staking::*;
+pub use redeem_spsol::*;
+pub use add_validator::*;
+pub use remove_validator::*;
+pub use set_delegation_target::*;
+pub use rebalance_validator::*;
+pub use get_claimable::*;
+pub use get_user_summary::*;
+pub use get_platform_stats::*;
+pub use get_loyalty::*;
+pub use migrate_account::*;
+pub use add_tier::*;
+pub use disable_tier::*;
+pub use set_tier_limits::*;
+pub use set_tier_accrual::*;
+pub use set_tier_post_lock::*;
+pub use set_tier_unbonding::*;
+pub use set_tier_penalty::*;
+pub use request_unstake::*;
+pub use complete_unstake::*;
+pub use queue_withdrawal::*;
+pub use process_queue::*;
+pub use fund_rewards::*;
+pub use register_referrer::*;
+pub use claim_referral_rewards::*;
+pub use set_referral_share::*;
+pub use create_campaign::*;
+pub use end_campaign::*;
+pub use create_distributor::*;
+pub use claim_bonus::*;
+pub use close_distributor::*;
+pub use initialize_insurance_fund::*;
+pub use set_insurance_share::*;
+pub use redirect_ticket::*;
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::ProgramPausedEvent;
+use crate::events::{PlatformTotals, ProgramPausedEvent};
 use crate::states::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct PauseProgram<'info> {
     #[account(
@@ -24,9 +25,10 @@ pub fn handler(ctx: Context<PauseProgram>) -> Result<()> {
     
     platform_state.is_paused = true;
     
-    emit!(ProgramPausedEvent {
+    emit_event!(ctx, ProgramPausedEvent {
         admin: ctx.accounts.admin.key(),
         timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
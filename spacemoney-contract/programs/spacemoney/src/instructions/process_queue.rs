@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, WithdrawalFilledEvent, WithdrawalSkippedEvent};
+use crate::states::*;
+use crate::utils::vault_sol_balance;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct ProcessQueue<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_QUEUE_SEED],
+        bump = withdrawal_queue.bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    
+    // USDT queue only
+    #[account(
+        mut,
+        constraint = platform_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub platform_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// CHECK: PDA authority for token transfers
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+// Fills the oldest tickets in one token while the vault can cover them. The
+// remaining accounts are the destinations of those tickets, in queue order;
+// anyone can crank it, and it stops at the first ticket it can't fill. A USDT
+// ticket whose token account can no longer receive is skipped and flagged,
+// so it waits for redirect_ticket without holding up the tickets behind it.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ProcessQueue<'info>>,
+    token_type: TokenType,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!ctx.accounts.platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Copy the tickets out so events can be emitted while paying them
+    let tickets: Vec<WithdrawalTicket> = ctx.accounts.withdrawal_queue.tickets.iter()
+        .filter(|ticket| ticket.token_type == token_type)
+        .copied()
+        .collect();
+    
+    // What the vault can pay out: SOL keeps the account rent-exempt, and
+    // neither token touches the treasury or the liquid pool
+    let balance = match token_type {
+        TokenType::SOL => vault_sol_balance(&ctx.accounts.platform_state.to_account_info())?,
+        TokenType::USDT => ctx.accounts.platform_token_account.as_ref()
+            .ok_or(SpaceMoneyError::InvalidTokenType)?
+            .amount,
+    };
+    let mut balance = balance
+        .saturating_sub(ctx.accounts.platform_state.held_outside_stakes(token_type));
+    
+    let mut filled = Vec::new();
+    for (ticket, destination) in tickets.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(
+            destination.key(),
+            ticket.destination,
+            SpaceMoneyError::InvalidQueueDestination
+        );
+        
+        if token_type == TokenType::USDT
+            && !accepts_usdt(destination, ctx.accounts.platform_state.usdt_mint)
+        {
+            emit_event!(ctx, WithdrawalSkippedEvent {
+                user: ticket.user,
+                ticket_id: ticket.id,
+                destination: ticket.destination,
+                token_type,
+                timestamp: clock.unix_timestamp,
+                totals: PlatformTotals::of(&ctx.accounts.platform_state),
+            });
+            continue;
+        }
+        
+        let amount = ticket.amount()?;
+        if balance < amount {
+            break;
+        }
+        
+        match token_type {
+            TokenType::SOL => {
+                **ctx.accounts.platform_state.to_account_info().try_borrow_mut_lamports()? -= amount;
+                **destination.try_borrow_mut_lamports()? += amount;
+            }
+            TokenType::USDT => {
+                let platform_token_account = ctx.accounts.platform_token_account.as_ref()
+                    .ok_or(SpaceMoneyError::InvalidTokenType)?;
+                let token_program = ctx.accounts.token_program.as_ref()
+                    .ok_or(SpaceMoneyError::InvalidTokenType)?;
+                
+                let vault_bump = ctx.bumps.vault_authority;
+                let seeds = &[VAULT_SEED, &[vault_bump]];
+                let signer_seeds = &[&seeds[..]];
+                
+                let cpi_accounts = Transfer {
+                    from: platform_token_account.to_account_info(),
+                    to: destination.clone(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token::transfer(cpi_ctx, amount)?;
+            }
+        }
+        balance -= amount;
+        
+        let queued = ctx.accounts.platform_state.queued_withdrawals_mut(token_type);
+        *queued = queued.saturating_sub(amount);
+        filled.push(ticket.id);
+        
+        emit_event!(ctx, WithdrawalFilledEvent {
+            user: ticket.user,
+            ticket_id: ticket.id,
+            stake_index: ticket.stake_index,
+            destination: ticket.destination,
+            principal: ticket.principal,
+            rewards: ticket.rewards,
+            token_type,
+            queued_at: ticket.queued_at,
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(&ctx.accounts.platform_state),
+        });
+    }
+    
+    ctx.accounts.withdrawal_queue.tickets.retain(|ticket| !filled.contains(&ticket.id));
+    
+    Ok(())
+}
+
+// A live, unfrozen token account of the USDT mint; closed accounts belong to
+// the system program and fail the owner check
+fn accepts_usdt(destination: &AccountInfo, usdt_mint: Pubkey) -> bool {
+    if destination.owner != &token::ID {
+        return false;
+    }
+    let data = match destination.try_borrow_data() {
+        Ok(data) => data,
+        Err(_) => return false,
+    };
+    match TokenAccount::try_deserialize(&mut &data[..]) {
+        Ok(account) => account.mint == usdt_mint && !account.is_frozen(),
+        Err(_) => false,
+    }
+}
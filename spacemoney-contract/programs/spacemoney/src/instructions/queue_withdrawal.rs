@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{LoyaltyUpdatedEvent, PlatformTotals, WithdrawalQueuedEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct QueueWithdrawal<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = WithdrawalQueue::LEN,
+        seeds = [WITHDRAWAL_QUEUE_SEED],
+        bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    
+    // USDT stakes only; the ticket pays out here
+    #[account(
+        constraint = user_token_account.owner == user.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = user_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+    )]
+    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueWithdrawal>, stake_index: u64) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let withdrawal_queue = &mut ctx.accounts.withdrawal_queue;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Get stake
+    let index = stake_index as usize;
+    require!(
+        index < user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &user_account.stakes[index],
+        user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
+    )?;
+    
+    let stake = user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    
+    // Anything withdraw_* or complete_unstake would pay out can be queued
+    let tier_info = ctx.accounts.tier_config.tier(stake.tier)?;
+    let terms = stake.terms(tier_info);
+    if stake.is_unbonding {
+        require!(
+            clock.unix_timestamp >= stake.unbonding_until,
+            SpaceMoneyError::UnbondingNotComplete
+        );
+    } else {
+        require!(
+            !terms.is_locked_at(clock.unix_timestamp),
+            SpaceMoneyError::StakeLocked
+        );
+        require!(
+            tier_info.unbonding_seconds == 0,
+            SpaceMoneyError::UnbondingRequired
+        );
+    }
+    
+    // The payout is fixed now; rewards stop when the stake closes
+    let rewards = terms
+        .unclaimed_at_withdrawal(clock.unix_timestamp)
+        .map_err(SpaceMoneyError::from)?;
+    let destination = match stake.token_type {
+        TokenType::SOL => ctx.accounts.user.key(),
+        TokenType::USDT => ctx.accounts.user_token_account.as_ref()
+            .ok_or(SpaceMoneyError::InvalidTokenType)?
+            .key(),
+    };
+    
+    require!(
+        withdrawal_queue.tickets.len() < MAX_QUEUED_WITHDRAWALS,
+        SpaceMoneyError::WithdrawalQueueFull
+    );
+    let tickets_ahead = withdrawal_queue.tickets.iter()
+        .filter(|ticket| ticket.token_type == stake.token_type)
+        .count() as u64;
+    let ticket = WithdrawalTicket {
+        id: withdrawal_queue.next_ticket_id,
+        user: ctx.accounts.user.key(),
+        stake_index,
+        token_type: stake.token_type,
+        destination,
+        principal: stake.amount,
+        rewards,
+        queued_at: clock.unix_timestamp,
+    };
+    withdrawal_queue.tickets.push(ticket);
+    withdrawal_queue.next_ticket_id = withdrawal_queue.next_ticket_id
+        .checked_add(1)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    withdrawal_queue.bump = ctx.bumps.withdrawal_queue;
+    
+    // The stake closes now and its payout is owed by the queue instead
+    let queued = platform_state.queued_withdrawals_mut(stake.token_type);
+    *queued = queued
+        .checked_add(ticket.amount()?)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    match stake.token_type {
+        TokenType::SOL => {
+            platform_state.total_staked_sol = platform_state.total_staked_sol
+                .checked_sub(stake.amount)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            user_account.total_claimed_sol = user_account.total_claimed_sol
+                .checked_add(rewards)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        }
+        TokenType::USDT => {
+            platform_state.total_staked_usdt = platform_state.total_staked_usdt
+                .checked_sub(stake.amount)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            user_account.total_claimed_usdt = user_account.total_claimed_usdt
+                .checked_add(rewards)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        }
+    }
+    
+    // The closed stake counts towards the owner's loyalty score
+    let stake_days_added = user_account.record_loyalty(index, clock.unix_timestamp)?;
+    
+    let closed = &mut user_account.stakes[index];
+    closed.is_active = false;
+    closed.is_unbonding = false;
+    
+    // Free the stake's place in its tier
+    ctx.accounts.tier_config
+        .tier_mut(stake.tier)?
+        .remove_stake(stake.token_type, stake.amount);
+    
+    emit_event!(ctx, WithdrawalQueuedEvent {
+        user: ctx.accounts.user.key(),
+        ticket_id: ticket.id,
+        stake_index,
+        principal: stake.amount,
+        rewards,
+        token_type: stake.token_type,
+        tickets_ahead,
+        timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    emit_event!(ctx, LoyaltyUpdatedEvent {
+        user: user_account.user,
+        stake_index,
+        stake_days_added,
+        stake_days: user_account.loyalty_stake_days,
+        level: user_account.loyalty_level(),
+        bonus_bps: user_account.loyalty_bonus_bps(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
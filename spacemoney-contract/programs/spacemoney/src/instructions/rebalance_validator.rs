@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{stake, sysvar, vote};
+use anchor_lang::solana_program::stake::state::StakeStateV2;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{DeactivatedStakeEvent, DelegatedStakeEvent, PlatformTotals, ValidatorRemovedEvent, WithdrewStakeEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct RebalanceValidator<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED],
+        bump = validator_list.bump
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    
+    /// CHECK: Validated as a vote program account
+    #[account(
+        constraint = *vote_account.owner == vote::program::ID @ SpaceMoneyError::InvalidVoteAccount
+    )]
+    pub vote_account: AccountInfo<'info>,
+    
+    /// CHECK: Stake account PDA for this validator, created on first delegation
+    #[account(
+        mut,
+        seeds = [VALIDATOR_STAKE_SEED, vote_account.key().as_ref()],
+        bump
+    )]
+    pub validator_stake: AccountInfo<'info>,
+    
+    /// CHECK: PDA authority acting as staker and withdrawer
+    #[account(
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    
+    /// CHECK: Stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: AccountInfo<'info>,
+    
+    /// CHECK: Stake config account, validated by the stake program
+    pub stake_config: AccountInfo<'info>,
+    
+    /// CHECK: Native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub cranker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RebalanceValidator>) -> Result<()> {
+    let clock = &ctx.accounts.clock;
+    let vote_account = ctx.accounts.vote_account.key();
+    let stake_key = ctx.accounts.validator_stake.key();
+    
+    let index = ctx.accounts.validator_list
+        .find(&vote_account)
+        .ok_or(SpaceMoneyError::ValidatorNotFound)?;
+    let entry = ctx.accounts.validator_list.validators[index];
+    
+    require!(
+        clock.epoch >= entry.next_update_epoch,
+        SpaceMoneyError::RebalanceTooSoon
+    );
+    
+    // Target per validator, based on all SOL the platform is holding for stakers
+    let total_staked = ctx.accounts.platform_state.total_staked_sol
+        .checked_add(ctx.accounts.platform_state.liquid_staked_sol)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    let target = calculate_validator_target(
+        total_staked,
+        ctx.accounts.validator_list.target_delegation_bps,
+        ctx.accounts.validator_list.active_count(),
+    )?;
+    
+    let vault_bump = ctx.bumps.vault_authority;
+    let vault_seeds = &[VAULT_SEED, &[vault_bump]];
+    let vault_signer = &[&vault_seeds[..]];
+    
+    match entry.status {
+        ValidatorStatus::Inactive => {
+            require!(!entry.pending_removal, SpaceMoneyError::ValidatorNotFound);
+            
+            // Never delegate rent, treasury funds or queued withdrawals
+            let platform_info = ctx.accounts.platform_state.to_account_info();
+            let reserved = ctx.accounts.rent
+                .minimum_balance(platform_info.data_len())
+                .checked_add(ctx.accounts.platform_state.treasury_sol)
+                .and_then(|v| v.checked_add(ctx.accounts.platform_state.queued_withdrawals_sol))
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            let available = platform_info.lamports().saturating_sub(reserved);
+            let amount = target.min(available);
+            
+            if amount >= MIN_VALIDATOR_DELEGATION {
+                let stake_rent = ctx.accounts.rent.minimum_balance(StakeStateV2::size_of());
+                let funding = amount
+                    .checked_add(stake_rent)
+                    .ok_or(SpaceMoneyError::NumericalOverflow)?;
+                require!(
+                    platform_info.lamports() >= funding,
+                    SpaceMoneyError::InsufficientTreasuryBalance
+                );
+                
+                // Fund the stake PDA from the vault
+                **platform_info.try_borrow_mut_lamports()? -= funding;
+                **ctx.accounts.validator_stake.try_borrow_mut_lamports()? += funding;
+                
+                let stake_bump = ctx.bumps.validator_stake;
+                let stake_seeds = &[VALIDATOR_STAKE_SEED, vote_account.as_ref(), &[stake_bump]];
+                
+                create_stake_account(
+                    &ctx.accounts.validator_stake,
+                    &platform_info,
+                    &ctx.accounts.vault_authority,
+                    &ctx.accounts.rent.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                    &ctx.accounts.stake_program,
+                    &[&stake_seeds[..]],
+                )?;
+                
+                delegate_stake(
+                    &ctx.accounts.validator_stake,
+                    &ctx.accounts.vote_account,
+                    &ctx.accounts.vault_authority,
+                    &ctx.accounts.clock.to_account_info(),
+                    &ctx.accounts.stake_history,
+                    &ctx.accounts.stake_config,
+                    &ctx.accounts.stake_program,
+                    vault_signer,
+                )?;
+                
+                let validator_list = &mut ctx.accounts.validator_list;
+                validator_list.validators[index].status = ValidatorStatus::Active;
+                validator_list.validators[index].active_stake = amount;
+                validator_list.total_delegated = validator_list.total_delegated
+                    .checked_add(amount)
+                    .ok_or(SpaceMoneyError::NumericalOverflow)?;
+                
+                emit_event!(ctx, DelegatedStakeEvent {
+                    vote_account,
+                    stake_account: stake_key,
+                    amount,
+                    epoch: clock.epoch,
+                    timestamp: clock.unix_timestamp,
+                    total_delegated: ctx.accounts.validator_list.total_delegated,
+                    totals: PlatformTotals::of(&ctx.accounts.platform_state),
+                });
+            }
+        }
+        ValidatorStatus::Active => {
+            // Unstake when the validator is being removed or is well over target
+            let threshold = target
+                .checked_mul(BPS_DENOMINATOR + REBALANCE_THRESHOLD_BPS)
+                .and_then(|v| v.checked_div(BPS_DENOMINATOR))
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            
+            if entry.pending_removal || entry.active_stake > threshold {
+                deactivate_stake(
+                    &ctx.accounts.validator_stake,
+                    &ctx.accounts.vault_authority,
+                    &ctx.accounts.clock.to_account_info(),
+                    &ctx.accounts.stake_program,
+                    vault_signer,
+                )?;
+                
+                ctx.accounts.validator_list.validators[index].status = ValidatorStatus::Deactivating;
+                
+                emit_event!(ctx, DeactivatedStakeEvent {
+                    vote_account,
+                    stake_account: stake_key,
+                    amount: entry.active_stake,
+                    epoch: clock.epoch,
+                    timestamp: clock.unix_timestamp,
+                    total_delegated: ctx.accounts.validator_list.total_delegated,
+                    totals: PlatformTotals::of(&ctx.accounts.platform_state),
+                });
+            }
+        }
+        ValidatorStatus::Deactivating => {
+            // Pull everything back into the vault once cooldown is over
+            let withdrawn = ctx.accounts.validator_stake.lamports();
+            
+            withdraw_stake(
+                &ctx.accounts.validator_stake,
+                &ctx.accounts.platform_state.to_account_info(),
+                &ctx.accounts.vault_authority,
+                &ctx.accounts.clock.to_account_info(),
+                &ctx.accounts.stake_history,
+                &ctx.accounts.stake_program,
+                withdrawn,
+                vault_signer,
+            )?;
+            
+            let stake_rent = ctx.accounts.rent.minimum_balance(StakeStateV2::size_of());
+            let staking_yield = withdrawn
+                .saturating_sub(entry.active_stake)
+                .saturating_sub(stake_rent);
+            
+            let validator_list = &mut ctx.accounts.validator_list;
+            validator_list.total_delegated = validator_list.total_delegated
+                .checked_sub(entry.active_stake)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            validator_list.total_yield = validator_list.total_yield
+                .checked_add(staking_yield)
+                .ok_or(SpaceMoneyError::NumericalOverflow)?;
+            
+            // The liquid pool earns its share of the yield, which raises the spSOL rate
+            let platform_state = &mut ctx.accounts.platform_state;
+            let liquid_yield = calculate_liquid_yield(
+                staking_yield,
+                platform_state.liquid_staked_sol,
+                platform_state.total_staked_sol,
+            )?;
+            if liquid_yield > 0 {
+                platform_state.liquid_staked_sol = platform_state.liquid_staked_sol
+                    .checked_add(liquid_yield)
+                    .ok_or(SpaceMoneyError::NumericalOverflow)?;
+                platform_state.liquid_last_accrual = clock.unix_timestamp;
+            }
+            
+            emit_event!(ctx, WithdrewStakeEvent {
+                vote_account,
+                stake_account: stake_key,
+                principal: entry.active_stake,
+                staking_yield,
+                epoch: clock.epoch,
+                timestamp: clock.unix_timestamp,
+                total_delegated: validator_list.total_delegated,
+                total_yield: validator_list.total_yield,
+                totals: PlatformTotals::of(&ctx.accounts.platform_state),
+            });
+            
+            if entry.pending_removal {
+                validator_list.validators.remove(index);
+                
+                emit_event!(ctx, ValidatorRemovedEvent {
+                    vote_account,
+                    timestamp: clock.unix_timestamp,
+                    validator_count: validator_list.validators.len() as u64,
+                    totals: PlatformTotals::of(&ctx.accounts.platform_state),
+                });
+                
+                return Ok(());
+            }
+            
+            validator_list.validators[index].status = ValidatorStatus::Inactive;
+            validator_list.validators[index].active_stake = 0;
+            
+            // Funds are back in the vault, so it can be delegated again right away
+            return Ok(());
+        }
+    }
+    
+    ctx.accounts.validator_list.validators[index].next_update_epoch = clock
+        .epoch
+        .checked_add(1)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, RedeemedSpsolEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct RedeemSpsol<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [SPSOL_MINT_SEED],
+        bump,
+        constraint = spsol_mint.key() == platform_state.spsol_mint @ SpaceMoneyError::InvalidSpsolMint,
+    )]
+    pub spsol_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = user_spsol_account.owner == user.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = user_spsol_account.mint == spsol_mint.key() @ SpaceMoneyError::InvalidSpsolMint,
+    )]
+    pub user_spsol_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RedeemSpsol>, spsol_amount: u64) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    require!(
+        spsol_amount > 0 && ctx.accounts.user_spsol_account.amount >= spsol_amount,
+        SpaceMoneyError::InsufficientBalance
+    );
+    
+    // The pool only holds deposits and collected staking yield, so the rate is backed
+    let sol_amount = calculate_sol_for_spsol(
+        spsol_amount,
+        platform_state.liquid_staked_sol,
+        ctx.accounts.spsol_mint.supply,
+    )?;
+    
+    // Only SOL that no locked stake, the treasury or the queue is owed can be redeemed
+    let platform_balance = vault_sol_balance(&platform_state.to_account_info())?;
+    require!(
+        platform_state.available_for_liquid(platform_balance) >= sol_amount,
+        SpaceMoneyError::InsufficientTreasuryBalance
+    );
+    
+    // Burn spSOL from the user
+    let cpi_accounts = Burn {
+        mint: ctx.accounts.spsol_mint.to_account_info(),
+        from: ctx.accounts.user_spsol_account.to_account_info(),
+        authority: ctx.accounts.user.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    );
+    token::burn(cpi_ctx, spsol_amount)?;
+    
+    // Transfer SOL from platform to user
+    **platform_state.to_account_info().try_borrow_mut_lamports()? -= sol_amount;
+    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? += sol_amount;
+    
+    // Update liquid pool
+    platform_state.liquid_staked_sol = platform_state.liquid_staked_sol
+        .checked_sub(sol_amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    // The mint account isn't reloaded after the CPI
+    let spsol_supply = ctx.accounts.spsol_mint.supply
+        .checked_sub(spsol_amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    emit_event!(ctx, RedeemedSpsolEvent {
+        user: ctx.accounts.user.key(),
+        spsol_amount,
+        sol_amount,
+        liquid_staked_sol: platform_state.liquid_staked_sol,
+        timestamp: clock.unix_timestamp,
+        spsol_supply,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
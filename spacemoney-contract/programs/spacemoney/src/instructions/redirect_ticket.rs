@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, WithdrawalRedirectedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct RedirectTicket<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [WITHDRAWAL_QUEUE_SEED],
+        bump = withdrawal_queue.bump
+    )]
+    pub withdrawal_queue: Account<'info, WithdrawalQueue>,
+    
+    // The ticket pays out here from now on
+    #[account(
+        constraint = user_token_account.owner == user.key() @ SpaceMoneyError::TokenAccountOwnerMismatch,
+        constraint = user_token_account.mint == platform_state.usdt_mint @ SpaceMoneyError::InvalidUsdtMint,
+        constraint = !user_token_account.is_frozen() @ SpaceMoneyError::InvalidQueueDestination,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+    
+    pub user: Signer<'info>,
+}
+
+// Points one of the signer's USDT tickets at a new token account, e.g. after
+// process_queue skipped it for a closed or frozen one. The ticket keeps its
+// place in the queue.
+pub fn handler(ctx: Context<RedirectTicket>, ticket_id: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let user = ctx.accounts.user.key();
+    let destination = ctx.accounts.user_token_account.key();
+    
+    let ticket = ctx.accounts.withdrawal_queue.tickets.iter_mut()
+        .find(|ticket| ticket.id == ticket_id && ticket.user == user)
+        .ok_or(SpaceMoneyError::TicketNotFound)?;
+    require!(
+        ticket.token_type == TokenType::USDT,
+        SpaceMoneyError::InvalidTokenType
+    );
+    
+    let previous_destination = ticket.destination;
+    ticket.destination = destination;
+    
+    emit_event!(ctx, WithdrawalRedirectedEvent {
+        user,
+        ticket_id,
+        previous_destination,
+        destination,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::events::{PlatformTotals, ReferrerRegisteredEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        init,
+        payer = referrer,
+        space = ReferralAccount::LEN,
+        seeds = [REFERRAL_SEED, referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+    
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    ctx.accounts.referral_account.set_inner(ReferralAccount {
+        referrer: ctx.accounts.referrer.key(),
+        referred_deposits: 0,
+        referred_volume_sol: 0,
+        referred_volume_usdt: 0,
+        pending_sol: 0,
+        pending_usdt: 0,
+        total_claimed_sol: 0,
+        total_claimed_usdt: 0,
+        created_at: clock.unix_timestamp,
+        bump: ctx.bumps.referral_account,
+        version: ReferralAccount::VERSION,
+        reserved: [0; ReferralAccount::RESERVED],
+    });
+    
+    emit_event!(ctx, ReferrerRegisteredEvent {
+        referrer: ctx.accounts.referrer.key(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
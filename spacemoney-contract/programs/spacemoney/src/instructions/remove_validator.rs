@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, ValidatorRemovedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct RemoveValidator<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED],
+        bump = validator_list.bump
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveValidator>, vote_account: Pubkey) -> Result<()> {
+    let validator_list = &mut ctx.accounts.validator_list;
+    let clock = Clock::get()?;
+    
+    let index = validator_list
+        .find(&vote_account)
+        .ok_or(SpaceMoneyError::ValidatorNotFound)?;
+    
+    // Undelegated validators can go right away, delegated ones are
+    // unwound by the rebalance crank first
+    if validator_list.validators[index].status == ValidatorStatus::Inactive {
+        validator_list.validators.remove(index);
+        
+        emit_event!(ctx, ValidatorRemovedEvent {
+            vote_account,
+            timestamp: clock.unix_timestamp,
+            validator_count: validator_list.validators.len() as u64,
+            totals: PlatformTotals::of(&ctx.accounts.platform_state),
+        });
+    } else {
+        validator_list.validators[index].pending_removal = true;
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, UnstakeRequestedEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RequestUnstake>, stake_index: u64) -> Result<()> {
+    let platform_state = &ctx.accounts.platform_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Get stake
+    let index = stake_index as usize;
+    require!(
+        index < user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &user_account.stakes[index],
+        user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
+    )?;
+    
+    let stake = &mut user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(!stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
+    
+    // The cooldown starts once the lock is over, so zero-lock tiers can
+    // start it at any time
+    let tier_info = ctx.accounts.tier_config.tier(stake.tier)?;
+    require!(
+        !stake.terms(tier_info).is_locked_at(clock.unix_timestamp),
+        SpaceMoneyError::StakeLocked
+    );
+    
+    let unbonding_until = clock.unix_timestamp
+        .checked_add(tier_info.unbonding_seconds)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    // Rewards stop here; the stake stays active until it's paid out
+    stake.is_unbonding = true;
+    stake.unstake_requested_at = clock.unix_timestamp;
+    stake.unbonding_until = unbonding_until;
+    
+    emit_event!(ctx, UnstakeRequestedEvent {
+        user: ctx.accounts.user.key(),
+        stake_index,
+        amount: stake.amount,
+        token_type: stake.token_type,
+        unbonding_until,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::ProgramResumedEvent;
+use crate::events::{PlatformTotals, ProgramResumedEvent};
 use crate::states::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct ResumeProgram<'info> {
     #[account(
@@ -24,9 +25,10 @@ pub fn handler(ctx: Context<ResumeProgram>) -> Result<()> {
     
     platform_state.is_paused = false;
     
-    emit!(ProgramResumedEvent {
+    emit_event!(ctx, ProgramResumedEvent {
         admin: ctx.accounts.admin.key(),
         timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
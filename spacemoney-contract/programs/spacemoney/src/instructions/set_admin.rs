@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::AdminChangedEvent;
+use crate::events::{AdminChangedEvent, PlatformTotals};
 use crate::states::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct SetAdmin<'info> {
     #[account(
@@ -28,10 +29,11 @@ pub fn handler(ctx: Context<SetAdmin>) -> Result<()> {
     let old_admin = platform_state.admin;
     platform_state.admin = ctx.accounts.new_admin.key();
     
-    emit!(AdminChangedEvent {
+    emit_event!(ctx, AdminChangedEvent {
         old_admin,
         new_admin: ctx.accounts.new_admin.key(),
         timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
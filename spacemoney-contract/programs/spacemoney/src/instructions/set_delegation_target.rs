@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{DelegationTargetUpdatedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetDelegationTarget<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [VALIDATOR_LIST_SEED],
+        bump = validator_list.bump
+    )]
+    pub validator_list: Account<'info, ValidatorList>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetDelegationTarget>, target_delegation_bps: u64) -> Result<()> {
+    let validator_list = &mut ctx.accounts.validator_list;
+    let clock = Clock::get()?;
+    
+    require!(
+        target_delegation_bps <= BPS_DENOMINATOR,
+        SpaceMoneyError::InvalidDelegationTarget
+    );
+    
+    validator_list.target_delegation_bps = target_delegation_bps;
+    
+    emit_event!(ctx, DelegationTargetUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        target_delegation_bps,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{InsuranceShareUpdatedEvent, PlatformTotals};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetInsuranceShare<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    // Only here so a share can't be set before the fund exists
+    #[account(
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Account<'info, InsuranceFund>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+// Applies to fees and penalties from now on
+pub fn handler(ctx: Context<SetInsuranceShare>, share_bps: u16) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    require!(
+        share_bps as u64 <= BPS_DENOMINATOR,
+        SpaceMoneyError::InvalidInsuranceShare
+    );
+    
+    platform_state.insurance_share_bps = share_bps;
+    
+    emit_event!(ctx, InsuranceShareUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        share_bps,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, ReferralShareUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+// Applies to deposits made from now on; credited rewards are unaffected
+pub fn handler(ctx: Context<SetReferralShare>, share_bps: u16) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    require!(
+        share_bps as u64 <= BPS_DENOMINATOR,
+        SpaceMoneyError::InvalidReferralShare
+    );
+    
+    platform_state.referral_share_bps = share_bps;
+    
+    emit_event!(ctx, ReferralShareUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        share_bps,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, StakeTransfersUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetStakeTransfers<'info> {
+    #[account(
+        mut,
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetStakeTransfers>, enabled: bool) -> Result<()> {
+    let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    platform_state.stake_transfers_enabled = enabled;
+    
+    emit_event!(ctx, StakeTransfersUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        enabled,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
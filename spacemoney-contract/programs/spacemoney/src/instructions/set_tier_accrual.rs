@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierAccrualUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetTierAccrual<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetTierAccrual>,
+    tier: u8,
+    accrual_mode: AccrualMode,
+    rounding: Rounding,
+) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    // Stakes keep the accrual they were made with, like their multiplier
+    let tier_info = tier_config.tier_mut(tier)?;
+    tier_info.accrual_mode = accrual_mode;
+    tier_info.rounding = rounding;
+    
+    emit_event!(ctx, TierAccrualUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        accrual_mode,
+        rounding,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
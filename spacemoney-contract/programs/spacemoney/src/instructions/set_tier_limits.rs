@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierLimitsUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetTierLimits<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetTierLimits>,
+    tier: u8,
+    max_total_stake: u64,
+    max_user_stake: u64,
+    open_at: i64,
+    close_at: i64,
+) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    require!(
+        close_at == 0 || close_at > open_at,
+        SpaceMoneyError::InvalidTierParams
+    );
+    
+    // Only checked on deposit, so stakes already over a lowered cap stay put
+    let tier_info = tier_config.tier_mut(tier)?;
+    tier_info.max_total_stake = max_total_stake;
+    tier_info.max_user_stake = max_user_stake;
+    tier_info.open_at = open_at;
+    tier_info.close_at = close_at;
+    
+    emit_event!(ctx, TierLimitsUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        max_total_stake,
+        max_user_stake,
+        open_at,
+        close_at,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierPenaltyUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetTierPenalty<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetTierPenalty>,
+    tier: u8,
+    curve: PenaltyCurve,
+    rate_bps: u16,
+    steps: Vec<PenaltyStep>,
+    on_principal: bool,
+    clawback: bool,
+) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    // Rates are shares of what they're charged on, and a schedule runs
+    // through the lock in order
+    let max_bps = BPS_DENOMINATOR as u16;
+    require!(rate_bps <= max_bps, SpaceMoneyError::InvalidTierParams);
+    require!(steps.len() <= MAX_PENALTY_STEPS, SpaceMoneyError::InvalidTierParams);
+    require!(
+        curve != PenaltyCurve::Step || !steps.is_empty(),
+        SpaceMoneyError::InvalidTierParams
+    );
+    require!(
+        steps.iter().all(|step| step.from_bps <= max_bps && step.rate_bps <= max_bps),
+        SpaceMoneyError::InvalidTierParams
+    );
+    require!(
+        steps.windows(2).all(|pair| pair[0].from_bps < pair[1].from_bps),
+        SpaceMoneyError::InvalidTierParams
+    );
+    
+    // Stakes have no room to record the curve, so it can only change while
+    // the tier has none that it would apply to
+    let tier_info = tier_config.tier_mut(tier)?;
+    require!(tier_info.active_stakes == 0, SpaceMoneyError::TierHasActiveStakes);
+    tier_info.penalty_curve = curve;
+    tier_info.penalty_rate_bps = rate_bps;
+    tier_info.penalty_steps = [PenaltyStep::default(); MAX_PENALTY_STEPS];
+    tier_info.penalty_steps[..steps.len()].copy_from_slice(&steps);
+    tier_info.penalty_step_count = steps.len() as u8;
+    tier_info.penalty_on_principal = on_principal;
+    tier_info.penalty_clawback = clawback;
+    
+    emit_event!(ctx, TierPenaltyUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        curve,
+        rate_bps,
+        steps,
+        on_principal,
+        clawback,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
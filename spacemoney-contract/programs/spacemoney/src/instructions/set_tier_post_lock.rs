@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierPostLockUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetTierPostLock<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetTierPostLock>,
+    tier: u8,
+    post_lock: PostLock,
+    post_lock_rate_bps: u16,
+) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    // The flexible rate is a share of the locked rate, so at most all of it
+    require!(
+        post_lock_rate_bps as u64 <= BPS_DENOMINATOR,
+        SpaceMoneyError::InvalidTierParams
+    );
+    
+    // Stakes record the policy when they're made, so this only affects new deposits
+    let tier_info = tier_config.tier_mut(tier)?;
+    tier_info.post_lock = post_lock;
+    tier_info.post_lock_rate_bps = post_lock_rate_bps;
+    
+    emit_event!(ctx, TierPostLockUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        post_lock,
+        post_lock_rate_bps,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TierUnbondingUpdatedEvent};
+use crate::states::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SetTierUnbonding<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump,
+        constraint = platform_state.admin == admin.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetTierUnbonding>, tier: u8, unbonding_seconds: i64) -> Result<()> {
+    let tier_config = &mut ctx.accounts.tier_config;
+    let clock = Clock::get()?;
+    
+    require!(unbonding_seconds >= 0, SpaceMoneyError::InvalidTierParams);
+    
+    // Unstakes already requested keep the cooldown they were given
+    tier_config.tier_mut(tier)?.unbonding_seconds = unbonding_seconds;
+    
+    emit_event!(ctx, TierUnbondingUpdatedEvent {
+        admin: ctx.accounts.admin.key(),
+        tier,
+        unbonding_seconds,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
+    });
+    
+    Ok(())
+}
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, UsdtMintChangedEvent};
 use crate::states::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct SetUsdtMint<'info> {
     #[account(
@@ -19,6 +21,18 @@ pub struct SetUsdtMint<'info> {
 
 pub fn handler(ctx: Context<SetUsdtMint>, usdt_mint: Pubkey) -> Result<()> {
     let platform_state = &mut ctx.accounts.platform_state;
+    let clock = Clock::get()?;
+    
+    let old_mint = platform_state.usdt_mint;
     platform_state.usdt_mint = usdt_mint;
+    
+    emit_event!(ctx, UsdtMintChangedEvent {
+        admin: ctx.accounts.admin.key(),
+        old_mint,
+        new_mint: usdt_mint,
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
+    });
+    
     Ok(())
 }
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, SplitStakeEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct SplitStake<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        mut,
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SplitStake>, stake_index: u64, amount: u64) -> Result<()> {
+    let platform_state = &ctx.accounts.platform_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Get stake
+    let index = stake_index as usize;
+    require!(
+        index < user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    let stake = user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(!stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
+    require!(!stake.has_receipt(), SpaceMoneyError::StakeHasReceipt);
+    
+    // Both parts must keep a non-zero principal
+    require!(
+        amount > 0 && amount < stake.amount,
+        SpaceMoneyError::InvalidSplitAmount
+    );
+    
+    let remaining_amount = stake.amount
+        .checked_sub(amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    // Rewards accrue as daily reward × days, so the split is exact only if
+    // the daily rewards of both parts add up to the original daily reward
+    let multiplier = stake.terms(ctx.accounts.tier_config.tier(stake.tier)?).multiplier;
+    let total_daily = calculate_daily_reward(stake.amount, multiplier)?;
+    let split_daily = calculate_daily_reward(amount, multiplier)?;
+    let remaining_daily = calculate_daily_reward(remaining_amount, multiplier)?;
+    
+    require!(
+        split_daily.checked_add(remaining_daily) == Some(total_daily),
+        SpaceMoneyError::RewardPrecisionLoss
+    );
+    
+    // Carry claimed rewards over in proportion to each part's daily reward
+    let split_claimed = calculate_claimed_share(stake.claimed_rewards, split_daily, total_daily)?;
+    let remaining_claimed = stake.claimed_rewards
+        .checked_sub(split_claimed)
+        .ok_or(SpaceMoneyError::CalculationError)?;
+    
+    let new_stake = UserStake {
+        amount,
+        claimed_rewards: split_claimed,
+        ..stake
+    };
+    
+    let original = &mut user_account.stakes[index];
+    original.amount = remaining_amount;
+    original.claimed_rewards = remaining_claimed;
+    
+    reserve_stake_space(
+        user_account,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    user_account.stakes.push(new_stake);
+    let new_stake_index = (user_account.stakes.len() - 1) as u64;
+    
+    // The new part is another active stake in the tier. Splitting only moves
+    // existing principal, so it isn't held to the tier's capacity.
+    let tier_info = ctx.accounts.tier_config.tier_mut(stake.tier)?;
+    tier_info.active_stakes = tier_info.active_stakes
+        .checked_add(1)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
+    emit_event!(ctx, SplitStakeEvent {
+        user: ctx.accounts.user.key(),
+        stake_index,
+        new_stake_index,
+        amount,
+        claimed_rewards: split_claimed,
+        timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::events::{PlatformTotals, TransferredStakeEvent};
+use crate::states::*;
+use crate::utils::*;
+
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
+#[derive(Accounts)]
+pub struct TransferStake<'info> {
+    #[account(
+        seeds = [PLATFORM_SEED],
+        bump = platform_state.bump
+    )]
+    pub platform_state: Account<'info, PlatformState>,
+    
+    #[account(
+        mut,
+        seeds = [USER_SEED, user.key().as_ref()],
+        bump = user_account.bump,
+        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserAccount::space_for_stakes(10),
+        seeds = [USER_SEED, recipient.key().as_ref()],
+        bump
+    )]
+    pub recipient_account: Account<'info, UserAccount>,
+    
+    #[account(
+        seeds = [TIERS_SEED],
+        bump = tier_config.bump
+    )]
+    pub tier_config: Account<'info, TierConfig>,
+    
+    /// CHECK: Recipient can be any address
+    #[account(
+        constraint = recipient.key() != user.key() @ SpaceMoneyError::InvalidRecipient
+    )]
+    pub recipient: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<TransferStake>, stake_index: u64) -> Result<()> {
+    let platform_state = &ctx.accounts.platform_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let recipient_account = &mut ctx.accounts.recipient_account;
+    let clock = Clock::get()?;
+    
+    // Check if program is paused
+    require!(!platform_state.is_paused, SpaceMoneyError::ProgramPaused);
+    
+    // Check if transfers are allowed
+    require!(
+        platform_state.stake_transfers_enabled,
+        SpaceMoneyError::StakeTransfersDisabled
+    );
+    
+    // Get stake
+    let index = stake_index as usize;
+    require!(
+        index < user_account.stakes.len(),
+        SpaceMoneyError::InvalidStakeIndex
+    );
+    
+    let stake = user_account.stakes[index];
+    require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(!stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
+    
+    // Receipt-backed stakes change hands by transferring the NFT
+    require!(!stake.has_receipt(), SpaceMoneyError::StakeHasReceipt);
+    
+    // Initialize recipient account if needed
+    if recipient_account.user == Pubkey::default() {
+        recipient_account.user = ctx.accounts.recipient.key();
+        recipient_account.stakes = Vec::new();
+        recipient_account.total_claimed_sol = 0;
+        recipient_account.total_claimed_usdt = 0;
+        recipient_account.last_claim_time = clock.unix_timestamp;
+        recipient_account.bump = ctx.bumps.recipient_account;
+        recipient_account.version = UserAccount::VERSION;
+    }
+    
+    // The stake stays in its tier, but the recipient is held to the tier's
+    // per-user cap as if they had deposited it
+    let recipient_staked = recipient_account.staked_in_tier(stake.tier, stake.token_type)?;
+    ctx.accounts.tier_config
+        .tier(stake.tier)?
+        .check_user_stake(stake.amount, recipient_staked)?;
+    
+    // Move the stake as-is, including rewards already claimed against it
+    reserve_stake_space(
+        recipient_account,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    recipient_account.stakes.push(stake);
+    let new_stake_index = (recipient_account.stakes.len() - 1) as u64;
+    
+    let source_stake = &mut user_account.stakes[index];
+    source_stake.amount = 0;
+    source_stake.claimed_rewards = 0;
+    source_stake.is_active = false;
+    
+    emit_event!(ctx, TransferredStakeEvent {
+        from: ctx.accounts.user.key(),
+        to: ctx.accounts.recipient.key(),
+        stake_index,
+        new_stake_index,
+        amount: stake.amount,
+        claimed_rewards: stake.claimed_rewards,
+        token_type: stake.token_type,
+        timestamp: clock.unix_timestamp,
+        from_active_stakes: user_account.active_stakes(),
+        to_active_stakes: recipient_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::TierConfigUpdatedEvent;
+use crate::events::{PlatformTotals, TierConfigUpdatedEvent};
 use crate::states::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct UpdateTierConfig<'info> {
     #[account(
@@ -29,36 +30,33 @@ pub fn handler(
     tier: u8,
     min_stake: u64,
     multiplier: u64,
-    lock_days: i64,
+    lock_seconds: i64,
+    max_stakes: u64,
 ) -> Result<()> {
     let tier_config = &mut ctx.accounts.tier_config;
     let clock = Clock::get()?;
     
-    match tier {
-        0 => {
-            tier_config.boot_min_stake = min_stake;
-            tier_config.boot_multiplier = multiplier;
-            tier_config.boot_lock_days = lock_days;
-        },
-        1 => {
-            tier_config.symbiotic_min_stake = min_stake;
-            tier_config.symbiotic_multiplier = multiplier;
-            tier_config.symbiotic_lock_days = lock_days;
-        },
-        2 => {
-            tier_config.space_min_stake = min_stake;
-            tier_config.space_multiplier = multiplier;
-            tier_config.space_lock_days = lock_days;
-        },
-        _ => return Err(SpaceMoneyError::InvalidTier.into()),
-    }
+    require!(
+        TierInfo::valid_terms(multiplier, lock_seconds),
+        SpaceMoneyError::InvalidTierParams
+    );
     
-    emit!(TierConfigUpdatedEvent {
+    // Stakes keep the multiplier and lock length they were made with, so
+    // only later deposits get the new terms
+    let tier_info = tier_config.tier_mut(tier)?;
+    tier_info.min_stake = min_stake;
+    tier_info.multiplier = multiplier;
+    tier_info.lock_seconds = lock_seconds;
+    tier_info.max_stakes = max_stakes;
+    
+    emit_event!(ctx, TierConfigUpdatedEvent {
         tier,
         min_stake,
         multiplier,
-        lock_days,
+        lock_seconds,
+        max_stakes,
         timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(&ctx.accounts.platform_state),
     });
     
     Ok(())
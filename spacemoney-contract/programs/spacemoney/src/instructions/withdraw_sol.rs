@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::{WithdrewEvent, TokenType};
+use crate::events::{InsuranceBackstopEvent, LoyaltyUpdatedEvent, PlatformTotals, WithdrewEvent};
 use crate::states::*;
 use crate::utils::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct WithdrawSol<'info> {
     #[account(
@@ -16,18 +18,28 @@ pub struct WithdrawSol<'info> {
     
     #[account(
         mut,
-        seeds = [USER_SEED, user.key().as_ref()],
-        bump = user_account.bump,
-        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
     )]
     pub user_account: Account<'info, UserAccount>,
     
     #[account(
+        mut,
         seeds = [TIERS_SEED],
         bump = tier_config.bump
     )]
     pub tier_config: Account<'info, TierConfig>,
     
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Covers what the vault is short of the withdrawal, if passed
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -49,39 +61,66 @@ pub fn handler(ctx: Context<WithdrawSol>, stake_index: u64) -> Result<()> {
         SpaceMoneyError::InvalidStakeIndex
     );
     
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &user_account.stakes[index],
+        user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
+    )?;
+    
     let stake = &mut user_account.stakes[index];
     require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(!stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
     require!(
         stake.token_type == TokenType::SOL,
         SpaceMoneyError::InvalidTokenType
     );
     
     // Check if lock period has passed
+    let tier_info = ctx.accounts.tier_config.tier(stake.tier)?;
+    let terms = stake.terms(tier_info);
     require!(
-        clock.unix_timestamp >= stake.lock_until,
+        !terms.is_locked_at(clock.unix_timestamp),
         SpaceMoneyError::StakeLocked
     );
     
-    // Calculate rewards
-    let (_, multiplier, lock_days) = match stake.tier {
-        Tier::Boot => (BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS),
-        Tier::Symbiotic => (SYMBIOTIC_MIN_STAKE, SYMBIOTIC_MULTIPLIER, SYMBIOTIC_LOCK_DAYS),
-        Tier::Space => (SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS),
-    };
+    // Tiers with a cooldown pay out through request_unstake / complete_unstake
+    require!(
+        tier_info.unbonding_seconds == 0,
+        SpaceMoneyError::UnbondingRequired
+    );
     
-    let total_rewards = calculate_rewards(stake.amount, multiplier, lock_days)?;
-    let unclaimed_rewards = total_rewards
-        .checked_sub(stake.claimed_rewards)
-        .ok_or(SpaceMoneyError::CalculationError)?;
+    // Calculate rewards
+    let unclaimed_rewards = terms
+        .unclaimed_at_withdrawal(clock.unix_timestamp)
+        .map_err(SpaceMoneyError::from)?;
     
     let total_withdrawal = stake.amount
         .checked_add(unclaimed_rewards)
         .ok_or(SpaceMoneyError::NumericalOverflow)?;
     
-    // Check platform has enough balance
-    let platform_balance = platform_state.to_account_info().lamports();
+    // Draw any shortfall from the insurance fund, which must then be passed
+    let platform_info = platform_state.to_account_info();
+    let available = platform_state.available_for_stakes(TokenType::SOL, vault_sol_balance(&platform_info)?);
+    let shortfall = backstop_sol(&platform_info, ctx.accounts.insurance_fund.as_mut(), available, total_withdrawal)?;
+    if let Some(insurance_fund) = ctx.accounts.insurance_fund.as_ref().filter(|_| shortfall > 0) {
+        emit_event!(ctx, InsuranceBackstopEvent {
+            user: ctx.accounts.user.key(),
+            stake_index,
+            token_type: TokenType::SOL,
+            shortfall,
+            balance: insurance_fund.balance_sol,
+            coverage_bps: insurance_fund.coverage_bps(TokenType::SOL, platform_state.total_staked_sol),
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(platform_state),
+        });
+    }
+    
+    // Check platform has enough balance above its rent
+    let platform_balance = vault_sol_balance(&platform_info)?;
     require!(
-        platform_balance >= total_withdrawal,
+        platform_state.available_for_stakes(TokenType::SOL, platform_balance) >= total_withdrawal,
         SpaceMoneyError::InsufficientTreasuryBalance
     );
     
@@ -102,13 +141,37 @@ pub fn handler(ctx: Context<WithdrawSol>, stake_index: u64) -> Result<()> {
     // Mark stake as inactive
     stake.is_active = false;
     
-    emit!(WithdrewEvent {
+    // Copy out the stake so the event can read the account totals too
+    let stake = user_account.stakes[index];
+    
+    // The closed stake counts towards the owner's loyalty score
+    let stake_days_added = user_account.record_loyalty(index, clock.unix_timestamp)?;
+    
+    // Free the stake's place in its tier
+    ctx.accounts.tier_config
+        .tier_mut(stake.tier)?
+        .remove_stake(stake.token_type, stake.amount);
+    
+    emit_event!(ctx, WithdrewEvent {
         user: ctx.accounts.user.key(),
         stake_index,
         principal: stake.amount,
         rewards: unclaimed_rewards,
         token_type: TokenType::SOL,
         timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    emit_event!(ctx, LoyaltyUpdatedEvent {
+        user: user_account.user,
+        stake_index,
+        stake_days_added,
+        stake_days: user_account.loyalty_stake_days,
+        level: user_account.loyalty_level(),
+        bonus_bps: user_account.loyalty_bonus_bps(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
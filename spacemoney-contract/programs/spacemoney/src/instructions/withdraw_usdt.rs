@@ -2,10 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::constants::*;
 use crate::errors::SpaceMoneyError;
-use crate::events::{WithdrewEvent, TokenType};
+use crate::events::{InsuranceBackstopEvent, LoyaltyUpdatedEvent, PlatformTotals, WithdrewEvent};
 use crate::states::*;
 use crate::utils::*;
 
+#[cfg_attr(not(feature = "log-events"), event_cpi)]
 #[derive(Accounts)]
 pub struct WithdrawUsdt<'info> {
     #[account(
@@ -17,13 +18,13 @@ pub struct WithdrawUsdt<'info> {
     
     #[account(
         mut,
-        seeds = [USER_SEED, user.key().as_ref()],
-        bump = user_account.bump,
-        constraint = user_account.user == user.key() @ SpaceMoneyError::Unauthorized
+        seeds = [USER_SEED, user_account.user.as_ref()],
+        bump = user_account.bump
     )]
     pub user_account: Account<'info, UserAccount>,
     
     #[account(
+        mut,
         seeds = [TIERS_SEED],
         bump = tier_config.bump
     )]
@@ -49,6 +50,23 @@ pub struct WithdrawUsdt<'info> {
     )]
     pub vault_authority: AccountInfo<'info>,
     
+    pub receipt_token_account: Option<Account<'info, TokenAccount>>,
+    
+    // Cover what the vault is short of the withdrawal, if passed
+    #[account(
+        mut,
+        seeds = [INSURANCE_FUND_SEED],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Account<'info, InsuranceFund>>,
+    
+    #[account(
+        mut,
+        seeds = [INSURANCE_VAULT_SEED],
+        bump
+    )]
+    pub insurance_vault: Option<Account<'info, TokenAccount>>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
@@ -56,7 +74,7 @@ pub struct WithdrawUsdt<'info> {
 }
 
 pub fn handler(ctx: Context<WithdrawUsdt>, stake_index: u64) -> Result<()> {
-    let platform_state = &ctx.accounts.platform_state;
+    let platform_state = &mut ctx.accounts.platform_state;
     let user_account = &mut ctx.accounts.user_account;
     let clock = Clock::get()?;
     
@@ -70,38 +88,88 @@ pub fn handler(ctx: Context<WithdrawUsdt>, stake_index: u64) -> Result<()> {
         SpaceMoneyError::InvalidStakeIndex
     );
     
+    // Check the signer controls the stake
+    verify_stake_authority(
+        &user_account.stakes[index],
+        user_account.user,
+        ctx.accounts.user.key(),
+        ctx.accounts.receipt_token_account.as_ref(),
+    )?;
+    
     let stake = &mut user_account.stakes[index];
     require!(stake.is_active, SpaceMoneyError::StakeNotFound);
+    require!(!stake.is_unbonding, SpaceMoneyError::StakeUnbonding);
     require!(
         stake.token_type == TokenType::USDT,
         SpaceMoneyError::InvalidTokenType
     );
     
     // Check if lock period has passed
+    let tier_info = ctx.accounts.tier_config.tier(stake.tier)?;
+    let terms = stake.terms(tier_info);
     require!(
-        clock.unix_timestamp >= stake.lock_until,
+        !terms.is_locked_at(clock.unix_timestamp),
         SpaceMoneyError::StakeLocked
     );
     
-    // Calculate rewards
-    let (_, multiplier, lock_days) = match stake.tier {
-        Tier::Boot => (BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS),
-        Tier::Symbiotic => (SYMBIOTIC_MIN_STAKE, SYMBIOTIC_MULTIPLIER, SYMBIOTIC_LOCK_DAYS),
-        Tier::Space => (SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS),
-    };
+    // Tiers with a cooldown pay out through request_unstake / complete_unstake
+    require!(
+        tier_info.unbonding_seconds == 0,
+        SpaceMoneyError::UnbondingRequired
+    );
     
-    let total_rewards = calculate_rewards(stake.amount, multiplier, lock_days)?;
-    let unclaimed_rewards = total_rewards
-        .checked_sub(stake.claimed_rewards)
-        .ok_or(SpaceMoneyError::CalculationError)?;
+    // Calculate rewards
+    let unclaimed_rewards = terms
+        .unclaimed_at_withdrawal(clock.unix_timestamp)
+        .map_err(SpaceMoneyError::from)?;
     
     let total_withdrawal = stake.amount
         .checked_add(unclaimed_rewards)
         .ok_or(SpaceMoneyError::NumericalOverflow)?;
     
+    // Draw any shortfall from the insurance fund's vault, which must then be passed
+    let shortfall = total_withdrawal.saturating_sub(
+        platform_state.available_for_stakes(TokenType::USDT, ctx.accounts.platform_token_account.amount),
+    );
+    if shortfall > 0 {
+        let insurance_fund = ctx.accounts.insurance_fund.as_mut()
+            .ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+        let insurance_vault = ctx.accounts.insurance_vault.as_ref()
+            .ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+        insurance_fund.backstop(TokenType::USDT, shortfall)?;
+        
+        let fund_bump = insurance_fund.bump;
+        let seeds = &[INSURANCE_FUND_SEED, &[fund_bump]];
+        let signer_seeds = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: insurance_vault.to_account_info(),
+            to: ctx.accounts.platform_token_account.to_account_info(),
+            authority: insurance_fund.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, shortfall)?;
+        ctx.accounts.platform_token_account.reload()?;
+        
+        emit_event!(ctx, InsuranceBackstopEvent {
+            user: ctx.accounts.user.key(),
+            stake_index,
+            token_type: TokenType::USDT,
+            shortfall,
+            balance: insurance_fund.balance_usdt,
+            coverage_bps: insurance_fund.coverage_bps(TokenType::USDT, platform_state.total_staked_usdt),
+            timestamp: clock.unix_timestamp,
+            totals: PlatformTotals::of(platform_state),
+        });
+    }
+    
     // Check platform has enough USDT
     require!(
-        ctx.accounts.platform_token_account.amount >= total_withdrawal,
+        platform_state.available_for_stakes(TokenType::USDT, ctx.accounts.platform_token_account.amount) >= total_withdrawal,
         SpaceMoneyError::InsufficientTreasuryBalance
     );
     
@@ -122,6 +190,11 @@ pub fn handler(ctx: Context<WithdrawUsdt>, stake_index: u64) -> Result<()> {
     );
     token::transfer(cpi_ctx, total_withdrawal)?;
     
+    // Update platform state
+    platform_state.total_staked_usdt = platform_state.total_staked_usdt
+        .checked_sub(stake.amount)
+        .ok_or(SpaceMoneyError::NumericalOverflow)?;
+    
     // Update user account
     user_account.total_claimed_usdt = user_account.total_claimed_usdt
         .checked_add(unclaimed_rewards)
@@ -130,13 +203,37 @@ pub fn handler(ctx: Context<WithdrawUsdt>, stake_index: u64) -> Result<()> {
     // Mark stake as inactive
     stake.is_active = false;
     
-    emit!(WithdrewEvent {
+    // Copy out the stake so the event can read the account totals too
+    let stake = user_account.stakes[index];
+    
+    // The closed stake counts towards the owner's loyalty score
+    let stake_days_added = user_account.record_loyalty(index, clock.unix_timestamp)?;
+    
+    // Free the stake's place in its tier
+    ctx.accounts.tier_config
+        .tier_mut(stake.tier)?
+        .remove_stake(stake.token_type, stake.amount);
+    
+    emit_event!(ctx, WithdrewEvent {
         user: ctx.accounts.user.key(),
         stake_index,
         principal: stake.amount,
         rewards: unclaimed_rewards,
         token_type: TokenType::USDT,
         timestamp: clock.unix_timestamp,
+        active_stakes: user_account.active_stakes(),
+        totals: PlatformTotals::of(platform_state),
+    });
+    
+    emit_event!(ctx, LoyaltyUpdatedEvent {
+        user: user_account.user,
+        stake_index,
+        stake_days_added,
+        stake_days: user_account.loyalty_stake_days,
+        level: user_account.loyalty_level(),
+        bonus_bps: user_account.loyalty_bonus_bps(),
+        timestamp: clock.unix_timestamp,
+        totals: PlatformTotals::of(platform_state),
     });
     
     Ok(())
@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
+#[macro_use]
 pub mod events;
 pub mod instructions;
 pub mod states;
 pub mod utils;
 
 use instructions::*;
+use states::{AccrualMode, PenaltyCurve, PenaltyStep, PostLock, Rounding, TokenType};
 
 declare_id!("Fg6Pa4H2Qv7Vu86vAisdStXVNoTMTgks9R59yQhF6P2");
 
@@ -27,6 +29,10 @@ pub mod spacemoney {
         instructions::deposit_usdt::handler(ctx, amount, tier)
     }
 
+    pub fn redeem_spsol(ctx: Context<RedeemSpsol>, spsol_amount: u64) -> Result<()> {
+        instructions::redeem_spsol::handler(ctx, spsol_amount)
+    }
+
     pub fn withdraw_sol(ctx: Context<WithdrawSol>, stake_index: u64) -> Result<()> {
         instructions::withdraw_sol::handler(ctx, stake_index)
     }
@@ -35,6 +41,48 @@ pub mod spacemoney {
         instructions::withdraw_usdt::handler(ctx, stake_index)
     }
 
+    pub fn request_unstake(ctx: Context<RequestUnstake>, stake_index: u64) -> Result<()> {
+        instructions::request_unstake::handler(ctx, stake_index)
+    }
+
+    pub fn complete_unstake(ctx: Context<CompleteUnstake>, stake_index: u64) -> Result<()> {
+        instructions::complete_unstake::handler(ctx, stake_index)
+    }
+
+    pub fn queue_withdrawal(ctx: Context<QueueWithdrawal>, stake_index: u64) -> Result<()> {
+        instructions::queue_withdrawal::handler(ctx, stake_index)
+    }
+
+    pub fn process_queue<'info>(
+        ctx: Context<'_, '_, '_, 'info, ProcessQueue<'info>>,
+        token_type: TokenType,
+    ) -> Result<()> {
+        instructions::process_queue::handler(ctx, token_type)
+    }
+
+    pub fn redirect_ticket(ctx: Context<RedirectTicket>, ticket_id: u64) -> Result<()> {
+        instructions::redirect_ticket::handler(ctx, ticket_id)
+    }
+
+    pub fn fund_rewards(
+        ctx: Context<FundRewards>,
+        amount: u64,
+        token_type: TokenType,
+    ) -> Result<()> {
+        instructions::fund_rewards::handler(ctx, amount, token_type)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer::handler(ctx)
+    }
+
+    pub fn claim_referral_rewards(
+        ctx: Context<ClaimReferralRewards>,
+        token_type: TokenType,
+    ) -> Result<()> {
+        instructions::claim_referral_rewards::handler(ctx, token_type)
+    }
+
     pub fn force_withdraw(ctx: Context<ForceWithdraw>, stake_index: u64) -> Result<()> {
         instructions::force_withdraw::handler(ctx, stake_index)
     }
@@ -43,6 +91,22 @@ pub mod spacemoney {
         instructions::claim_rewards::handler(ctx, stake_index)
     }
 
+    pub fn split_stake(ctx: Context<SplitStake>, stake_index: u64, amount: u64) -> Result<()> {
+        instructions::split_stake::handler(ctx, stake_index, amount)
+    }
+
+    pub fn merge_stakes(
+        ctx: Context<MergeStakes>,
+        target_index: u64,
+        source_indices: Vec<u64>,
+    ) -> Result<()> {
+        instructions::merge_stakes::handler(ctx, target_index, source_indices)
+    }
+
+    pub fn transfer_stake(ctx: Context<TransferStake>, stake_index: u64) -> Result<()> {
+        instructions::transfer_stake::handler(ctx, stake_index)
+    }
+
     pub fn admin_transfer(
         ctx: Context<AdminTransfer>,
         amount: u64,
@@ -60,15 +124,211 @@ pub mod spacemoney {
         tier: u8,
         min_stake: u64,
         multiplier: u64,
-        lock_days: i64,
+        lock_seconds: i64,
+        max_stakes: u64,
     ) -> Result<()> {
-        instructions::update_tier_config::handler(ctx, tier, min_stake, multiplier, lock_days)
+        instructions::update_tier_config::handler(
+            ctx,
+            tier,
+            min_stake,
+            multiplier,
+            lock_seconds,
+            max_stakes,
+        )
+    }
+
+    pub fn add_tier(
+        ctx: Context<AddTier>,
+        name: String,
+        min_stake: u64,
+        multiplier: u64,
+        lock_seconds: i64,
+        max_stakes: u64,
+    ) -> Result<()> {
+        instructions::add_tier::handler(ctx, name, min_stake, multiplier, lock_seconds, max_stakes)
+    }
+
+    pub fn disable_tier(ctx: Context<DisableTier>, tier: u8) -> Result<()> {
+        instructions::disable_tier::handler(ctx, tier)
+    }
+
+    pub fn set_tier_limits(
+        ctx: Context<SetTierLimits>,
+        tier: u8,
+        max_total_stake: u64,
+        max_user_stake: u64,
+        open_at: i64,
+        close_at: i64,
+    ) -> Result<()> {
+        instructions::set_tier_limits::handler(
+            ctx,
+            tier,
+            max_total_stake,
+            max_user_stake,
+            open_at,
+            close_at,
+        )
+    }
+
+    pub fn set_tier_accrual(
+        ctx: Context<SetTierAccrual>,
+        tier: u8,
+        accrual_mode: AccrualMode,
+        rounding: Rounding,
+    ) -> Result<()> {
+        instructions::set_tier_accrual::handler(ctx, tier, accrual_mode, rounding)
+    }
+
+    pub fn set_tier_post_lock(
+        ctx: Context<SetTierPostLock>,
+        tier: u8,
+        post_lock: PostLock,
+        post_lock_rate_bps: u16,
+    ) -> Result<()> {
+        instructions::set_tier_post_lock::handler(ctx, tier, post_lock, post_lock_rate_bps)
+    }
+
+    pub fn set_tier_unbonding(
+        ctx: Context<SetTierUnbonding>,
+        tier: u8,
+        unbonding_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_tier_unbonding::handler(ctx, tier, unbonding_seconds)
+    }
+
+    pub fn set_tier_penalty(
+        ctx: Context<SetTierPenalty>,
+        tier: u8,
+        curve: PenaltyCurve,
+        rate_bps: u16,
+        steps: Vec<PenaltyStep>,
+        on_principal: bool,
+        clawback: bool,
+    ) -> Result<()> {
+        instructions::set_tier_penalty::handler(ctx, tier, curve, rate_bps, steps, on_principal, clawback)
     }
 
     pub fn set_usdt_mint(ctx: Context<SetUsdtMint>, usdt_mint: Pubkey) -> Result<()> {
         instructions::set_usdt_mint::handler(ctx, usdt_mint)
     }
 
+    pub fn set_stake_transfers(ctx: Context<SetStakeTransfers>, enabled: bool) -> Result<()> {
+        instructions::set_stake_transfers::handler(ctx, enabled)
+    }
+
+    pub fn set_referral_share(ctx: Context<SetReferralShare>, share_bps: u16) -> Result<()> {
+        instructions::set_referral_share::handler(ctx, share_bps)
+    }
+
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        start_time: i64,
+        end_time: i64,
+        tier_mask: u16,
+        boost_bps: u16,
+        budget_sol: u64,
+        budget_usdt: u64,
+    ) -> Result<()> {
+        instructions::create_campaign::handler(
+            ctx,
+            start_time,
+            end_time,
+            tier_mask,
+            boost_bps,
+            budget_sol,
+            budget_usdt,
+        )
+    }
+
+    pub fn end_campaign(ctx: Context<EndCampaign>) -> Result<()> {
+        instructions::end_campaign::handler(ctx)
+    }
+
+    pub fn create_distributor(
+        ctx: Context<CreateDistributor>,
+        merkle_root: [u8; 32],
+        token_type: TokenType,
+        total_amount: u64,
+        max_claims: u32,
+    ) -> Result<()> {
+        instructions::create_distributor::handler(
+            ctx,
+            merkle_root,
+            token_type,
+            total_amount,
+            max_claims,
+        )
+    }
+
+    pub fn claim_bonus(
+        ctx: Context<ClaimBonus>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_bonus::handler(
+            ctx,
+            index,
+            amount,
+            proof,
+        )
+    }
+
+    pub fn close_distributor(ctx: Context<CloseDistributor>) -> Result<()> {
+        instructions::close_distributor::handler(ctx)
+    }
+
+    pub fn initialize_insurance_fund(ctx: Context<InitializeInsuranceFund>) -> Result<()> {
+        instructions::initialize_insurance_fund::handler(ctx)
+    }
+
+    pub fn set_insurance_share(ctx: Context<SetInsuranceShare>, share_bps: u16) -> Result<()> {
+        instructions::set_insurance_share::handler(ctx, share_bps)
+    }
+
+    pub fn initialize_liquid_staking(ctx: Context<InitializeLiquidStaking>) -> Result<()> {
+        instructions::initialize_liquid_staking::handler(ctx)
+    }
+
+    pub fn add_validator(ctx: Context<AddValidator>) -> Result<()> {
+        instructions::add_validator::handler(ctx)
+    }
+
+    pub fn remove_validator(ctx: Context<RemoveValidator>, vote_account: Pubkey) -> Result<()> {
+        instructions::remove_validator::handler(ctx, vote_account)
+    }
+
+    pub fn set_delegation_target(
+        ctx: Context<SetDelegationTarget>,
+        target_delegation_bps: u64,
+    ) -> Result<()> {
+        instructions::set_delegation_target::handler(ctx, target_delegation_bps)
+    }
+
+    pub fn rebalance_validator(ctx: Context<RebalanceValidator>) -> Result<()> {
+        instructions::rebalance_validator::handler(ctx)
+    }
+
+    pub fn get_claimable(ctx: Context<GetClaimable>, stake_index: u64) -> Result<ClaimableView> {
+        instructions::get_claimable::handler(ctx, stake_index)
+    }
+
+    pub fn get_user_summary(ctx: Context<GetUserSummary>) -> Result<UserSummary> {
+        instructions::get_user_summary::handler(ctx)
+    }
+
+    pub fn get_platform_stats(ctx: Context<GetPlatformStats>) -> Result<PlatformStats> {
+        instructions::get_platform_stats::handler(ctx)
+    }
+
+    pub fn get_loyalty(ctx: Context<GetLoyalty>) -> Result<LoyaltyView> {
+        instructions::get_loyalty::handler(ctx)
+    }
+
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        instructions::migrate_account::handler(ctx)
+    }
+
     pub fn pause_program(ctx: Context<PauseProgram>) -> Result<()> {
         instructions::pause_program::handler(ctx)
     }
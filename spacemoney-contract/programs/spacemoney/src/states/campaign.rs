@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::{TierInfo, TokenType, UserStake};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Campaign {
+    pub id: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    // Bit n set when deposits into tier n are boosted
+    pub tier_mask: u16,
+    pub boost_bps: u16,
+    // Most the boosts may add in each token; a token without a budget isn't boosted
+    pub budget_sol: u64,
+    pub budget_usdt: u64,
+    // Boost rewards reserved by boosted stakes
+    pub spent_sol: u64,
+    pub spent_usdt: u64,
+    pub boosted_deposits: u64,
+    // Set once a deposit finds a token's remaining budget too small
+    pub sol_exhausted: bool,
+    pub usdt_exhausted: bool,
+    // 0 until the campaign ends
+    pub ended_at: i64,
+    pub created_at: i64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; Campaign::RESERVED],
+}
+
+// Why a campaign stopped boosting deposits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CampaignEndReason {
+    Expired,
+    BudgetExhausted,
+    Cancelled,
+}
+
+impl Campaign {
+    pub const VERSION: u8 = 1;
+    pub const RESERVED: usize = 32;
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn budget(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.budget_sol,
+            TokenType::USDT => self.budget_usdt,
+        }
+    }
+    
+    pub fn spent(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.spent_sol,
+            TokenType::USDT => self.spent_usdt,
+        }
+    }
+    
+    // Every token with a budget has run out
+    pub fn is_exhausted(&self) -> bool {
+        (self.budget_sol == 0 || self.sol_exhausted)
+            && (self.budget_usdt == 0 || self.usdt_exhausted)
+    }
+    
+    // Deposits can only name a campaign that is running and covers the stake
+    pub fn check_eligible(&self, stake: &UserStake, timestamp: i64) -> Result<()> {
+        require!(
+            self.ended_at == 0 && timestamp >= self.start_time && timestamp < self.end_time,
+            SpaceMoneyError::CampaignNotActive
+        );
+        require!(
+            self.tier_mask & (1 << stake.tier) != 0 && self.budget(stake.token_type) > 0,
+            SpaceMoneyError::CampaignNotEligible
+        );
+        Ok(())
+    }
+    
+    // Reserves what the boost adds over the stake's first lock. Returns the
+    // amount reserved, or None once the token's budget can't cover it, which
+    // marks that budget exhausted.
+    pub fn reserve(&mut self, stake: &UserStake, tier: &TierInfo) -> Result<Option<u64>> {
+        let boosted = UserStake {
+            campaign_boost_bps: self.boost_bps,
+            ..*stake
+        };
+        let reserved = boosted.terms(tier).campaign_rewards()
+            .map_err(SpaceMoneyError::from)?;
+        
+        let (budget, spent, exhausted) = match stake.token_type {
+            TokenType::SOL => (self.budget_sol, &mut self.spent_sol, &mut self.sol_exhausted),
+            TokenType::USDT => (self.budget_usdt, &mut self.spent_usdt, &mut self.usdt_exhausted),
+        };
+        let total = spent
+            .checked_add(reserved)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        if *exhausted || total > budget {
+            *exhausted = true;
+            return Ok(None);
+        }
+        
+        *spent = total;
+        self.boosted_deposits = self.boosted_deposits
+            .checked_add(1)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(Some(reserved))
+    }
+}
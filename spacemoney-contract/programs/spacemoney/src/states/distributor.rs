@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::states::TokenType;
+
+// An off-chain computed bonus round: each leaf of the Merkle tree pays one
+// claimant, who proves it in claim_bonus
+#[account]
+#[derive(InitSpace)]
+pub struct Distributor {
+    pub id: u64,
+    pub merkle_root: [u8; 32],
+    pub token_type: TokenType,
+    // Moved out of the treasury when the distributor was created
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    // Leaves in the tree, and so bits in the claimed bitmap
+    pub max_claims: u32,
+    pub claimed_count: u32,
+    pub created_at: i64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; Distributor::RESERVED],
+}
+
+impl Distributor {
+    pub const VERSION: u8 = 1;
+    pub const RESERVED: usize = 32;
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn remaining(&self) -> u64 {
+        self.total_amount.saturating_sub(self.claimed_amount)
+    }
+}
+
+// One bit per leaf, set once the leaf has been claimed
+#[account]
+pub struct ClaimedBitmap {
+    pub distributor: Pubkey,
+    pub bump: u8,
+    pub bits: Vec<u8>,
+}
+
+impl ClaimedBitmap {
+    pub fn space_for(max_claims: u32) -> usize {
+        8 + 32 + 1 + 4 + Self::bytes_for(max_claims)
+    }
+    
+    pub fn bytes_for(max_claims: u32) -> usize {
+        (max_claims as usize).div_ceil(8)
+    }
+    
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.bits[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+    
+    pub fn set_claimed(&mut self, index: u32) {
+        self.bits[index as usize / 8] |= 1 << (index % 8);
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use spacemoney_math::calculate_coverage_bps;
+use crate::errors::SpaceMoneyError;
+use crate::states::TokenType;
+
+// Backs withdrawals the vault can't cover. Its SOL sits on this account above
+// rent and its USDT in the insurance vault token account, which it owns.
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceFund {
+    // What the fund can pay out
+    pub balance_sol: u64,
+    pub balance_usdt: u64,
+    // Paid in from fees and penalties
+    pub contributed_sol: u64,
+    pub contributed_usdt: u64,
+    // Paid out to cover vault shortfalls
+    pub backstopped_sol: u64,
+    pub backstopped_usdt: u64,
+    pub backstop_count: u64,
+    pub created_at: i64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; InsuranceFund::RESERVED],
+}
+
+// Where a contribution to the insurance fund came from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum InsuranceSource {
+    DepositFee,
+    Penalty,
+}
+
+impl InsuranceFund {
+    pub const VERSION: u8 = 1;
+    pub const RESERVED: usize = 32;
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    pub fn balance(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.balance_sol,
+            TokenType::USDT => self.balance_usdt,
+        }
+    }
+    
+    pub fn contribute(&mut self, token_type: TokenType, amount: u64) -> Result<()> {
+        let (balance, contributed) = match token_type {
+            TokenType::SOL => (&mut self.balance_sol, &mut self.contributed_sol),
+            TokenType::USDT => (&mut self.balance_usdt, &mut self.contributed_usdt),
+        };
+        *balance = balance
+            .checked_add(amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        *contributed = contributed
+            .checked_add(amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(())
+    }
+    
+    // Records a payout covering a vault shortfall
+    pub fn backstop(&mut self, token_type: TokenType, amount: u64) -> Result<()> {
+        let (balance, backstopped) = match token_type {
+            TokenType::SOL => (&mut self.balance_sol, &mut self.backstopped_sol),
+            TokenType::USDT => (&mut self.balance_usdt, &mut self.backstopped_usdt),
+        };
+        require!(*balance >= amount, SpaceMoneyError::InsufficientTreasuryBalance);
+        *balance -= amount;
+        *backstopped = backstopped
+            .checked_add(amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        self.backstop_count = self.backstop_count
+            .checked_add(1)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(())
+    }
+    
+    // The balance as a share of `total_staked`, in basis points
+    pub fn coverage_bps(&self, token_type: TokenType, total_staked: u64) -> u64 {
+        calculate_coverage_bps(self.balance(token_type), total_staked)
+    }
+}
@@ -0,0 +1,533 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::*;
+use crate::states::*;
+
+// Accounts that carry a version byte, told apart by their discriminator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    PlatformState,
+    UserAccount,
+    TierConfig,
+}
+
+impl AccountKind {
+    pub fn from_discriminator(data: &[u8]) -> Option<Self> {
+        let discriminator = data.get(..8)?;
+        if discriminator == PlatformState::DISCRIMINATOR {
+            Some(AccountKind::PlatformState)
+        } else if discriminator == UserAccount::DISCRIMINATOR {
+            Some(AccountKind::UserAccount)
+        } else if discriminator == TierConfig::DISCRIMINATOR {
+            Some(AccountKind::TierConfig)
+        } else {
+            None
+        }
+    }
+    
+    pub fn version(&self) -> u8 {
+        match self {
+            AccountKind::PlatformState => PlatformState::VERSION,
+            AccountKind::UserAccount => UserAccount::VERSION,
+            AccountKind::TierConfig => TierConfig::VERSION,
+        }
+    }
+    
+    // Size after migrating. Version 0 platform states grow by the version byte
+    // and the fields and padding after it, user accounts keep room for as many
+    // stakes as they had, and tier configs are allocated for the full tier list.
+    pub fn migrated_len(&self, data_len: usize, from_version: u8) -> usize {
+        match self {
+            AccountKind::PlatformState => PlatformState::LEN,
+            AccountKind::UserAccount => {
+                let (fixed_len, stake_len) = match from_version {
+                    0 if UserAccountBaseline::fits(data_len) => {
+                        (UserAccountV0::FIXED_LEN, UserStakeBaseline::LEN)
+                    }
+                    0 => (UserAccountV0::FIXED_LEN, UserStakeV0::LEN),
+                    1 => (UserAccountV0::FIXED_LEN + 1 + UserAccount::RESERVED, UserStakeV0::LEN),
+                    2 => (UserAccountV0::FIXED_LEN + 1 + UserAccount::RESERVED, UserStakeV2::LEN),
+                    _ => (UserAccount::INITIAL_LEN, UserStake::LEN),
+                };
+                let capacity = data_len.saturating_sub(fixed_len) / stake_len;
+                UserAccount::space_for_stakes(capacity)
+            }
+            AccountKind::TierConfig => TierConfig::LEN,
+        }
+    }
+}
+
+// Layouts from before accounts were versioned. New fields are only ever
+// appended, so these also decode the leading fields of later versions (up to
+// tier config version 1, after which the tier list replaced the fixed fields,
+// and user account version 1, after which stakes grew).
+// Each older tier config layout upgrades straight to the current one.
+
+// The original platform state, before stake transfers and liquid staking
+// appended their fields
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlatformStateBaseline {
+    pub admin: Pubkey,
+    pub treasury_sol: u64,
+    pub treasury_usdt: u64,
+    pub total_staked_sol: u64,
+    pub total_staked_usdt: u64,
+    pub usdt_mint: Pubkey,
+    pub is_paused: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl PlatformStateBaseline {
+    // Transfers start disabled and no spSOL mint is set, as on a fresh platform
+    pub fn upgrade(self) -> PlatformStateV0 {
+        PlatformStateV0 {
+            admin: self.admin,
+            treasury_sol: self.treasury_sol,
+            treasury_usdt: self.treasury_usdt,
+            total_staked_sol: self.total_staked_sol,
+            total_staked_usdt: self.total_staked_usdt,
+            usdt_mint: self.usdt_mint,
+            is_paused: self.is_paused,
+            created_at: self.created_at,
+            bump: self.bump,
+            stake_transfers_enabled: false,
+            spsol_mint: Pubkey::default(),
+            liquid_staked_sol: 0,
+            liquid_last_accrual: 0,
+        }
+    }
+}
+
+// Platform state with the fields appended before accounts were versioned
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlatformStateV0 {
+    pub admin: Pubkey,
+    pub treasury_sol: u64,
+    pub treasury_usdt: u64,
+    pub total_staked_sol: u64,
+    pub total_staked_usdt: u64,
+    pub usdt_mint: Pubkey,
+    pub is_paused: bool,
+    pub created_at: i64,
+    pub bump: u8,
+    pub stake_transfers_enabled: bool,
+    pub spsol_mint: Pubkey,
+    pub liquid_staked_sol: u64,
+    pub liquid_last_accrual: i64,
+}
+
+impl PlatformStateV0 {
+    // Reads these fields from the data after the discriminator, leaving `data`
+    // at whatever follows them. Accounts written before a group of fields was
+    // appended end early, and keep the defaults for it.
+    pub fn decode(data: &mut &[u8]) -> Result<Self> {
+        Self::decode_appended(data).map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+    }
+
+    fn decode_appended(data: &mut &[u8]) -> std::io::Result<Self> {
+        let mut legacy = PlatformStateBaseline::deserialize(data)?.upgrade();
+        // Stake transfers appended their toggle
+        if !data.is_empty() {
+            legacy.stake_transfers_enabled = bool::deserialize(data)?;
+        }
+        // Then liquid staking its mint and totals
+        if !data.is_empty() {
+            legacy.spsol_mint = Pubkey::deserialize(data)?;
+            legacy.liquid_staked_sol = u64::deserialize(data)?;
+            legacy.liquid_last_accrual = i64::deserialize(data)?;
+        }
+        Ok(legacy)
+    }
+
+    pub fn upgrade(self) -> PlatformState {
+        PlatformState {
+            admin: self.admin,
+            treasury_sol: self.treasury_sol,
+            treasury_usdt: self.treasury_usdt,
+            total_staked_sol: self.total_staked_sol,
+            total_staked_usdt: self.total_staked_usdt,
+            usdt_mint: self.usdt_mint,
+            is_paused: self.is_paused,
+            created_at: self.created_at,
+            bump: self.bump,
+            stake_transfers_enabled: self.stake_transfers_enabled,
+            spsol_mint: self.spsol_mint,
+            liquid_staked_sol: self.liquid_staked_sol,
+            liquid_last_accrual: self.liquid_last_accrual,
+            version: PlatformState::VERSION,
+            queued_withdrawals_sol: 0,
+            queued_withdrawals_usdt: 0,
+            referral_share_bps: 0,
+            campaign_count: 0,
+            distributor_count: 0,
+            insurance_share_bps: 0,
+            reserved: [0; PlatformState::RESERVED],
+        }
+    }
+}
+
+// The original user account, with stakes from before receipts
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserAccountBaseline {
+    pub user: Pubkey,
+    pub stakes: Vec<UserStakeBaseline>,
+    pub total_claimed_sol: u64,
+    pub total_claimed_usdt: u64,
+    pub last_claim_time: i64,
+    pub bump: u8,
+}
+
+impl UserAccountBaseline {
+    // Baseline accounts were allocated for whole baseline stakes, which no
+    // allocation of longer stakes divides into (the 10 stake default is 419
+    // bytes, against 739 once stakes recorded a receipt)
+    pub fn fits(data_len: usize) -> bool {
+        let capacity = data_len.saturating_sub(UserAccountV0::FIXED_LEN);
+        let leftover = (capacity % UserStakeBaseline::LEN, capacity % UserStakeV0::LEN);
+        matches!(leftover, (0, 1..))
+    }
+
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if !Self::fits(data.len()) {
+            return None;
+        }
+        Self::deserialize(&mut data.get(8..)?).ok()
+    }
+
+    pub fn upgrade(self) -> UserAccountV0 {
+        UserAccountV0 {
+            user: self.user,
+            stakes: self.stakes.into_iter().map(UserStakeBaseline::upgrade).collect(),
+            total_claimed_sol: self.total_claimed_sol,
+            total_claimed_usdt: self.total_claimed_usdt,
+            last_claim_time: self.last_claim_time,
+            bump: self.bump,
+        }
+    }
+}
+
+// The baseline tier enum encoded as the same byte as today's tier id
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct UserStakeBaseline {
+    pub amount: u64,
+    pub tier: u8,
+    pub token_type: TokenType,
+    pub deposited_at: i64,
+    pub lock_until: i64,
+    pub claimed_rewards: u64,
+    pub is_active: bool,
+}
+
+impl UserStakeBaseline {
+    pub const LEN: usize = Self::INIT_SPACE;
+
+    // Baseline stakes never had a receipt
+    pub fn upgrade(self) -> UserStakeV0 {
+        UserStakeV0 {
+            amount: self.amount,
+            tier: self.tier,
+            token_type: self.token_type,
+            deposited_at: self.deposited_at,
+            lock_until: self.lock_until,
+            claimed_rewards: self.claimed_rewards,
+            is_active: self.is_active,
+            receipt_mint: Pubkey::default(),
+        }
+    }
+}
+
+// User account with the stake fields added before accounts were versioned
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserAccountV0 {
+    pub user: Pubkey,
+    pub stakes: Vec<UserStakeV0>,
+    pub total_claimed_sol: u64,
+    pub total_claimed_usdt: u64,
+    pub last_claim_time: i64,
+    pub bump: u8,
+}
+
+impl UserAccountV0 {
+    // Discriminator and fields around an empty stake list
+    pub const FIXED_LEN: usize = 8 + 32 + 4 + 8 + 8 + 8 + 1;
+
+    pub fn upgrade(self) -> UserAccount {
+        UserAccount {
+            user: self.user,
+            stakes: self.stakes.into_iter().map(UserStakeV0::upgrade).collect(),
+            total_claimed_sol: self.total_claimed_sol,
+            total_claimed_usdt: self.total_claimed_usdt,
+            last_claim_time: self.last_claim_time,
+            bump: self.bump,
+            version: UserAccount::VERSION,
+            loyalty_stake_days: 0,
+            reserved: [0; UserAccount::RESERVED],
+        }
+    }
+}
+
+// Stakes before they recorded a post-lock policy (user accounts version 0 and 1)
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct UserStakeV0 {
+    pub amount: u64,
+    pub tier: u8,
+    pub token_type: TokenType,
+    pub deposited_at: i64,
+    pub lock_until: i64,
+    pub claimed_rewards: u64,
+    pub is_active: bool,
+    pub receipt_mint: Pubkey,
+}
+
+impl UserStakeV0 {
+    pub const LEN: usize = Self::INIT_SPACE;
+
+    // Older stakes keep the rewards-stop-at-expiry behaviour they were made with
+    pub fn upgrade(self) -> UserStake {
+        UserStake {
+            amount: self.amount,
+            tier: self.tier,
+            token_type: self.token_type,
+            deposited_at: self.deposited_at,
+            lock_until: self.lock_until,
+            claimed_rewards: self.claimed_rewards,
+            is_active: self.is_active,
+            receipt_mint: self.receipt_mint,
+            post_lock: PostLock::None,
+            post_lock_rate_bps: 0,
+            is_unbonding: false,
+            unstake_requested_at: 0,
+            unbonding_until: 0,
+            loyalty_bonus_bps: 0,
+            campaign_boost_bps: 0,
+            terms_fixed: false,
+            multiplier: 0,
+            lock_days: 0,
+            accrual_mode: AccrualMode::Daily,
+            rounding: Rounding::Down,
+            reserved: [0; UserStake::RESERVED],
+        }
+    }
+}
+
+// Version 2 user account: stakes with a post-lock policy but no unbonding state
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UserAccountV2 {
+    pub user: Pubkey,
+    pub stakes: Vec<UserStakeV2>,
+    pub total_claimed_sol: u64,
+    pub total_claimed_usdt: u64,
+    pub last_claim_time: i64,
+    pub bump: u8,
+    pub version: u8,
+    // Padding before the loyalty score took 8 bytes of it
+    pub reserved: [u8; 32],
+}
+
+impl UserAccountV2 {
+    // Stakes changed size between versions, so the version byte can only be
+    // found by decoding a layout; unused capacity is zeroed, so decoding the
+    // wrong one doesn't land on a matching version
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let account = Self::deserialize(&mut data.get(8..)?).ok()?;
+        (account.version == 2).then_some(account)
+    }
+
+    pub fn upgrade(self) -> UserAccount {
+        UserAccount {
+            user: self.user,
+            stakes: self.stakes.into_iter().map(UserStakeV2::upgrade).collect(),
+            total_claimed_sol: self.total_claimed_sol,
+            total_claimed_usdt: self.total_claimed_usdt,
+            last_claim_time: self.last_claim_time,
+            bump: self.bump,
+            version: UserAccount::VERSION,
+            loyalty_stake_days: 0,
+            reserved: [0; UserAccount::RESERVED],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct UserStakeV2 {
+    pub amount: u64,
+    pub tier: u8,
+    pub token_type: TokenType,
+    pub deposited_at: i64,
+    pub lock_until: i64,
+    pub claimed_rewards: u64,
+    pub is_active: bool,
+    pub receipt_mint: Pubkey,
+    pub post_lock: PostLock,
+    pub post_lock_rate_bps: u16,
+}
+
+impl UserStakeV2 {
+    pub const LEN: usize = Self::INIT_SPACE;
+
+    pub fn upgrade(self) -> UserStake {
+        UserStake {
+            amount: self.amount,
+            tier: self.tier,
+            token_type: self.token_type,
+            deposited_at: self.deposited_at,
+            lock_until: self.lock_until,
+            claimed_rewards: self.claimed_rewards,
+            is_active: self.is_active,
+            receipt_mint: self.receipt_mint,
+            post_lock: self.post_lock,
+            post_lock_rate_bps: self.post_lock_rate_bps,
+            is_unbonding: false,
+            unstake_requested_at: 0,
+            unbonding_until: 0,
+            loyalty_bonus_bps: 0,
+            campaign_boost_bps: 0,
+            terms_fixed: false,
+            multiplier: 0,
+            lock_days: 0,
+            accrual_mode: AccrualMode::Daily,
+            rounding: Rounding::Down,
+            reserved: [0; UserStake::RESERVED],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TierConfigV0 {
+    pub boot_min_stake: u64,
+    pub boot_multiplier: u64,
+    pub boot_lock_days: i64,
+    pub symbiotic_min_stake: u64,
+    pub symbiotic_multiplier: u64,
+    pub symbiotic_lock_days: i64,
+    pub space_min_stake: u64,
+    pub space_multiplier: u64,
+    pub space_lock_days: i64,
+    pub bump: u8,
+}
+
+impl TierConfigV0 {
+    pub fn upgrade(self) -> TierConfigV1 {
+        TierConfigV1 {
+            boot_min_stake: self.boot_min_stake,
+            boot_multiplier: self.boot_multiplier,
+            boot_lock_days: self.boot_lock_days,
+            symbiotic_min_stake: self.symbiotic_min_stake,
+            symbiotic_multiplier: self.symbiotic_multiplier,
+            symbiotic_lock_days: self.symbiotic_lock_days,
+            space_min_stake: self.space_min_stake,
+            space_multiplier: self.space_multiplier,
+            space_lock_days: self.space_lock_days,
+            bump: self.bump,
+            version: 1,
+            reserved: [0; TierConfig::RESERVED],
+        }
+    }
+}
+
+// Version 1 tier config: one fixed set of fields per built-in tier
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TierConfigV1 {
+    pub boot_min_stake: u64,
+    pub boot_multiplier: u64,
+    pub boot_lock_days: i64,
+    pub symbiotic_min_stake: u64,
+    pub symbiotic_multiplier: u64,
+    pub symbiotic_lock_days: i64,
+    pub space_min_stake: u64,
+    pub space_multiplier: u64,
+    pub space_lock_days: i64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; TierConfig::RESERVED],
+}
+
+impl TierConfigV1 {
+    pub fn upgrade(self) -> TierConfig {
+        TierConfig {
+            tiers: vec![
+                TierInfo::from_lock_days("Boot", self.boot_min_stake, self.boot_multiplier, self.boot_lock_days),
+                TierInfo::from_lock_days(
+                    "Symbiotic",
+                    self.symbiotic_min_stake,
+                    self.symbiotic_multiplier,
+                    self.symbiotic_lock_days,
+                ),
+                TierInfo::from_lock_days("Space", self.space_min_stake, self.space_multiplier, self.space_lock_days),
+            ],
+            bump: self.bump,
+            version: TierConfig::VERSION,
+            reserved: [0; TierConfig::RESERVED],
+        }
+    }
+}
+
+// Version 2 tier config: the first tier list, without caps, deposit windows
+// or per-tier padding
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct TierConfigV2 {
+    #[max_len(MAX_TIERS)]
+    pub tiers: Vec<TierInfoV2>,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; TierConfig::RESERVED],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct TierInfoV2 {
+    #[max_len(MAX_TIER_NAME_LEN)]
+    pub name: String,
+    pub min_stake: u64,
+    pub multiplier: u64,
+    pub lock_seconds: i64,
+    pub enabled: bool,
+    pub max_stakes: u64,
+    pub active_stakes: u64,
+}
+
+impl TierConfigV2 {
+    // Version 2 accounts were always allocated for the full tier list
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    pub fn upgrade(self) -> TierConfig {
+        TierConfig {
+            tiers: self.tiers.into_iter().map(TierInfoV2::upgrade).collect(),
+            bump: self.bump,
+            version: TierConfig::VERSION,
+            reserved: self.reserved,
+        }
+    }
+}
+
+impl TierInfoV2 {
+    pub fn upgrade(self) -> TierInfo {
+        TierInfo {
+            name: self.name,
+            min_stake: self.min_stake,
+            multiplier: self.multiplier,
+            lock_seconds: self.lock_seconds,
+            enabled: self.enabled,
+            max_stakes: self.max_stakes,
+            active_stakes: self.active_stakes,
+            max_total_stake: 0,
+            max_user_stake: 0,
+            open_at: 0,
+            close_at: 0,
+            total_staked_sol: 0,
+            total_staked_usdt: 0,
+            accrual_mode: AccrualMode::Daily,
+            rounding: Rounding::Down,
+            post_lock: PostLock::None,
+            post_lock_rate_bps: 0,
+            unbonding_seconds: 0,
+            penalty_curve: PenaltyCurve::Flat,
+            penalty_rate_bps: 0,
+            penalty_steps: [PenaltyStep::default(); MAX_PENALTY_STEPS],
+            penalty_step_count: 0,
+            penalty_on_principal: false,
+            penalty_clawback: false,
+            reserved: [0; TierInfo::RESERVED],
+        }
+    }
+}
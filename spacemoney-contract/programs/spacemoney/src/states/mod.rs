@@ -1,7 +1,23 @@
 pub mod platform_state;
 pub mod user_account;
 pub mod tier_config;
+pub mod stake_receipt;
+pub mod validator_list;
+pub mod withdrawal_queue;
+pub mod referral_account;
+pub mod campaign;
+pub mod distributor;
+pub mod insurance_fund;
+pub mod legacy;
 
 pub use platform_state::*;
 pub use user_account::*;
 pub use tier_config::*;
+pub use stake_receipt::*;
+pub use validator_list::*;
+pub use withdrawal_queue::*;
+pub use referral_account::*;
+pub use campaign::*;
+pub use distributor::*;
+pub use insurance_fund::*;
+pub use legacy::*;
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
+use crate::states::TokenType;
 
 #[account]
+#[derive(InitSpace)]
 pub struct PlatformState {
     pub admin: Pubkey,
     pub treasury_sol: u64,
@@ -11,17 +13,89 @@ pub struct PlatformState {
     pub is_paused: bool,
     pub created_at: i64,
     pub bump: u8,
+    pub stake_transfers_enabled: bool,
+    pub spsol_mint: Pubkey,
+    // Liquid deposits plus their share of collected staking yield, less redemptions
+    pub liquid_staked_sol: u64,
+    // When staking yield was last credited to the liquid pool
+    pub liquid_last_accrual: i64,
+    pub version: u8,
+    // Owed to tickets in the withdrawal queue
+    pub queued_withdrawals_sol: u64,
+    pub queued_withdrawals_usdt: u64,
+    // Share of each deposit fee credited to the depositor's referrer
+    pub referral_share_bps: u16,
+    // Campaigns created so far; the next one's id
+    pub campaign_count: u64,
+    // Bonus distributors created so far; the next one's id
+    pub distributor_count: u64,
+    // Share of the treasury's fees and penalties paid into the insurance fund
+    pub insurance_share_bps: u16,
+    pub reserved: [u8; PlatformState::RESERVED],
 }
 
 impl PlatformState {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // admin
-        8 +  // treasury_sol
-        8 +  // treasury_usdt
-        8 +  // total_staked_sol
-        8 +  // total_staked_usdt
-        32 + // usdt_mint
-        1 +  // is_paused
-        8 +  // created_at
-        1;   // bump
+    pub const VERSION: u8 = 1;
+    // Room for new fields without a realloc; shrink it as fields are added
+    pub const RESERVED: usize = 28;
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    // What a payout can draw on once queued withdrawals are set aside, so
+    // nothing is paid ahead of the queue
+    pub fn available(&self, token_type: TokenType, balance: u64) -> u64 {
+        balance.saturating_sub(self.queued_withdrawals(token_type))
+    }
+    
+    // What treasury-funded payouts can draw on: the liquid pool's SOL isn't
+    // the platform's to spend
+    pub fn available_for_payouts(&self, token_type: TokenType, balance: u64) -> u64 {
+        let liquid = match token_type {
+            TokenType::SOL => self.liquid_staked_sol,
+            TokenType::USDT => 0,
+        };
+        self.available(token_type, balance).saturating_sub(liquid)
+    }
+    
+    // What locked-stake payouts can draw on: the treasury isn't theirs either
+    pub fn available_for_stakes(&self, token_type: TokenType, balance: u64) -> u64 {
+        self.available(token_type, balance)
+            .saturating_sub(self.held_outside_stakes(token_type))
+    }
+    
+    // Vault funds with another claim on them than the locked stakes
+    pub fn held_outside_stakes(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.liquid_staked_sol.saturating_add(self.treasury_sol),
+            TokenType::USDT => self.treasury_usdt,
+        }
+    }
+    
+    // What spSOL redemptions can draw on: the vault also holds locked stakes
+    // and the treasury, which the liquid pool has no claim to
+    pub fn available_for_liquid(&self, balance: u64) -> u64 {
+        self.available(TokenType::SOL, balance)
+            .saturating_sub(self.total_staked_sol)
+            .saturating_sub(self.treasury_sol)
+    }
+    
+    pub fn total_staked(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.total_staked_sol,
+            TokenType::USDT => self.total_staked_usdt,
+        }
+    }
+    
+    pub fn queued_withdrawals(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.queued_withdrawals_sol,
+            TokenType::USDT => self.queued_withdrawals_usdt,
+        }
+    }
+    
+    pub fn queued_withdrawals_mut(&mut self, token_type: TokenType) -> &mut u64 {
+        match token_type {
+            TokenType::SOL => &mut self.queued_withdrawals_sol,
+            TokenType::USDT => &mut self.queued_withdrawals_usdt,
+        }
+    }
 }
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::TokenType;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,
+    // Deposits made with this referrer and their gross amounts
+    pub referred_deposits: u64,
+    pub referred_volume_sol: u64,
+    pub referred_volume_usdt: u64,
+    // Fee share credited and not yet claimed
+    pub pending_sol: u64,
+    pub pending_usdt: u64,
+    pub total_claimed_sol: u64,
+    pub total_claimed_usdt: u64,
+    pub created_at: i64,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; ReferralAccount::RESERVED],
+}
+
+impl ReferralAccount {
+    pub const VERSION: u8 = 1;
+    pub const RESERVED: usize = 32;
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+    
+    // Records a referred deposit and its share of the fee
+    pub fn credit(&mut self, token_type: TokenType, volume: u64, reward: u64) -> Result<()> {
+        let (referred_volume, pending) = match token_type {
+            TokenType::SOL => (&mut self.referred_volume_sol, &mut self.pending_sol),
+            TokenType::USDT => (&mut self.referred_volume_usdt, &mut self.pending_usdt),
+        };
+        *referred_volume = referred_volume
+            .checked_add(volume)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        *pending = pending
+            .checked_add(reward)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        self.referred_deposits = self.referred_deposits
+            .checked_add(1)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(())
+    }
+    
+    pub fn pending(&self, token_type: TokenType) -> u64 {
+        match token_type {
+            TokenType::SOL => self.pending_sol,
+            TokenType::USDT => self.pending_usdt,
+        }
+    }
+    
+    // Clears the pending balance once it has been paid out
+    pub fn settle(&mut self, token_type: TokenType) -> Result<u64> {
+        let (pending, total_claimed) = match token_type {
+            TokenType::SOL => (&mut self.pending_sol, &mut self.total_claimed_sol),
+            TokenType::USDT => (&mut self.pending_usdt, &mut self.total_claimed_usdt),
+        };
+        let amount = *pending;
+        *total_claimed = total_claimed
+            .checked_add(amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        *pending = 0;
+        Ok(amount)
+    }
+}
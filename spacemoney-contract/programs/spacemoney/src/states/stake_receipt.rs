@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::states::TokenType;
+
+#[account]
+pub struct StakeReceipt {
+    pub mint: Pubkey,
+    pub user_account: Pubkey,
+    pub stake_index: u64,
+    pub amount: u64,
+    pub tier: u8,
+    pub token_type: TokenType,
+    pub deposited_at: i64,
+    pub lock_until: i64,
+    pub bump: u8,
+}
+
+impl StakeReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // user_account
+        8 +  // stake_index
+        8 +  // amount
+        1 +  // tier
+        1 +  // token_type
+        8 +  // deposited_at
+        8 +  // lock_until
+        1;   // bump
+}
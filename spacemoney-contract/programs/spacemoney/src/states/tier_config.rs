@@ -1,26 +1,302 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::TokenType;
 
 #[account]
+#[derive(InitSpace)]
 pub struct TierConfig {
-    pub boot_min_stake: u64,
-    pub boot_multiplier: u64,
-    pub boot_lock_days: i64,
+    // Indexed by the tier id stored on each stake, so tiers are never removed
+    #[max_len(MAX_TIERS)]
+    pub tiers: Vec<TierInfo>,
+    pub bump: u8,
+    pub version: u8,
+    pub reserved: [u8; TierConfig::RESERVED],
+}
+
+impl TierConfig {
+    pub const VERSION: u8 = 3;
+    pub const RESERVED: usize = 64;
+    // Allocated for the full tier list so add_tier never has to resize
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+
+    pub fn tier(&self, tier: u8) -> Result<&TierInfo> {
+        Ok(self.tiers.get(tier as usize).ok_or(SpaceMoneyError::InvalidTier)?)
+    }
+
+    pub fn tier_mut(&mut self, tier: u8) -> Result<&mut TierInfo> {
+        Ok(self.tiers.get_mut(tier as usize).ok_or(SpaceMoneyError::InvalidTier)?)
+    }
+
+    // The Boot, Symbiotic and Space tiers every platform starts with
+    pub fn default_tiers() -> Vec<TierInfo> {
+        vec![
+            TierInfo::from_lock_days("Boot", BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS),
+            TierInfo::from_lock_days("Symbiotic", SYMBIOTIC_MIN_STAKE, SYMBIOTIC_MULTIPLIER, SYMBIOTIC_LOCK_DAYS),
+            TierInfo::from_lock_days("Space", SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS),
+        ]
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TierInfo {
+    #[max_len(MAX_TIER_NAME_LEN)]
+    pub name: String,
+    pub min_stake: u64,
+    pub multiplier: u64,
+    pub lock_seconds: i64,
+    // Disabled tiers take no new deposits; existing stakes are unaffected
+    pub enabled: bool,
+    // Cap on active stakes in the tier, 0 for no cap
+    pub max_stakes: u64,
+    pub active_stakes: u64,
+    // Caps on principal, applied to each token in its own base units; 0 for no cap
+    pub max_total_stake: u64,
+    pub max_user_stake: u64,
+    // Deposit window; 0 leaves that side open
+    pub open_at: i64,
+    pub close_at: i64,
+    pub total_staked_sol: u64,
+    pub total_staked_usdt: u64,
+    // Taken from reserved, where the zeroed bytes read as Daily and Down
+    pub accrual_mode: AccrualMode,
+    pub rounding: Rounding,
+    // Copied onto each new stake, so changes only reach later deposits
+    pub post_lock: PostLock,
+    pub post_lock_rate_bps: u16,
+    // Cooldown between request_unstake and complete_unstake; 0 lets stakes
+    // be withdrawn directly
+    pub unbonding_seconds: i64,
+    // Early-exit penalty, read when a stake is force withdrawn; the zeroed
+    // bytes read as the flat 20% of unclaimed rewards
+    pub penalty_curve: PenaltyCurve,
+    pub penalty_rate_bps: u16,
+    pub penalty_steps: [PenaltyStep; MAX_PENALTY_STEPS],
+    pub penalty_step_count: u8,
+    pub penalty_on_principal: bool,
+    pub penalty_clawback: bool,
+    // Room for per-tier settings without another tier list migration
+    pub reserved: [u8; TierInfo::RESERVED],
+}
+
+impl TierInfo {
+    pub const RESERVED: usize = 29;
+
+    // Built-in and legacy tiers were configured in whole days
+    pub fn from_lock_days(name: &str, min_stake: u64, multiplier: u64, lock_days: i64) -> Self {
+        Self {
+            name: name.to_string(),
+            min_stake,
+            multiplier,
+            lock_seconds: lock_days * SECONDS_PER_DAY,
+            enabled: true,
+            max_stakes: 0,
+            active_stakes: 0,
+            max_total_stake: 0,
+            max_user_stake: 0,
+            open_at: 0,
+            close_at: 0,
+            total_staked_sol: 0,
+            total_staked_usdt: 0,
+            accrual_mode: AccrualMode::Daily,
+            rounding: Rounding::Down,
+            post_lock: PostLock::None,
+            post_lock_rate_bps: 0,
+            unbonding_seconds: 0,
+            penalty_curve: PenaltyCurve::Flat,
+            penalty_rate_bps: 0,
+            penalty_steps: [PenaltyStep::default(); MAX_PENALTY_STEPS],
+            penalty_step_count: 0,
+            penalty_on_principal: false,
+            penalty_clawback: false,
+            reserved: [0; TierInfo::RESERVED],
+        }
+    }
+
+    pub fn penalty(&self) -> spacemoney_math::PenaltyPolicy {
+        let mut steps = [spacemoney_math::PenaltyStep::default(); MAX_PENALTY_STEPS];
+        for (step, stored) in steps.iter_mut().zip(self.penalty_steps.iter()) {
+            *step = (*stored).into();
+        }
+        spacemoney_math::PenaltyPolicy {
+            curve: self.penalty_curve.into(),
+            rate_bps: self.penalty_rate_bps as u64,
+            steps,
+            step_count: self.penalty_step_count as usize,
+            on_principal: self.penalty_on_principal,
+            clawback_claimed: self.penalty_clawback,
+        }
+    }
     
-    pub symbiotic_min_stake: u64,
-    pub symbiotic_multiplier: u64,
-    pub symbiotic_lock_days: i64,
+    // Rewards accrue per whole day of the lock
+    pub fn lock_days(&self) -> i64 {
+        self.lock_seconds / SECONDS_PER_DAY
+    }
     
-    pub space_min_stake: u64,
-    pub space_multiplier: u64,
-    pub space_lock_days: i64,
+    // Stakes record the multiplier and lock days in 4 bytes each
+    pub fn valid_terms(multiplier: u64, lock_seconds: i64) -> bool {
+        lock_seconds >= 0
+            && u32::try_from(multiplier).is_ok()
+            && u32::try_from(lock_seconds / SECONDS_PER_DAY).is_ok()
+    }
     
-    pub bump: u8,
+    // The multiplier and lock days a new stake records
+    pub fn fixed_terms(&self) -> Result<(u32, u32)> {
+        let multiplier = u32::try_from(self.multiplier)
+            .map_err(|_| SpaceMoneyError::InvalidTierParams)?;
+        let lock_days = u32::try_from(self.lock_days())
+            .map_err(|_| SpaceMoneyError::InvalidTierParams)?;
+        Ok((multiplier, lock_days))
+    }
+
+    // Deposits are only taken inside the tier's window
+    pub fn check_open(&self, timestamp: i64) -> Result<()> {
+        require!(
+            self.open_at == 0 || timestamp >= self.open_at,
+            SpaceMoneyError::TierNotOpen
+        );
+        require!(
+            self.close_at == 0 || timestamp < self.close_at,
+            SpaceMoneyError::TierClosed
+        );
+        Ok(())
+    }
+    
+    // Counts a new stake against the tier's caps. `user_staked` is what the
+    // depositor already has in the tier in the same token.
+    pub fn add_stake(&mut self, token_type: TokenType, amount: u64, user_staked: u64) -> Result<()> {
+        require!(
+            self.max_stakes == 0 || self.active_stakes < self.max_stakes,
+            SpaceMoneyError::TierFull
+        );
+        self.check_user_stake(amount, user_staked)?;
+        
+        let max_total_stake = self.max_total_stake;
+        let total_staked = self.total_staked_mut(token_type);
+        *total_staked = total_staked
+            .checked_add(amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        require!(
+            max_total_stake == 0 || *total_staked <= max_total_stake,
+            SpaceMoneyError::TierStakeCapExceeded
+        );
+        
+        self.active_stakes = self.active_stakes
+            .checked_add(1)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(())
+    }
+    
+    // Holds one user to the tier's per-user cap once `amount` more is theirs
+    pub fn check_user_stake(&self, amount: u64, user_staked: u64) -> Result<()> {
+        let user_total = user_staked
+            .checked_add(amount)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        require!(
+            self.max_user_stake == 0 || user_total <= self.max_user_stake,
+            SpaceMoneyError::UserStakeCapExceeded
+        );
+        Ok(())
+    }
+    
+    // Stakes from before the tier list (or its totals) weren't counted, so
+    // this saturates rather than failing
+    pub fn remove_stake(&mut self, token_type: TokenType, amount: u64) {
+        self.active_stakes = self.active_stakes.saturating_sub(1);
+        let total_staked = self.total_staked_mut(token_type);
+        *total_staked = total_staked.saturating_sub(amount);
+    }
+    
+    fn total_staked_mut(&mut self, token_type: TokenType) -> &mut u64 {
+        match token_type {
+            TokenType::SOL => &mut self.total_staked_sol,
+            TokenType::USDT => &mut self.total_staked_usdt,
+        }
+    }
 }
 
-impl TierConfig {
-    pub const LEN: usize = 8 + // discriminator
-        8 + 8 + 8 + // boot
-        8 + 8 + 8 + // symbiotic
-        8 + 8 + 8 + // space
-        1;          // bump
+// How a tier's rewards accrue; see spacemoney_math::AccrualMode
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AccrualMode {
+    Daily,
+    PerSecond,
+}
+
+// Rounding for per-second accrual; see spacemoney_math::Rounding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum Rounding {
+    Down,
+    Nearest,
+    Up,
+}
+
+// What a stake does after its lock; see spacemoney_math::PostLock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PostLock {
+    None,
+    AutoRoll,
+    Flexible,
+}
+
+// How a tier's early-exit rate moves through a lock; see spacemoney_math::PenaltyCurve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PenaltyCurve {
+    Flat,
+    Linear,
+    Step,
+}
+
+// A step of a Step penalty schedule; see spacemoney_math::PenaltyStep
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct PenaltyStep {
+    pub from_bps: u16,
+    pub rate_bps: u16,
+}
+
+impl From<AccrualMode> for spacemoney_math::AccrualMode {
+    fn from(mode: AccrualMode) -> Self {
+        match mode {
+            AccrualMode::Daily => spacemoney_math::AccrualMode::Daily,
+            AccrualMode::PerSecond => spacemoney_math::AccrualMode::PerSecond,
+        }
+    }
+}
+
+impl From<Rounding> for spacemoney_math::Rounding {
+    fn from(rounding: Rounding) -> Self {
+        match rounding {
+            Rounding::Down => spacemoney_math::Rounding::Down,
+            Rounding::Nearest => spacemoney_math::Rounding::Nearest,
+            Rounding::Up => spacemoney_math::Rounding::Up,
+        }
+    }
+}
+
+impl From<PostLock> for spacemoney_math::PostLock {
+    fn from(post_lock: PostLock) -> Self {
+        match post_lock {
+            PostLock::None => spacemoney_math::PostLock::None,
+            PostLock::AutoRoll => spacemoney_math::PostLock::AutoRoll,
+            PostLock::Flexible => spacemoney_math::PostLock::Flexible,
+        }
+    }
+}
+
+impl From<PenaltyCurve> for spacemoney_math::PenaltyCurve {
+    fn from(curve: PenaltyCurve) -> Self {
+        match curve {
+            PenaltyCurve::Flat => spacemoney_math::PenaltyCurve::Flat,
+            PenaltyCurve::Linear => spacemoney_math::PenaltyCurve::Linear,
+            PenaltyCurve::Step => spacemoney_math::PenaltyCurve::Step,
+        }
+    }
+}
+
+impl From<PenaltyStep> for spacemoney_math::PenaltyStep {
+    fn from(step: PenaltyStep) -> Self {
+        Self {
+            from_bps: step.from_bps as u64,
+            rate_bps: step.rate_bps as u64,
+        }
+    }
 }
@@ -1,77 +1,170 @@
 use anchor_lang::prelude::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::{AccrualMode, PostLock, Rounding, TierInfo};
+use spacemoney_math::{
+    calculate_stake_days, loyalty_bonus_bps, loyalty_level, stake_days_to_next_level, StakeTerms,
+    LOYALTY_UNIT_SOL, LOYALTY_UNIT_USDT,
+};
 
 #[account]
+#[derive(InitSpace)]
 pub struct UserAccount {
     pub user: Pubkey,
+    #[max_len(0)]
     pub stakes: Vec<UserStake>,
     pub total_claimed_sol: u64,
     pub total_claimed_usdt: u64,
     pub last_claim_time: i64,
     pub bump: u8,
+    pub version: u8,
+    // Amount-weighted days every closed stake was held; sets the loyalty bonus
+    // of new stakes
+    pub loyalty_stake_days: u64,
+    pub reserved: [u8; UserAccount::RESERVED],
 }
 
 impl UserAccount {
-    pub const INITIAL_LEN: usize = 8 + // discriminator
-        32 + // user
-        4 +  // vec length
-        8 +  // total_claimed_sol
-        8 +  // total_claimed_usdt
-        8 +  // last_claim_time
-        1;   // bump
+    pub const VERSION: u8 = 4;
+    pub const RESERVED: usize = 24;
+    // Discriminator plus an empty stake list
+    pub const INITIAL_LEN: usize = 8 + Self::INIT_SPACE;
 
     pub fn space_for_stakes(num_stakes: usize) -> usize {
         Self::INITIAL_LEN + (num_stakes * UserStake::LEN)
     }
+    
+    pub fn active_stakes(&self) -> u64 {
+        self.stakes.iter().filter(|stake| stake.is_active).count() as u64
+    }
+    
+    // Active principal the user has in a tier, in one token
+    pub fn staked_in_tier(&self, tier: u8, token_type: TokenType) -> Result<u64> {
+        let total = self.stakes.iter()
+            .filter(|stake| stake.is_active && stake.tier == tier && stake.token_type == token_type)
+            .try_fold(0u64, |total, stake| total.checked_add(stake.amount))
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(total)
+    }
+    
+    // Adds the stake-days a closing stake built up to the loyalty score.
+    // Returns the stake-days added.
+    pub fn record_loyalty(&mut self, index: usize, timestamp: i64) -> Result<u64> {
+        let stake_days = self.stakes[index].stake_days_at(timestamp);
+        self.loyalty_stake_days = self.loyalty_stake_days
+            .checked_add(stake_days)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?;
+        Ok(stake_days)
+    }
+    
+    pub fn loyalty_level(&self) -> u8 {
+        loyalty_level(self.loyalty_stake_days)
+    }
+    
+    // Bonus a new stake gets at the current loyalty level
+    pub fn loyalty_bonus_bps(&self) -> u16 {
+        loyalty_bonus_bps(self.loyalty_stake_days) as u16
+    }
+    
+    pub fn stake_days_to_next_level(&self) -> u64 {
+        stake_days_to_next_level(self.loyalty_stake_days)
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
 pub struct UserStake {
     pub amount: u64,
-    pub tier: Tier,
+    // Index into TierConfig::tiers, encoded like the old Boot/Symbiotic/Space enum
+    pub tier: u8,
     pub token_type: TokenType,
     pub deposited_at: i64,
     pub lock_until: i64,
     pub claimed_rewards: u64,
     pub is_active: bool,
+    pub receipt_mint: Pubkey,
+    // The tier's post-lock policy when the stake was made
+    pub post_lock: PostLock,
+    pub post_lock_rate_bps: u16,
+    // Set by request_unstake: the stake stays active, earning nothing more,
+    // until complete_unstake pays it out after the tier's cooldown
+    pub is_unbonding: bool,
+    pub unstake_requested_at: i64,
+    pub unbonding_until: i64,
+    // The owner's loyalty bonus when the stake was made
+    pub loyalty_bonus_bps: u16,
+    // Boost from the campaign the stake was made under, if any
+    pub campaign_boost_bps: u16,
+    // The tier's multiplier, lock days and accrual when the stake was made,
+    // so tier updates only reach new stakes. Unset on stakes made before
+    // version 4 until their account is migrated, which read the live tier
+    // meanwhile.
+    pub terms_fixed: bool,
+    pub multiplier: u32,
+    pub lock_days: u32,
+    pub accrual_mode: AccrualMode,
+    pub rounding: Rounding,
+    // Room for per-stake state without another user account migration
+    pub reserved: [u8; UserStake::RESERVED],
 }
 
 impl UserStake {
-    pub const LEN: usize = 8 +  // amount
-        1 +  // tier
-        1 +  // token_type
-        8 +  // deposited_at
-        8 +  // lock_until
-        8 +  // claimed_rewards
-        1;   // is_active
-}
+    pub const LEN: usize = Self::INIT_SPACE;
+    pub const RESERVED: usize = 1;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum Tier {
-    Boot,
-    Symbiotic,
-    Space,
-}
-
-impl Tier {
-    pub fn from_u8(value: u8) -> Option<Self> {
-        match value {
-            0 => Some(Tier::Boot),
-            1 => Some(Tier::Symbiotic),
-            2 => Some(Tier::Space),
-            _ => None,
-        }
+    pub fn has_receipt(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
     }
-
-    pub fn to_u8(&self) -> u8 {
-        match self {
-            Tier::Boot => 0,
-            Tier::Symbiotic => 1,
-            Tier::Space => 2,
+    
+    // Amount-weighted days held by `timestamp`; an unbonding stake stops
+    // counting at its unstake request
+    pub fn stake_days_at(&self, timestamp: i64) -> u64 {
+        let held_until = if self.is_unbonding {
+            self.unstake_requested_at
+        } else {
+            timestamp
+        };
+        let unit = match self.token_type {
+            TokenType::SOL => LOYALTY_UNIT_SOL,
+            TokenType::USDT => LOYALTY_UNIT_USDT,
+        };
+        calculate_stake_days(self.amount, unit, self.deposited_at, held_until)
+    }
+    
+    // Records the tier's reward terms on the stake
+    pub fn fix_terms(&mut self, tier: &TierInfo) -> Result<()> {
+        (self.multiplier, self.lock_days) = tier.fixed_terms()?;
+        self.accrual_mode = tier.accrual_mode;
+        self.rounding = tier.rounding;
+        self.terms_fixed = true;
+        Ok(())
+    }
+    
+    // Reward terms used for claim, withdraw and off-chain projections
+    pub fn terms(&self, tier: &TierInfo) -> StakeTerms {
+        let (multiplier, lock_days, accrual_mode, rounding) = if self.terms_fixed {
+            (self.multiplier as u64, self.lock_days as i64, self.accrual_mode, self.rounding)
+        } else {
+            (tier.multiplier, tier.lock_days(), tier.accrual_mode, tier.rounding)
+        };
+        StakeTerms {
+            amount: self.amount,
+            multiplier,
+            lock_days,
+            deposited_at: self.deposited_at,
+            lock_until: self.lock_until,
+            claimed_rewards: self.claimed_rewards,
+            accrual: accrual_mode.into(),
+            rounding: rounding.into(),
+            post_lock: self.post_lock.into(),
+            post_lock_rate_bps: self.post_lock_rate_bps as u64,
+            stopped_at: self.is_unbonding.then_some(self.unstake_requested_at),
+            penalty: tier.penalty(),
+            loyalty_bonus_bps: self.loyalty_bonus_bps as u64,
+            campaign_boost_bps: self.campaign_boost_bps as u64,
         }
     }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum TokenType {
     SOL,
     USDT,
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_VALIDATORS;
+
+#[account]
+pub struct ValidatorList {
+    pub validators: Vec<ValidatorEntry>,
+    pub target_delegation_bps: u64,
+    pub total_delegated: u64,
+    pub total_yield: u64,
+    pub bump: u8,
+}
+
+impl ValidatorList {
+    pub const LEN: usize = 8 + // discriminator
+        4 + (MAX_VALIDATORS * ValidatorEntry::LEN) + // validators
+        8 +  // target_delegation_bps
+        8 +  // total_delegated
+        8 +  // total_yield
+        1;   // bump
+
+    pub fn find(&self, vote_account: &Pubkey) -> Option<usize> {
+        self.validators
+            .iter()
+            .position(|entry| entry.vote_account == *vote_account)
+    }
+
+    pub fn active_count(&self) -> u64 {
+        self.validators
+            .iter()
+            .filter(|entry| !entry.pending_removal)
+            .count() as u64
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ValidatorEntry {
+    pub vote_account: Pubkey,
+    pub status: ValidatorStatus,
+    pub active_stake: u64,
+    pub next_update_epoch: u64,
+    pub pending_removal: bool,
+}
+
+impl ValidatorEntry {
+    pub const LEN: usize = 32 + // vote_account
+        1 +  // status
+        8 +  // active_stake
+        8 +  // next_update_epoch
+        1;   // pending_removal
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ValidatorStatus {
+    Inactive,
+    Active,
+    Deactivating,
+}
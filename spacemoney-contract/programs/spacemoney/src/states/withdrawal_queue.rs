@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_QUEUED_WITHDRAWALS;
+use crate::errors::SpaceMoneyError;
+use crate::states::TokenType;
+
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalQueue {
+    // Oldest first; filled tickets are removed
+    #[max_len(MAX_QUEUED_WITHDRAWALS)]
+    pub tickets: Vec<WithdrawalTicket>,
+    pub next_ticket_id: u64,
+    pub bump: u8,
+}
+
+impl WithdrawalQueue {
+    pub const LEN: usize = 8 + Self::INIT_SPACE;
+}
+
+// A closed stake's payout, waiting for the vault to hold enough to cover it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct WithdrawalTicket {
+    pub id: u64,
+    pub user: Pubkey,
+    pub stake_index: u64,
+    pub token_type: TokenType,
+    // Wallet for SOL, token account for USDT
+    pub destination: Pubkey,
+    pub principal: u64,
+    pub rewards: u64,
+    pub queued_at: i64,
+}
+
+impl WithdrawalTicket {
+    pub fn amount(&self) -> Result<u64> {
+        Ok(self.principal
+            .checked_add(self.rewards)
+            .ok_or(SpaceMoneyError::NumericalOverflow)?)
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::states::UserAccount;
+use crate::utils::transfer_sol;
+
+// Stakes are never removed, since their index identifies them (receipt PDAs
+// are derived from it), so a full account grows by one stake at a time. The
+// payer covers the extra rent.
+pub fn reserve_stake_space<'info>(
+    user_account: &Account<'info, UserAccount>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let account = user_account.to_account_info();
+    let needed = UserAccount::space_for_stakes(user_account.stakes.len() + 1);
+    if account.data_len() >= needed {
+        return Ok(());
+    }
+    
+    let rent_due = Rent::get()?
+        .minimum_balance(needed)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        transfer_sol(payer, &account, system_program, rent_due)?;
+    }
+    
+    account.realloc(needed, true)?;
+    Ok(())
+}
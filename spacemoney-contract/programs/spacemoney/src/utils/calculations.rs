@@ -1,20 +1,33 @@
-use crate::constants::*;
 use crate::errors::SpaceMoneyError;
 use anchor_lang::prelude::*;
+use spacemoney_math::{MathError, PenaltyBreakdown, PenaltyPolicy};
+
+// The arithmetic itself lives in spacemoney-math; these wrappers map its
+// errors onto program error codes
+impl From<MathError> for SpaceMoneyError {
+    fn from(err: MathError) -> Self {
+        match err {
+            MathError::Overflow => SpaceMoneyError::NumericalOverflow,
+            MathError::Calculation => SpaceMoneyError::CalculationError,
+            MathError::InvalidTier => SpaceMoneyError::InvalidTier,
+        }
+    }
+}
 
 pub fn calculate_deposit_fee(amount: u64) -> Result<u64> {
-    amount
-        .checked_mul(DEPOSIT_FEE_BPS)
-        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
-        .ok_or(SpaceMoneyError::NumericalOverflow.into())
+    Ok(spacemoney_math::calculate_deposit_fee(amount).map_err(SpaceMoneyError::from)?)
 }
 
 pub fn calculate_net_deposit(amount: u64) -> Result<(u64, u64)> {
-    let fee = calculate_deposit_fee(amount)?;
-    let net_amount = amount
-        .checked_sub(fee)
-        .ok_or(SpaceMoneyError::NumericalOverflow)?;
-    Ok((net_amount, fee))
+    Ok(spacemoney_math::calculate_net_deposit(amount).map_err(SpaceMoneyError::from)?)
+}
+
+pub fn calculate_referral_reward(fee: u64, share_bps: u16) -> Result<u64> {
+    Ok(spacemoney_math::calculate_referral_reward(fee, share_bps as u64).map_err(SpaceMoneyError::from)?)
+}
+
+pub fn calculate_insurance_share(amount: u64, share_bps: u16) -> Result<u64> {
+    Ok(spacemoney_math::calculate_insurance_share(amount, share_bps as u64).map_err(SpaceMoneyError::from)?)
 }
 
 pub fn calculate_rewards(
@@ -22,22 +35,18 @@ pub fn calculate_rewards(
     multiplier: u64,
     days_locked: i64,
 ) -> Result<u64> {
-    // Formula: principal × multiplier × 1% × days
-    // Breaking it down to avoid overflow
-    let base_daily = principal
-        .checked_mul(multiplier)
-        .ok_or(SpaceMoneyError::CalculationError)?;
-    
-    let daily_reward = base_daily
-        .checked_mul(DAILY_YIELD_BPS)
-        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
-        .ok_or(SpaceMoneyError::CalculationError)?;
-    
-    let total_reward = daily_reward
-        .checked_mul(days_locked as u64)
-        .ok_or(SpaceMoneyError::CalculationError)?;
-    
-    Ok(total_reward)
+    Ok(spacemoney_math::calculate_rewards(principal, multiplier, days_locked)
+        .map_err(SpaceMoneyError::from)?)
+}
+
+pub fn calculate_daily_reward(principal: u64, multiplier: u64) -> Result<u64> {
+    Ok(spacemoney_math::calculate_daily_reward(principal, multiplier)
+        .map_err(SpaceMoneyError::from)?)
+}
+
+pub fn calculate_claimed_share(claimed: u64, part_daily: u64, total_daily: u64) -> Result<u64> {
+    Ok(spacemoney_math::calculate_claimed_share(claimed, part_daily, total_daily)
+        .map_err(SpaceMoneyError::from)?)
 }
 
 pub fn calculate_accrued_rewards(
@@ -46,41 +55,49 @@ pub fn calculate_accrued_rewards(
     start_time: i64,
     current_time: i64,
 ) -> Result<u64> {
-    let elapsed_seconds = current_time
-        .checked_sub(start_time)
-        .ok_or(SpaceMoneyError::CalculationError)?;
-    
-    let elapsed_days = elapsed_seconds / SECONDS_PER_DAY;
-    
-    if elapsed_days <= 0 {
-        return Ok(0);
-    }
-    
-    calculate_rewards(principal, multiplier, elapsed_days)
+    Ok(spacemoney_math::calculate_accrued_rewards(principal, multiplier, start_time, current_time)
+        .map_err(SpaceMoneyError::from)?)
 }
 
-pub fn calculate_force_withdraw_penalty(rewards: u64) -> Result<u64> {
-    rewards
-        .checked_mul(FORCE_WITHDRAW_PENALTY_BPS)
-        .and_then(|v| v.checked_div(BPS_DENOMINATOR))
-        .ok_or(SpaceMoneyError::CalculationError.into())
+pub fn calculate_liquid_yield(staking_yield: u64, liquid_staked: u64, total_staked: u64) -> Result<u64> {
+    Ok(spacemoney_math::calculate_liquid_yield(staking_yield, liquid_staked, total_staked)
+        .map_err(SpaceMoneyError::from)?)
 }
 
-pub fn get_tier_config(tier: u8) -> Result<(u64, u64, i64)> {
-    match tier {
-        0 => Ok((BOOT_MIN_STAKE, BOOT_MULTIPLIER, BOOT_LOCK_DAYS)),
-        1 => Ok((SYMBIOTIC_MIN_STAKE, SYMBIOTIC_MULTIPLIER, SYMBIOTIC_LOCK_DAYS)),
-        2 => Ok((SPACE_MIN_STAKE, SPACE_MULTIPLIER, SPACE_LOCK_DAYS)),
-        _ => Err(SpaceMoneyError::InvalidTier.into()),
-    }
+pub fn calculate_spsol_to_mint(sol_amount: u64, liquid_staked: u64, spsol_supply: u64) -> Result<u64> {
+    Ok(spacemoney_math::calculate_spsol_to_mint(sol_amount, liquid_staked, spsol_supply)
+        .map_err(SpaceMoneyError::from)?)
 }
 
-pub fn validate_stake_amount(amount: u64, tier: u8) -> Result<()> {
-    let (min_stake, _, _) = get_tier_config(tier)?;
-    
-    if amount < min_stake {
-        return Err(SpaceMoneyError::InsufficientStakeAmount.into());
-    }
-    
-    Ok(())
+pub fn calculate_sol_for_spsol(spsol_amount: u64, liquid_staked: u64, spsol_supply: u64) -> Result<u64> {
+    Ok(spacemoney_math::calculate_sol_for_spsol(spsol_amount, liquid_staked, spsol_supply)
+        .map_err(SpaceMoneyError::from)?)
+}
+
+pub fn calculate_validator_target(
+    total_staked: u64,
+    target_delegation_bps: u64,
+    validator_count: u64,
+) -> Result<u64> {
+    Ok(spacemoney_math::calculate_validator_target(total_staked, target_delegation_bps, validator_count)
+        .map_err(SpaceMoneyError::from)?)
+}
+
+pub fn calculate_force_withdraw_penalty(
+    policy: &PenaltyPolicy,
+    elapsed: i64,
+    lock_length: i64,
+    principal: u64,
+    unclaimed_rewards: u64,
+    claimed_rewards: u64,
+) -> Result<PenaltyBreakdown> {
+    Ok(spacemoney_math::calculate_force_withdraw_penalty(
+        policy,
+        elapsed,
+        lock_length,
+        principal,
+        unclaimed_rewards,
+        claimed_rewards,
+    )
+    .map_err(SpaceMoneyError::from)?)
 }
@@ -0,0 +1,31 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::*;
+use anchor_lang::Event;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::constants::EVENT_AUTHORITY_SEED;
+
+// The self-CPI behind emit_cpi!, as a function so emit_event! can pass the
+// accounts explicitly instead of relying on a `ctx` in scope
+pub fn emit_cpi_event<'info, E: Event>(
+    event_authority: &AccountInfo<'info>,
+    event_authority_bump: u8,
+    event: &E,
+) -> Result<()> {
+    let mut data = EVENT_IX_TAG_LE.to_vec();
+    data.extend_from_slice(&event.data());
+    
+    let ix = Instruction::new_with_bytes(
+        crate::ID,
+        &data,
+        vec![AccountMeta::new_readonly(event_authority.key(), true)],
+    );
+    
+    invoke_signed(
+        &ix,
+        std::slice::from_ref(event_authority),
+        &[&[EVENT_AUTHORITY_SEED, &[event_authority_bump]]],
+    )?;
+    
+    Ok(())
+}
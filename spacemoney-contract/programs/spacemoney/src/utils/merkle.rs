@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak::hashv;
+
+// Leaves and inner nodes hash under different prefixes, so an inner node
+// can't be passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+// The leaf for paying `amount` to `claimant`; `index` is the claim's bit in
+// the distributor's claimed bitmap
+pub fn bonus_leaf(index: u32, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ]).to_bytes()
+}
+
+// Pairs are hashed in sorted order, so proofs don't carry left/right flags
+pub fn merkle_parent(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    hashv(&[NODE_PREFIX, first, second]).to_bytes()
+}
+
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    proof.iter().fold(leaf, |node, sibling| merkle_parent(&node, sibling)) == *root
+}
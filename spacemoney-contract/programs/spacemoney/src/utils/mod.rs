@@ -1,5 +1,17 @@
 pub mod calculations;
 pub mod token_utils;
+pub mod receipt_utils;
+pub mod stake_utils;
+pub mod event_utils;
+pub mod merkle;
+pub mod account_utils;
+pub mod vault_utils;
 
 pub use calculations::*;
 pub use token_utils::*;
+pub use receipt_utils::*;
+pub use stake_utils::*;
+pub use event_utils::*;
+pub use merkle::*;
+pub use account_utils::*;
+pub use vault_utils::*;
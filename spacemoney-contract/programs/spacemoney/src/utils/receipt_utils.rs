@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, SetAuthority, Token, TokenAccount};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use crate::errors::SpaceMoneyError;
+use crate::states::UserStake;
+
+pub fn mint_stake_receipt<'info>(
+    mint: &Account<'info, Mint>,
+    to: &Account<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = MintTo {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        seeds,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+    
+    // Revoke the mint authority so the supply stays at exactly one
+    let cpi_accounts = SetAuthority {
+        current_authority: authority.to_account_info(),
+        account_or_mint: mint.to_account_info(),
+    };
+    
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        seeds,
+    );
+    token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)
+}
+
+// Stakes with a receipt are controlled by whoever holds the NFT,
+// all others by the depositor that owns the user account
+pub fn verify_stake_authority(
+    stake: &UserStake,
+    depositor: Pubkey,
+    signer: Pubkey,
+    receipt_token_account: Option<&Account<TokenAccount>>,
+) -> Result<()> {
+    if !stake.has_receipt() {
+        require!(depositor == signer, SpaceMoneyError::Unauthorized);
+        return Ok(());
+    }
+    
+    let receipt_token_account = receipt_token_account
+        .ok_or(SpaceMoneyError::MissingReceiptAccounts)?;
+    
+    require!(
+        receipt_token_account.mint == stake.receipt_mint,
+        SpaceMoneyError::Unauthorized
+    );
+    require!(
+        receipt_token_account.owner == signer,
+        SpaceMoneyError::TokenAccountOwnerMismatch
+    );
+    require!(
+        receipt_token_account.amount == 1,
+        SpaceMoneyError::Unauthorized
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::stake::{
+    self,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+use anchor_lang::solana_program::system_instruction;
+
+// Turns a funded, system-owned PDA into an initialized stake account whose
+// staker and withdrawer are both the vault authority
+#[allow(clippy::too_many_arguments)]
+pub fn create_stake_account<'info>(
+    stake_account: &AccountInfo<'info>,
+    funding_account: &AccountInfo<'info>,
+    vault_authority: &AccountInfo<'info>,
+    rent_sysvar: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    stake_program: &AccountInfo<'info>,
+    stake_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // The funding account was debited directly, so it rides along on the
+    // first CPI to keep the caller's lamport balance in sync
+    let mut allocate_ix =
+        system_instruction::allocate(stake_account.key, StakeStateV2::size_of() as u64);
+    allocate_ix.accounts.push(AccountMeta::new(funding_account.key(), false));
+    
+    invoke_signed(
+        &allocate_ix,
+        &[stake_account.clone(), funding_account.clone(), system_program.clone()],
+        stake_seeds,
+    )?;
+    
+    invoke_signed(
+        &system_instruction::assign(stake_account.key, &stake::program::ID),
+        &[stake_account.clone(), system_program.clone()],
+        stake_seeds,
+    )?;
+    
+    let authorized = Authorized {
+        staker: vault_authority.key(),
+        withdrawer: vault_authority.key(),
+    };
+    
+    invoke_signed(
+        &stake::instruction::initialize(stake_account.key, &authorized, &Lockup::default()),
+        &[stake_account.clone(), rent_sysvar.clone(), stake_program.clone()],
+        &[],
+    )?;
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn delegate_stake<'info>(
+    stake_account: &AccountInfo<'info>,
+    vote_account: &AccountInfo<'info>,
+    vault_authority: &AccountInfo<'info>,
+    clock: &AccountInfo<'info>,
+    stake_history: &AccountInfo<'info>,
+    stake_config: &AccountInfo<'info>,
+    stake_program: &AccountInfo<'info>,
+    vault_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_signed(
+        &stake::instruction::delegate_stake(
+            stake_account.key,
+            vault_authority.key,
+            vote_account.key,
+        ),
+        &[
+            stake_account.clone(),
+            vote_account.clone(),
+            clock.clone(),
+            stake_history.clone(),
+            stake_config.clone(),
+            vault_authority.clone(),
+            stake_program.clone(),
+        ],
+        vault_seeds,
+    )?;
+    
+    Ok(())
+}
+
+pub fn deactivate_stake<'info>(
+    stake_account: &AccountInfo<'info>,
+    vault_authority: &AccountInfo<'info>,
+    clock: &AccountInfo<'info>,
+    stake_program: &AccountInfo<'info>,
+    vault_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_signed(
+        &stake::instruction::deactivate_stake(stake_account.key, vault_authority.key),
+        &[
+            stake_account.clone(),
+            clock.clone(),
+            vault_authority.clone(),
+            stake_program.clone(),
+        ],
+        vault_seeds,
+    )?;
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw_stake<'info>(
+    stake_account: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    vault_authority: &AccountInfo<'info>,
+    clock: &AccountInfo<'info>,
+    stake_history: &AccountInfo<'info>,
+    stake_program: &AccountInfo<'info>,
+    amount: u64,
+    vault_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    invoke_signed(
+        &stake::instruction::withdraw(
+            stake_account.key,
+            vault_authority.key,
+            to.key,
+            amount,
+            None,
+        ),
+        &[
+            stake_account.clone(),
+            to.clone(),
+            clock.clone(),
+            stake_history.clone(),
+            vault_authority.clone(),
+            stake_program.clone(),
+        ],
+        vault_seeds,
+    )?;
+    
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::errors::SpaceMoneyError;
+use crate::states::{InsuranceFund, TokenType};
+
+// SOL the vault holds above its rent-exempt minimum; no payout touches the rent
+pub fn vault_sol_balance(platform_info: &AccountInfo) -> Result<u64> {
+    let rent = Rent::get()?.minimum_balance(platform_info.data_len());
+    Ok(platform_info.lamports().saturating_sub(rent))
+}
+
+// Tops the vault up from the insurance fund by what `available` falls short
+// of `amount`, and returns that shortfall. The fund is only needed when
+// there is one.
+pub fn backstop_sol<'info>(
+    platform_info: &AccountInfo<'info>,
+    insurance_fund: Option<&mut Account<'info, InsuranceFund>>,
+    available: u64,
+    amount: u64,
+) -> Result<u64> {
+    let shortfall = amount.saturating_sub(available);
+    if shortfall == 0 {
+        return Ok(0);
+    }
+    
+    let insurance_fund = insurance_fund.ok_or(SpaceMoneyError::InsuranceFundRequired)?;
+    insurance_fund.backstop(TokenType::SOL, shortfall)?;
+    **insurance_fund.to_account_info().try_borrow_mut_lamports()? -= shortfall;
+    **platform_info.try_borrow_mut_lamports()? += shortfall;
+    Ok(shortfall)
+}
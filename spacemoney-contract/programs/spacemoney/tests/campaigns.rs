@@ -0,0 +1,105 @@
+mod common;
+
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signer,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{Campaign, PlatformState, TierConfig, TokenType, UserAccount};
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+// Boot deposits only
+fn create_campaign_ix(
+    admin: Pubkey,
+    id: u64,
+    start_time: i64,
+    end_time: i64,
+    boost_bps: u16,
+    budget_sol: u64,
+) -> Instruction {
+    instructions::create_campaign(&admin, id, start_time, end_time, 0b001, boost_bps, budget_sol, 0)
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8, campaign: Option<u64>) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, campaign, false)
+}
+
+#[tokio::test]
+async fn campaigns_boost_eligible_deposits_until_their_budget_runs_out() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let user_account = pda::user_account(&admin);
+    let stranger = funded_keypair(&mut context, LAMPORTS_PER_SOL).await;
+
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let week = now + 7 * SECONDS_PER_DAY;
+
+    // Only the admin creates campaigns, and only sensible ones
+    let result = send(&mut context, &[create_campaign_ix(stranger.pubkey(), 0, now, week, 10_000, LAMPORTS_PER_SOL)], &stranger).await;
+    assert_error(result, SpaceMoneyError::Unauthorized);
+    let result = send(&mut context, &[create_campaign_ix(admin, 0, week, now, 10_000, LAMPORTS_PER_SOL)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidCampaignWindow);
+    let result = send(&mut context, &[create_campaign_ix(admin, 0, now, week, 10_001, LAMPORTS_PER_SOL)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidCampaignBoost);
+    let result = send(&mut context, &[create_campaign_ix(admin, 0, now, week, 10_000, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::EmptyCampaign);
+
+    // 2x on Boot deposits, with room for one 10 SOL stake's boost
+    send(&mut context, &[create_campaign_ix(admin, 0, now, week, 10_000, 4 * LAMPORTS_PER_SOL)], &payer).await.unwrap();
+    let platform: PlatformState = fetch(&mut context, pda::platform_state()).await;
+    assert_eq!(platform.campaign_count, 1);
+
+    let result = send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 1, Some(0))], &payer).await;
+    assert_error(result, SpaceMoneyError::CampaignNotEligible);
+
+    let amount = 10 * LAMPORTS_PER_SOL;
+    send(&mut context, &[deposit_ix(admin, amount, 0, Some(0))], &payer).await.unwrap();
+
+    // The boost doubles the first lock's rewards, and the budget reserves it
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    let tiers: TierConfig = fetch(&mut context, pda::tier_config()).await;
+    let stake = account.stakes[0];
+    assert_eq!(stake.campaign_boost_bps, 10_000);
+    let (net_amount, _) = spacemoney_math::calculate_net_deposit(amount).unwrap();
+    let base = spacemoney_math::calculate_rewards(net_amount, BOOT_MULTIPLIER, BOOT_LOCK_DAYS).unwrap();
+    assert_eq!(stake.terms(&tiers.tiers[0]).max_rewards().unwrap(), 2 * base);
+
+    let campaign: Campaign = fetch(&mut context, pda::campaign(0)).await;
+    assert_eq!((campaign.spent_sol, campaign.boosted_deposits), (base, 1));
+    assert_eq!(campaign.spent(TokenType::SOL), base);
+
+    // The next boost doesn't fit; the deposit goes through unboosted and,
+    // with no other budget left, the campaign ends
+    send(&mut context, &[deposit_ix(admin, amount, 0, Some(0))], &payer).await.unwrap();
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.stakes[1].campaign_boost_bps, 0);
+    let campaign: Campaign = fetch(&mut context, pda::campaign(0)).await;
+    assert!(campaign.sol_exhausted);
+    assert_ne!(campaign.ended_at, 0);
+    assert_eq!((campaign.spent_sol, campaign.boosted_deposits), (base, 1));
+
+    let result = send(&mut context, &[deposit_ix(admin, amount, 0, Some(0))], &payer).await;
+    assert_error(result, SpaceMoneyError::CampaignNotActive);
+    let result = send(&mut context, &[instructions::end_campaign(&admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::CampaignEnded);
+
+    // Boosted and unboosted stakes can't be merged
+    let merge_ix = instructions::merge_stakes(&admin, 0, vec![1]);
+    let result = send(&mut context, &[merge_ix], &payer).await;
+    assert_error(result, SpaceMoneyError::IncompatibleStakes);
+
+    // Before its end time only the admin can end a campaign; after it, anyone
+    send(&mut context, &[create_campaign_ix(admin, 1, now, week, 5_000, LAMPORTS_PER_SOL)], &payer).await.unwrap();
+    let result = send(&mut context, &[instructions::end_campaign(&stranger.pubkey(), 1)], &stranger).await;
+    assert_error(result, SpaceMoneyError::CampaignStillRunning);
+    set_clock(&mut context, week).await;
+    let result = send(&mut context, &[deposit_ix(admin, amount, 0, Some(1))], &payer).await;
+    assert_error(result, SpaceMoneyError::CampaignNotActive);
+    send(&mut context, &[instructions::end_campaign(&stranger.pubkey(), 1)], &stranger).await.unwrap();
+    let campaign: Campaign = fetch(&mut context, pda::campaign(1)).await;
+    assert_eq!(campaign.ended_at, week);
+    assert_eq!(campaign.boosted_deposits, 0);
+}
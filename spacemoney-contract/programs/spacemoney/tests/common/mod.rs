@@ -0,0 +1,209 @@
+// Program-test harness shared by the integration tests. Each test binary
+// uses a different subset, hence the allow.
+#![allow(dead_code)]
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize};
+use anchor_spl::token::spl_token;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::AccountSharedData,
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{PlatformState, TierConfig, UserAccount};
+use spacemoney_client::instructions::StakeAuthority;
+use spacemoney_client::{decode_return_data, instructions, pda};
+
+// Anchor's entrypoint ties the account slice to the AccountInfo lifetime,
+// which processor! can't express, so the slice is leaked for the test
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    spacemoney::entry(program_id, accounts, data)
+}
+
+pub fn program_test() -> ProgramTest {
+    ProgramTest::new("spacemoney", spacemoney::id(), processor!(process_instruction))
+}
+
+pub async fn start() -> ProgramTestContext {
+    program_test().start_with_context().await
+}
+
+// Starts a fresh program initialized by the payer, who is returned as admin
+pub async fn start_initialized(usdt_mint: Pubkey) -> (ProgramTestContext, Keypair) {
+    let mut context = start().await;
+    let admin = context.payer.insecure_clone();
+    send(&mut context, &[instructions::initialize(&admin.pubkey(), &usdt_mint)], &admin)
+        .await
+        .unwrap();
+    (context, admin)
+}
+
+// Like `start_initialized`, with a real USDT mint and the insurance fund
+pub async fn start_insured() -> (ProgramTestContext, Keypair) {
+    let mut context = start().await;
+    let admin = context.payer.insecure_clone();
+    let usdt_mint = create_mint(&mut context, &admin.pubkey()).await;
+    let initialize_ix = instructions::initialize(&admin.pubkey(), &usdt_mint);
+    let insurance_ix = instructions::initialize_insurance_fund(&admin.pubkey(), &usdt_mint);
+    send(&mut context, &[initialize_ix, insurance_ix], &admin).await.unwrap();
+    (context, admin)
+}
+
+pub async fn send(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signer: &Keypair,
+) -> Result<(), BanksClientError> {
+    send_signed(context, instructions, signer, &[]).await
+}
+
+// Like `send`, with extra signers next to the fee payer
+pub async fn send_signed(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
+
+// Runs a view instruction without committing it and decodes what it returned
+pub async fn view<T: AnchorDeserialize>(context: &mut ProgramTestContext, instruction: Instruction) -> T {
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    let simulation = context.banks_client.simulate_transaction(tx).await.unwrap();
+    simulation.result.unwrap().unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    decode_return_data(&return_data.program_id, &return_data.data).unwrap()
+}
+
+pub fn assert_error(result: Result<(), BanksClientError>, error: SpaceMoneyError) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(0, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(error))
+        }
+        other => panic!("unexpected error {other:?}"),
+    }
+}
+
+// Creates a keypair holding `lamports`, paid for by the context payer
+pub async fn funded_keypair(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    let payer = context.payer.insecure_clone();
+    let fund_ix = system_instruction::transfer(&payer.pubkey(), &keypair.pubkey(), lamports);
+    send(context, &[fund_ix], &payer).await.unwrap();
+    keypair
+}
+
+// Creates a 6-decimal mint with `authority`, paid for by the context payer
+pub async fn create_mint(context: &mut ProgramTestContext, authority: &Pubkey) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.insecure_clone();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_ix = system_instruction::create_account(
+        &payer.pubkey(),
+        &mint.pubkey(),
+        rent.minimum_balance(spl_token::state::Mint::LEN),
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::id(),
+    );
+    let initialize_ix =
+        spl_token::instruction::initialize_mint(&spl_token::id(), &mint.pubkey(), authority, None, 6).unwrap();
+    send_signed(context, &[create_ix, initialize_ix], &payer, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+// Creates a token account for `mint` owned by `owner`, paid for by the context payer
+pub async fn create_token_account(context: &mut ProgramTestContext, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    let account = Keypair::new();
+    let payer = context.payer.insecure_clone();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let create_ix = system_instruction::create_account(
+        &payer.pubkey(),
+        &account.pubkey(),
+        rent.minimum_balance(spl_token::state::Account::LEN),
+        spl_token::state::Account::LEN as u64,
+        &spl_token::id(),
+    );
+    let initialize_ix =
+        spl_token::instruction::initialize_account3(&spl_token::id(), &account.pubkey(), mint, owner).unwrap();
+    send_signed(context, &[create_ix, initialize_ix], &payer, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+// Mints `amount` to a token account, with the context payer as mint authority
+pub async fn mint_tokens(context: &mut ProgramTestContext, mint: &Pubkey, account: &Pubkey, amount: u64) {
+    let payer = context.payer.insecure_clone();
+    let mint_ix =
+        spl_token::instruction::mint_to(&spl_token::id(), mint, account, &payer.pubkey(), &[], amount).unwrap();
+    send(context, &[mint_ix], &payer).await.unwrap();
+}
+
+pub fn depositor(user: &Keypair) -> StakeAuthority {
+    StakeAuthority::Depositor(user.pubkey())
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+pub async fn fetch<T: AccountDeserialize>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn fetch_platform_state(context: &mut ProgramTestContext) -> PlatformState {
+    fetch(context, pda::platform_state()).await
+}
+
+pub async fn fetch_tier_config(context: &mut ProgramTestContext) -> TierConfig {
+    fetch(context, pda::tier_config()).await
+}
+
+pub async fn fetch_user_account(context: &mut ProgramTestContext, user: Pubkey) -> UserAccount {
+    fetch(context, pda::user_account(&user)).await
+}
+
+pub async fn lamports(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    context.banks_client.get_balance(address).await.unwrap()
+}
+
+pub async fn set_lamports(context: &mut ProgramTestContext, address: Pubkey, lamports: u64) {
+    let mut account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    account.lamports = lamports;
+    context.set_account(&address, &AccountSharedData::from(account));
+}
+
+pub async fn set_clock(context: &mut ProgramTestContext, timestamp: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp = timestamp;
+    context.set_sysvar(&clock);
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer,
+};
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{ClaimedBitmap, Distributor, PlatformState, TokenType};
+use spacemoney::utils::{bonus_leaf, merkle_parent};
+use spacemoney_client::{instructions, pda, BonusTree};
+
+use common::*;
+
+fn create_distributor_ix(
+    admin: Pubkey,
+    id: u64,
+    merkle_root: [u8; 32],
+    total_amount: u64,
+    max_claims: u32,
+) -> Instruction {
+    instructions::create_distributor(&admin, id, merkle_root, TokenType::SOL, total_amount, max_claims)
+}
+
+fn claim_bonus_ix(claimant: Pubkey, id: u64, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Instruction {
+    instructions::claim_bonus(&claimant, None, id, index, amount, proof)
+}
+
+#[tokio::test]
+async fn bonuses_are_claimed_once_with_a_valid_proof() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let platform_state = pda::platform_state();
+
+    // A deposit fee gives the treasury something to distribute
+    let amount = 100 * LAMPORTS_PER_SOL;
+    let deposit_ix = instructions::deposit_sol(&admin, amount, 0, None, None, false);
+    send(&mut context, &[deposit_ix], &payer).await.unwrap();
+    let (_, fee) = spacemoney_math::calculate_net_deposit(amount).unwrap();
+
+    let staker = funded_keypair(&mut context, LAMPORTS_PER_SOL).await;
+
+    // Two leaves: the staker's bonus, then the admin's
+    let claims = [(staker.pubkey(), fee / 2), (admin, fee / 4)];
+    let leaves: Vec<[u8; 32]> = claims
+        .iter()
+        .enumerate()
+        .map(|(index, (claimant, bonus))| bonus_leaf(index as u32, claimant, *bonus))
+        .collect();
+    let root = merkle_parent(&leaves[0], &leaves[1]);
+    let total = fee / 2 + fee / 4;
+
+    // Only the admin funds distributors, and only from the treasury
+    let result = send(&mut context, &[create_distributor_ix(staker.pubkey(), 0, root, total, 2)], &staker).await;
+    assert_error(result, SpaceMoneyError::Unauthorized);
+    let result = send(&mut context, &[create_distributor_ix(admin, 0, root, fee + 1, 2)], &payer).await;
+    assert_error(result, SpaceMoneyError::InsufficientTreasuryBalance);
+    let result = send(&mut context, &[create_distributor_ix(admin, 0, root, total, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidDistributor);
+
+    send(&mut context, &[create_distributor_ix(admin, 0, root, total, 2)], &payer).await.unwrap();
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    assert_eq!(platform.treasury_sol, fee - total);
+    assert_eq!(platform.distributor_count, 1);
+    let bitmap: ClaimedBitmap = fetch(&mut context, pda::claimed_bitmap(0)).await;
+    assert_eq!(bitmap.distributor, pda::distributor(0));
+    assert_eq!(bitmap.bits, vec![0]);
+
+    // The vault pays the staker's leaf
+    let vault_before = lamports(&mut context, platform_state).await;
+    send(&mut context, &[claim_bonus_ix(staker.pubkey(), 0, 0, fee / 2, vec![leaves[1]])], &staker).await.unwrap();
+    let vault_after = lamports(&mut context, platform_state).await;
+    assert_eq!(vault_before - vault_after, fee / 2);
+
+    let result = send(&mut context, &[claim_bonus_ix(staker.pubkey(), 0, 0, fee / 2, vec![leaves[1]])], &staker).await;
+    assert_error(result, SpaceMoneyError::BonusAlreadyClaimed);
+
+    // Proofs are bound to the claimant, the amount and the index
+    let result = send(&mut context, &[claim_bonus_ix(admin, 0, 1, fee / 2, vec![leaves[0]])], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidMerkleProof);
+    let result = send(&mut context, &[claim_bonus_ix(staker.pubkey(), 0, 1, fee / 4, vec![leaves[0]])], &staker).await;
+    assert_error(result, SpaceMoneyError::InvalidMerkleProof);
+    let result = send(&mut context, &[claim_bonus_ix(admin, 0, 2, fee / 4, vec![leaves[0]])], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidClaimIndex);
+
+    send(&mut context, &[claim_bonus_ix(admin, 0, 1, fee / 4, vec![leaves[0]])], &payer).await.unwrap();
+    let distributor: Distributor = fetch(&mut context, pda::distributor(0)).await;
+    assert_eq!((distributor.claimed_amount, distributor.claimed_count), (total, 2));
+    assert_eq!(distributor.remaining(), 0);
+    let bitmap: ClaimedBitmap = fetch(&mut context, pda::claimed_bitmap(0)).await;
+    assert_eq!(bitmap.bits, vec![0b11]);
+}
+
+#[tokio::test]
+async fn closing_a_distributor_returns_unclaimed_bonuses() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let platform_state = pda::platform_state();
+
+    let amount = 100 * LAMPORTS_PER_SOL;
+    let deposit_ix = instructions::deposit_sol(&admin, amount, 0, None, None, false);
+    send(&mut context, &[deposit_ix], &payer).await.unwrap();
+    let (_, fee) = spacemoney_math::calculate_net_deposit(amount).unwrap();
+
+    // Nine claims, so the admin's at indexes 7 and 8 sit either side of a bitmap byte
+    let staker = funded_keypair(&mut context, LAMPORTS_PER_SOL).await;
+    let bonus = fee / 10;
+    let claims: Vec<(Pubkey, u64)> = (0..9)
+        .map(|index| match index {
+            0 => (staker.pubkey(), bonus),
+            7 | 8 => (admin, bonus),
+            _ => (Pubkey::new_unique(), bonus),
+        })
+        .collect();
+    let tree = BonusTree::new(&claims);
+    let total = 9 * bonus;
+    send(&mut context, &[create_distributor_ix(admin, 0, tree.root(), total, 9)], &payer).await.unwrap();
+    let bitmap: ClaimedBitmap = fetch(&mut context, pda::claimed_bitmap(0)).await;
+    assert_eq!(bitmap.bits, vec![0, 0]);
+
+    for index in [7, 8] {
+        let claim_ix = claim_bonus_ix(admin, 0, index, bonus, tree.proof(index as usize));
+        send(&mut context, &[claim_ix], &payer).await.unwrap();
+    }
+    let bitmap: ClaimedBitmap = fetch(&mut context, pda::claimed_bitmap(0)).await;
+    assert_eq!(bitmap.bits, vec![0b1000_0000, 0b1]);
+    for index in [7, 8] {
+        let claim_ix = claim_bonus_ix(admin, 0, index, bonus, tree.proof(index as usize));
+        let result = send(&mut context, &[claim_ix], &payer).await;
+        assert_error(result, SpaceMoneyError::BonusAlreadyClaimed);
+    }
+
+    // Only the admin closes it, and the seven unclaimed bonuses go back to the treasury
+    let close_ix = instructions::close_distributor(&staker.pubkey(), 0);
+    let result = send(&mut context, &[close_ix], &staker).await;
+    assert_error(result, SpaceMoneyError::Unauthorized);
+
+    let rent = lamports(&mut context, pda::distributor(0)).await
+        + lamports(&mut context, pda::claimed_bitmap(0)).await;
+    let admin_before = lamports(&mut context, admin).await;
+    send(&mut context, &[instructions::close_distributor(&admin, 0)], &payer).await.unwrap();
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    assert_eq!(platform.treasury_sol, fee - 2 * bonus);
+    assert_eq!(lamports(&mut context, admin).await, admin_before + rent - 5_000);
+    for address in [pda::distributor(0), pda::claimed_bitmap(0)] {
+        assert!(context.banks_client.get_account(address).await.unwrap().is_none());
+    }
+
+    // Its leaves can't be claimed any more
+    let claim_ix = claim_bonus_ix(staker.pubkey(), 0, 0, bonus, tree.proof(0));
+    assert!(send(&mut context, &[claim_ix], &staker).await.is_err());
+}
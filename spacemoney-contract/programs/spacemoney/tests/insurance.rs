@@ -0,0 +1,207 @@
+mod common;
+
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    clock::Clock,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::instructions::ClaimableView;
+use spacemoney::states::{InsuranceFund, PlatformState};
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::pda;
+
+use common::*;
+
+async fn fetch_insurance_fund(context: &mut ProgramTestContext) -> InsuranceFund {
+    fetch(context, pda::insurance_fund()).await
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, insurance: bool) -> Instruction {
+    instructions::deposit_sol(&user, amount, 0, None, None, insurance)
+}
+
+fn force_withdraw_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::force_withdraw(&StakeAuthority::Depositor(user), None, stake_index, true)
+}
+
+fn withdraw_ix(user: Pubkey, stake_index: u64, insurance: bool) -> Instruction {
+    instructions::withdraw_sol(&StakeAuthority::Depositor(user), stake_index, insurance)
+}
+
+#[tokio::test]
+async fn fees_and_penalties_fund_a_backstop_for_short_withdrawals() {
+    let mut context = start().await;
+    let payer = context.payer.insecure_clone();
+    let admin = payer.pubkey();
+    let staker = Keypair::new();
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    // The fund's USDT vault needs a real mint
+    let usdt_mint = create_mint(&mut context, &admin).await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let initialize_ix = instructions::initialize(&admin, &usdt_mint);
+    let fund_staker_ix = system_instruction::transfer(&admin, &staker.pubkey(), 20 * LAMPORTS_PER_SOL);
+    send(&mut context, &[initialize_ix, fund_staker_ix], &payer).await.unwrap();
+
+    // Only the admin creates the fund and sets its share, which can't pass 100%
+    let result = send(
+        &mut context,
+        &[instructions::initialize_insurance_fund(&staker.pubkey(), &usdt_mint)],
+        &staker,
+    )
+    .await;
+    assert_error(result, SpaceMoneyError::Unauthorized);
+    send(&mut context, &[instructions::initialize_insurance_fund(&admin, &usdt_mint)], &payer)
+        .await
+        .unwrap();
+    let result = send(&mut context, &[instructions::set_insurance_share(&admin, 10_001)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidInsuranceShare);
+    send(&mut context, &[instructions::set_insurance_share(&admin, 10_000)], &payer).await.unwrap();
+    assert_eq!(fetch_platform_state(&mut context).await.insurance_share_bps, 10_000);
+
+    // With a share set, deposits must pass the fund
+    let result = send(&mut context, &[deposit_ix(staker.pubkey(), 10 * LAMPORTS_PER_SOL, false)], &staker).await;
+    assert_error(result, SpaceMoneyError::InsuranceFundRequired);
+
+    // The whole deposit fee goes to the fund rather than the treasury
+    let fund_lamports = lamports(&mut context, pda::insurance_fund()).await;
+    send(&mut context, &[deposit_ix(staker.pubkey(), 10 * LAMPORTS_PER_SOL, true)], &staker)
+        .await
+        .unwrap();
+    let fee = spacemoney_math::calculate_deposit_fee(10 * LAMPORTS_PER_SOL).unwrap();
+    let fund = fetch_insurance_fund(&mut context).await;
+    assert_eq!(fund.balance_sol, fee);
+    assert_eq!(lamports(&mut context, pda::insurance_fund()).await, fund_lamports + fee);
+    assert_eq!(fetch_platform_state(&mut context).await.treasury_sol, 0);
+
+    // So does an early exit's penalty
+    send(&mut context, &[deposit_ix(admin, 400 * LAMPORTS_PER_SOL, true)], &payer).await.unwrap();
+    set_clock(&mut context, now + SECONDS_PER_DAY).await;
+    let before = fetch_insurance_fund(&mut context).await;
+    send(&mut context, &[force_withdraw_ix(admin, 0)], &payer).await.unwrap();
+    let after = fetch_insurance_fund(&mut context).await;
+    assert!(after.contributed_sol > before.contributed_sol);
+    assert_eq!(after.balance_sol - before.balance_sol, after.contributed_sol - before.contributed_sol);
+    assert_eq!(fetch_platform_state(&mut context).await.treasury_sol, 0);
+
+    // Nothing funded the staker's rewards, so the vault alone can't pay them out
+    // and the withdrawal has to pass the fund
+    let lock_seconds = fetch_tier_config(&mut context).await.tiers[0].lock_seconds;
+    set_clock(&mut context, now + lock_seconds + SECONDS_PER_DAY).await;
+    let result = send(&mut context, &[withdraw_ix(staker.pubkey(), 0, false)], &staker).await;
+    assert_error(result, SpaceMoneyError::InsuranceFundRequired);
+
+    // The fund tops the vault up to exactly what's owed, leaving it its rent
+    let fund_lamports = lamports(&mut context, pda::insurance_fund()).await;
+    send(&mut context, &[withdraw_ix(staker.pubkey(), 0, true)], &staker).await.unwrap();
+    let fund = fetch_insurance_fund(&mut context).await;
+    let backstopped = fund.backstopped_sol;
+    assert!(backstopped > 0);
+    assert_eq!(fund.backstop_count, 1);
+    assert_eq!(fund.balance_sol, after.balance_sol - backstopped);
+    assert_eq!(lamports(&mut context, pda::insurance_fund()).await, fund_lamports - backstopped);
+    let platform = context.banks_client.get_account(pda::platform_state()).await.unwrap().unwrap();
+    assert_eq!(platform.lamports, rent.minimum_balance(platform.data.len()));
+}
+
+#[tokio::test]
+async fn shortfalls_beyond_the_fund_fail_the_withdrawal() {
+    let (mut context, payer) = start_insured().await;
+    let admin = payer.pubkey();
+    let staker = funded_keypair(&mut context, 20 * LAMPORTS_PER_SOL).await;
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    // The fund holds the staker's deposit fee, and nothing funded their rewards
+    send(&mut context, &[instructions::set_insurance_share(&admin, 10_000)], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(staker.pubkey(), 10 * LAMPORTS_PER_SOL, true)], &staker)
+        .await
+        .unwrap();
+    let lock_seconds = fetch_tier_config(&mut context).await.tiers[0].lock_seconds;
+    set_clock(&mut context, now + lock_seconds + SECONDS_PER_DAY).await;
+
+    // The vault is short by more than the fund holds
+    let claimable: ClaimableView = view(&mut context, instructions::get_claimable(&staker.pubkey(), 0)).await;
+    let owed = claimable.withdrawable;
+    let platform = context.banks_client.get_account(pda::platform_state()).await.unwrap().unwrap();
+    let vault = platform.lamports - Rent::default().minimum_balance(platform.data.len());
+    let before = fetch_insurance_fund(&mut context).await;
+    assert!(owed - vault > before.balance_sol);
+    let fund_lamports = lamports(&mut context, pda::insurance_fund()).await;
+
+    // The fund can't cover the rest, so nothing moves
+    let result = send(&mut context, &[withdraw_ix(staker.pubkey(), 0, true)], &staker).await;
+    assert_error(result, SpaceMoneyError::InsufficientTreasuryBalance);
+    let after = fetch_insurance_fund(&mut context).await;
+    assert_eq!((after.balance_sol, after.backstop_count), (before.balance_sol, 0));
+    assert_eq!(lamports(&mut context, pda::insurance_fund()).await, fund_lamports);
+
+    // Once rewards are funded the vault pays alone, without the fund
+    let fund_ix = instructions::fund_rewards(&admin, None, 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+    send(&mut context, &[withdraw_ix(staker.pubkey(), 0, false)], &staker).await.unwrap();
+    assert_eq!(fetch_insurance_fund(&mut context).await.backstop_count, 0);
+}
+
+#[tokio::test]
+async fn sol_payouts_draw_on_the_fund_and_leave_the_vault_its_rent() {
+    let (mut context, payer) = start_insured().await;
+    let admin = payer.pubkey();
+    let staker = funded_keypair(&mut context, 20 * LAMPORTS_PER_SOL).await;
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let rent = context.banks_client.get_rent().await.unwrap().minimum_balance(PlatformState::LEN);
+
+    // The staker's deposit fee goes to the fund
+    send(&mut context, &[instructions::set_insurance_share(&admin, 10_000)], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(staker.pubkey(), 10 * LAMPORTS_PER_SOL, true)], &staker)
+        .await
+        .unwrap();
+    let (principal, _) = spacemoney_math::calculate_net_deposit(10 * LAMPORTS_PER_SOL).unwrap();
+    let daily = spacemoney_math::calculate_daily_reward(principal, BOOT_MULTIPLIER).unwrap();
+
+    // With most of the vault delegated, a day's rewards are more than it holds above its rent
+    set_clock(&mut context, now + SECONDS_PER_DAY).await;
+    set_lamports(&mut context, pda::platform_state(), rent + daily / 2).await;
+    let authority = StakeAuthority::Depositor(staker.pubkey());
+    let result = send(&mut context, &[instructions::claim_rewards(&authority, None, 0, false)], &staker).await;
+    assert_error(result, SpaceMoneyError::InsuranceFundRequired);
+
+    // The fund covers the rest, and the vault is left with exactly its rent
+    let before = fetch_insurance_fund(&mut context).await;
+    send(&mut context, &[instructions::claim_rewards(&authority, None, 0, true)], &staker).await.unwrap();
+    let after = fetch_insurance_fund(&mut context).await;
+    assert_eq!(after.backstopped_sol, daily - daily / 2);
+    assert_eq!(after.balance_sol, before.balance_sol - (daily - daily / 2));
+    assert_eq!(lamports(&mut context, pda::platform_state()).await, rent);
+
+    // A force withdrawal pays the fund its share of the penalty from the vault
+    // too, so the fund tops up that part when the vault only holds the payout
+    send(&mut context, &[deposit_ix(staker.pubkey(), 5 * LAMPORTS_PER_SOL, true)], &staker)
+        .await
+        .unwrap();
+    set_clock(&mut context, now + 2 * SECONDS_PER_DAY).await;
+    let claimable: ClaimableView = view(&mut context, instructions::get_claimable(&staker.pubkey(), 1)).await;
+    set_lamports(&mut context, pda::platform_state(), rent + claimable.withdrawable).await;
+    let before = fetch_insurance_fund(&mut context).await;
+    send(&mut context, &[force_withdraw_ix(staker.pubkey(), 1)], &staker).await.unwrap();
+    let after = fetch_insurance_fund(&mut context).await;
+    let share = after.contributed_sol - before.contributed_sol;
+    assert!(share > 0);
+    assert_eq!(after.backstopped_sol - before.backstopped_sol, share);
+    assert_eq!(lamports(&mut context, pda::platform_state()).await, rent);
+
+    // Treasury payouts can't reach into the rent either
+    send(&mut context, &[instructions::set_insurance_share(&admin, 0)], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(admin, LAMPORTS_PER_SOL, false)], &payer).await.unwrap();
+    let treasury = fetch_platform_state(&mut context).await.treasury_sol;
+    set_lamports(&mut context, pda::platform_state(), rent + treasury - 1).await;
+    let transfer_ix = instructions::admin_transfer_sol(&admin, &admin, treasury);
+    let result = send(&mut context, &[transfer_ix], &payer).await;
+    assert_error(result, SpaceMoneyError::InsufficientTreasuryBalance);
+}
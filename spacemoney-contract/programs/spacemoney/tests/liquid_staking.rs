@@ -0,0 +1,46 @@
+mod common;
+
+use solana_sdk::{clock::Clock, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent, signature::Signer};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::PlatformState;
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+#[tokio::test]
+async fn spsol_redeems_only_from_sol_owed_to_the_liquid_pool() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    send(&mut context, &[instructions::initialize_liquid_staking(&admin)], &payer).await.unwrap();
+    let spsol_account = create_token_account(&mut context, &pda::spsol_mint(), &admin).await;
+
+    // A locked stake and a liquid deposit share the vault
+    let locked_ix = instructions::deposit_sol(&admin, 10 * LAMPORTS_PER_SOL, 0, None, None, false);
+    let liquid_ix = instructions::deposit_sol_liquid(&admin, &spsol_account, 5 * LAMPORTS_PER_SOL, None, false);
+    send(&mut context, &[locked_ix, liquid_ix], &payer).await.unwrap();
+    let net_liquid = 5 * LAMPORTS_PER_SOL - spacemoney_math::calculate_deposit_fee(5 * LAMPORTS_PER_SOL).unwrap();
+    assert_eq!(token_balance(&mut context, spsol_account).await, net_liquid);
+
+    // Time alone doesn't raise the rate, so a month later spSOL still redeems 1:1
+    set_clock(&mut context, now + 30 * SECONDS_PER_DAY).await;
+    let balance = lamports(&mut context, admin).await;
+    let redeem_ix = instructions::redeem_spsol(&admin, &spsol_account, net_liquid / 5);
+    send(&mut context, &[redeem_ix], &payer).await.unwrap();
+    assert_eq!(lamports(&mut context, admin).await, balance + net_liquid / 5 - 5_000);
+    let platform = fetch_platform_state(&mut context).await;
+    assert_eq!(platform.liquid_staked_sol, net_liquid - net_liquid / 5);
+
+    // With the vault short, redemptions can't dip into locked stakes, the treasury or rent
+    let reserved = Rent::default().minimum_balance(PlatformState::LEN) + platform.total_staked_sol + platform.treasury_sol;
+    set_lamports(&mut context, pda::platform_state(), reserved + LAMPORTS_PER_SOL).await;
+    let redeem_ix = instructions::redeem_spsol(&admin, &spsol_account, 2 * LAMPORTS_PER_SOL);
+    let result = send(&mut context, &[redeem_ix], &payer).await;
+    assert_error(result, SpaceMoneyError::InsufficientTreasuryBalance);
+
+    let redeem_ix = instructions::redeem_spsol(&admin, &spsol_account, LAMPORTS_PER_SOL);
+    send(&mut context, &[redeem_ix], &payer).await.unwrap();
+    assert_eq!(lamports(&mut context, pda::platform_state()).await, reserved);
+    assert_eq!(token_balance(&mut context, spsol_account).await, net_liquid - net_liquid / 5 - LAMPORTS_PER_SOL);
+}
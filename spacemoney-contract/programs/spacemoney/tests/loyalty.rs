@@ -0,0 +1,126 @@
+mod common;
+
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signer,
+};
+use spacemoney::constants::*;
+use spacemoney::instructions::LoyaltyView;
+use spacemoney::states::{TierConfig, UserAccount};
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::pda;
+
+use common::*;
+
+async fn loyalty_view(context: &mut ProgramTestContext, user: Pubkey) -> LoyaltyView {
+    view(context, instructions::get_loyalty(&user)).await
+}
+
+fn withdraw_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::withdraw_sol(&StakeAuthority::Depositor(user), stake_index, false)
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn closed_stakes_build_a_loyalty_bonus_for_new_ones() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let user = payer.pubkey();
+    let user_account = pda::user_account(&user);
+    let fund_ix = instructions::fund_rewards(&user, None, 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // 1 SOL less the deposit fee earns 0.98 stake-days a day
+    let start = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let amount = LAMPORTS_PER_SOL;
+    send(&mut context, &[deposit_ix(user, amount, 0)], &payer).await.unwrap();
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.stakes[0].loyalty_bonus_bps, 0);
+
+    // Days still running on active stakes show up but don't count yet
+    set_clock(&mut context, start + 40 * SECONDS_PER_DAY).await;
+    let view = loyalty_view(&mut context, user).await;
+    assert_eq!((view.stake_days, view.pending_stake_days, view.level), (0, 39, 0));
+    assert_eq!(view.next_level_stake_days, 30);
+
+    // Withdrawing the Boot stake after 100 days reaches level 2
+    set_clock(&mut context, start + 100 * SECONDS_PER_DAY).await;
+    send(&mut context, &[withdraw_ix(user, 0)], &payer).await.unwrap();
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.loyalty_stake_days, 98);
+
+    let view = loyalty_view(&mut context, user).await;
+    assert_eq!(view.user, user);
+    assert_eq!((view.stake_days, view.pending_stake_days), (98, 0));
+    assert_eq!((view.level, view.bonus_bps), (2, 500));
+    assert_eq!(view.next_level_stake_days, 82);
+
+    // The next stake earns 5% more, for its whole lock
+    send(&mut context, &[deposit_ix(user, amount, 0)], &payer).await.unwrap();
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.stakes[1].loyalty_bonus_bps, 500);
+    let tiers: TierConfig = fetch(&mut context, pda::tier_config()).await;
+    let mut unboosted = account.stakes[1];
+    unboosted.loyalty_bonus_bps = 0;
+    let base = unboosted.terms(&tiers.tiers[0]).max_rewards().unwrap();
+    let boosted = account.stakes[1].terms(&tiers.tiers[0]).max_rewards().unwrap();
+    assert_eq!(boosted, base + base * 500 / 10_000);
+}
+
+#[tokio::test]
+async fn splitting_a_stake_keeps_its_loyalty_credit() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let user = payer.pubkey();
+    let user_account = pda::user_account(&user);
+    let fund_ix = instructions::fund_rewards(&user, None, 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // 10 SOL less the fee, split in half
+    let start = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    send(&mut context, &[deposit_ix(user, 10 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+    let net = fetch::<UserAccount>(&mut context, user_account).await.stakes[0].amount;
+    let split_ix = instructions::split_stake(&user, 0, net / 2);
+    send(&mut context, &[split_ix], &payer).await.unwrap();
+    let view = loyalty_view(&mut context, user).await;
+    assert_eq!(view.pending_stake_days, 0);
+
+    // Both halves closed at day 100 add what the whole stake would have
+    set_clock(&mut context, start + 100 * SECONDS_PER_DAY).await;
+    let view = loyalty_view(&mut context, user).await;
+    assert_eq!(view.pending_stake_days, 980);
+    send(&mut context, &[withdraw_ix(user, 0), withdraw_ix(user, 1)], &payer).await.unwrap();
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.loyalty_stake_days, 980);
+    assert_eq!(loyalty_view(&mut context, user).await.level, 4);
+}
+
+#[tokio::test]
+async fn merging_matured_stakes_keeps_each_ones_loyalty_credit() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let user = payer.pubkey();
+    let user_account = pda::user_account(&user);
+    let fund_ix = instructions::fund_rewards(&user, None, 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // An old 1 SOL stake and a 10 SOL one made 100 days later
+    let start = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    send(&mut context, &[deposit_ix(user, LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+    set_clock(&mut context, start + 100 * SECONDS_PER_DAY).await;
+    send(&mut context, &[deposit_ix(user, 10 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+
+    // Once both have matured, the new stake merges into the old one; the
+    // merged stake-days round down once instead of per stake
+    set_clock(&mut context, start + 131 * SECONDS_PER_DAY).await;
+    let pending = loyalty_view(&mut context, user).await.pending_stake_days;
+    let merge_ix = instructions::merge_stakes(&user, 0, vec![1]);
+    send(&mut context, &[merge_ix], &payer).await.unwrap();
+    assert_eq!(loyalty_view(&mut context, user).await.pending_stake_days, pending + 1);
+
+    // 0.98 SOL held 131 days and 9.8 SOL held 31, not 10.78 SOL for 131 days
+    send(&mut context, &[withdraw_ix(user, 0)], &payer).await.unwrap();
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.loyalty_stake_days, 432);
+}
@@ -0,0 +1,407 @@
+mod common;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use solana_sdk::{
+    account::Account, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent, signature::Signer,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{PlatformState, PostLock, TierConfig, TokenType, UserAccount};
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+// Accounts written by the original program: platform state and tier config
+// at their exact size, and a user account allocated for 10 stakes holding one
+// active SOL stake and one closed USDT stake
+const PLATFORM_STATE_V0: &[u8] = include_bytes!("fixtures/platform_state_v0.bin");
+const TIER_CONFIG_V0: &[u8] = include_bytes!("fixtures/tier_config_v0.bin");
+const USER_ACCOUNT_V0: &[u8] = include_bytes!("fixtures/user_account_v0.bin");
+// The same platform state once stake transfers appended their toggle, enabled
+const PLATFORM_STATE_V0_TRANSFERS: &[u8] = include_bytes!("fixtures/platform_state_v0_transfers.bin");
+// The same platform state and user account just before accounts were
+// versioned, with transfers enabled and a receipt NFT on the SOL stake
+const PLATFORM_STATE_V0_LIQUID: &[u8] = include_bytes!("fixtures/platform_state_v0_liquid.bin");
+const USER_ACCOUNT_V0_RECEIPTS: &[u8] = include_bytes!("fixtures/user_account_v0_receipts.bin");
+// A version 1 tier config with the fixed per-tier fields, Boot moved to 2 SOL,
+// 2x and 45 days
+const TIER_CONFIG_V1: &[u8] = include_bytes!("fixtures/tier_config_v1.bin");
+// A version 2 tier list with a disabled fourth Campaign tier capped at 100
+// stakes, 7 of them active, and 4 active Boot stakes
+const TIER_CONFIG_V2: &[u8] = include_bytes!("fixtures/tier_config_v2.bin");
+// The version 0 user account after its migration to version 1, with stakes
+// that don't record a post-lock policy yet
+const USER_ACCOUNT_V1: &[u8] = include_bytes!("fixtures/user_account_v1.bin");
+// That account again at version 2, allocated for 10 stakes, with its closed
+// USDT stake made under a 25% flexible post-lock policy
+const USER_ACCOUNT_V2: &[u8] = include_bytes!("fixtures/user_account_v2.bin");
+
+fn fixture_account(data: &[u8]) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data: data.to_vec(),
+        owner: spacemoney::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn migrates_legacy_accounts_in_place() {
+    let user = Pubkey::new_from_array([9; 32]);
+    let platform_state = pda::platform_state();
+    let tier_config = pda::tier_config();
+    let user_account = pda::user_account(&user);
+
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0));
+    program_test.add_account(tier_config, fixture_account(TIER_CONFIG_V0));
+    program_test.add_account(user_account, fixture_account(USER_ACCOUNT_V0));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    // The old layouts don't decode as the current accounts
+    let raw = context.banks_client.get_account(platform_state).await.unwrap().unwrap();
+    assert!(PlatformState::try_deserialize(&mut raw.data.as_slice()).is_err());
+
+    let migrate_ixs = [
+        instructions::migrate_account(&payer.pubkey(), &platform_state),
+        instructions::migrate_account(&payer.pubkey(), &tier_config),
+        instructions::migrate_account(&payer.pubkey(), &user_account),
+    ];
+    send(&mut context, &migrate_ixs, &payer).await.unwrap();
+
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    assert_eq!(platform.version, PlatformState::VERSION);
+    assert_eq!(platform.admin, Pubkey::new_from_array([7; 32]));
+    assert_eq!(platform.usdt_mint, Pubkey::new_from_array([8; 32]));
+    assert_eq!(platform.treasury_sol, 2_000_000_000);
+    assert_eq!(platform.total_staked_sol, 98_000_000_000);
+    assert_eq!(platform.total_staked_usdt, 490_000_000);
+    assert!(!platform.stake_transfers_enabled);
+    assert_eq!(platform.spsol_mint, Pubkey::default());
+    assert_eq!(platform.liquid_staked_sol, 0);
+    assert_eq!(platform.referral_share_bps, 0);
+    assert_eq!(platform.reserved, [0; PlatformState::RESERVED]);
+
+    let tiers: TierConfig = fetch(&mut context, tier_config).await;
+    assert_eq!(tiers.version, TierConfig::VERSION);
+    let names: Vec<&str> = tiers.tiers.iter().map(|tier| tier.name.as_str()).collect();
+    assert_eq!(names, ["Boot", "Symbiotic", "Space"]);
+    assert_eq!(tiers.tiers[1].min_stake, SYMBIOTIC_MIN_STAKE);
+    assert_eq!(tiers.tiers[2].multiplier, SPACE_MULTIPLIER);
+    assert_eq!(tiers.tiers[2].lock_seconds, SPACE_LOCK_DAYS * SECONDS_PER_DAY);
+    assert!(tiers.tiers.iter().all(|tier| tier.enabled && tier.max_stakes == 0));
+
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.version, UserAccount::VERSION);
+    assert_eq!(account.user, user);
+    assert_eq!(account.stakes.len(), 2);
+    assert_eq!(account.stakes[0].amount, 49_000_000_000);
+    assert_eq!(account.stakes[0].tier, 1);
+    assert_eq!(account.stakes[0].claimed_rewards, 1_000_000);
+    assert!(account.stakes[0].is_active);
+    assert!(account.stakes[0].post_lock == PostLock::None);
+    assert!(account.stakes[1].token_type == TokenType::USDT);
+    assert!(!account.stakes[1].is_active);
+    assert!(account.stakes.iter().all(|stake| stake.receipt_mint == Pubkey::default()));
+    assert_eq!(account.total_claimed_sol, 1_000_000);
+
+    // Stakes record the terms of their migrated tier
+    assert!(account.stakes.iter().all(|stake| stake.terms_fixed));
+    assert_eq!(account.stakes[0].multiplier as u64, SYMBIOTIC_MULTIPLIER);
+    assert_eq!(account.stakes[0].lock_days as i64, SYMBIOTIC_LOCK_DAYS);
+
+    // Platform state grew to its current size, the user account kept room for
+    // its 10 stakes, the tier config is sized for the full tier list, and all
+    // stay rent exempt
+    let sizes = [
+        (platform_state, PlatformState::LEN),
+        (tier_config, TierConfig::LEN),
+        (user_account, UserAccount::space_for_stakes(10)),
+    ];
+    for (address, len) in sizes {
+        let raw = context.banks_client.get_account(address).await.unwrap().unwrap();
+        assert_eq!(raw.data.len(), len);
+        assert!(raw.lamports >= Rent::default().minimum_balance(raw.data.len()));
+    }
+    assert_eq!(PLATFORM_STATE_V0.len(), 114);
+    assert_eq!(USER_ACCOUNT_V0.len(), 419);
+
+    // Migrating twice is rejected
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &user_account)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_platform_state_with_stake_transfer_toggle() {
+    let platform_state = pda::platform_state();
+
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0_TRANSFERS));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &platform_state)], &payer).await.unwrap();
+
+    // The toggle carries over, and liquid staking starts unset
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    assert_eq!(platform.version, PlatformState::VERSION);
+    assert_eq!(platform.admin, Pubkey::new_from_array([7; 32]));
+    assert_eq!(platform.total_staked_usdt, 490_000_000);
+    assert!(platform.stake_transfers_enabled);
+    assert_eq!(platform.spsol_mint, Pubkey::default());
+    assert_eq!(platform.liquid_last_accrual, 0);
+
+    let raw = context.banks_client.get_account(platform_state).await.unwrap().unwrap();
+    assert_eq!(raw.data.len(), PlatformState::LEN);
+    assert!(raw.lamports >= Rent::default().minimum_balance(raw.data.len()));
+
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &platform_state)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_accounts_with_fields_appended_before_versioning() {
+    let user = Pubkey::new_from_array([9; 32]);
+    let platform_state = pda::platform_state();
+    let user_account = pda::user_account(&user);
+
+    let tier_config = pda::tier_config();
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0_LIQUID));
+    program_test.add_account(tier_config, fixture_account(TIER_CONFIG_V0));
+    program_test.add_account(user_account, fixture_account(USER_ACCOUNT_V0_RECEIPTS));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let migrate_ixs = [
+        instructions::migrate_account(&payer.pubkey(), &platform_state),
+        instructions::migrate_account(&payer.pubkey(), &tier_config),
+        instructions::migrate_account(&payer.pubkey(), &user_account),
+    ];
+    send(&mut context, &migrate_ixs, &payer).await.unwrap();
+
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    assert_eq!(platform.version, PlatformState::VERSION);
+    assert_eq!(platform.total_staked_sol, 98_000_000_000);
+    assert!(platform.stake_transfers_enabled);
+    assert_eq!(platform.reserved, [0; PlatformState::RESERVED]);
+
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.version, UserAccount::VERSION);
+    assert_eq!(account.stakes.len(), 2);
+    assert_eq!(account.stakes[0].amount, 49_000_000_000);
+    assert_eq!(account.stakes[0].claimed_rewards, 1_000_000);
+    assert!(!account.stakes[1].is_active);
+    assert_eq!(account.total_claimed_sol, 1_000_000);
+
+    // The receipt keeps controlling its stake
+    assert_eq!(account.stakes[0].receipt_mint, Pubkey::new_from_array([5; 32]));
+    assert!(account.stakes[0].has_receipt());
+    assert!(!account.stakes[1].has_receipt());
+
+    // Both layouts had a fixed size, so they migrate to the same sizes as the baseline ones
+    for (address, len) in [(platform_state, PlatformState::LEN), (user_account, UserAccount::space_for_stakes(10))] {
+        let raw = context.banks_client.get_account(address).await.unwrap().unwrap();
+        assert_eq!(raw.data.len(), len);
+    }
+    assert_eq!(
+        PLATFORM_STATE_V0_LIQUID.len() + 1 + 2 * 8 + 2 + 8 + 8 + 2 + PlatformState::RESERVED,
+        PlatformState::LEN
+    );
+
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &platform_state)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_version_1_tier_config_to_tier_list() {
+    let platform_state = pda::platform_state();
+    let tier_config = pda::tier_config();
+
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0));
+    program_test.add_account(tier_config, fixture_account(TIER_CONFIG_V1));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &tier_config)], &payer).await.unwrap();
+
+    // The admin's changes carry over to the matching tier ids
+    let tiers: TierConfig = fetch(&mut context, tier_config).await;
+    assert_eq!(tiers.version, TierConfig::VERSION);
+    assert_eq!(tiers.bump, 255);
+    assert_eq!(tiers.tiers.len(), 3);
+    assert_eq!(tiers.tiers[0].name, "Boot");
+    assert_eq!(tiers.tiers[0].min_stake, 2_000_000_000);
+    assert_eq!(tiers.tiers[0].multiplier, 2);
+    assert_eq!(tiers.tiers[0].lock_seconds, 45 * SECONDS_PER_DAY);
+    assert_eq!(tiers.tiers[2].min_stake, SPACE_MIN_STAKE);
+
+    // A tier list is already current
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &tier_config)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_version_2_tier_list_to_tier_limits() {
+    let platform_state = pda::platform_state();
+    let tier_config = pda::tier_config();
+
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0));
+    program_test.add_account(tier_config, fixture_account(TIER_CONFIG_V2));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &tier_config)], &payer).await.unwrap();
+
+    let account = context.banks_client.get_account(tier_config).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), TierConfig::LEN);
+
+    // Every tier keeps its settings and counts, with no limits set
+    let tiers: TierConfig = fetch(&mut context, tier_config).await;
+    assert_eq!(tiers.version, TierConfig::VERSION);
+    assert_eq!(tiers.bump, 254);
+    assert_eq!(tiers.tiers.len(), 4);
+    assert_eq!(tiers.tiers[0].active_stakes, 4);
+    assert_eq!(tiers.tiers[3].name, "Campaign");
+    assert_eq!(tiers.tiers[3].multiplier, 4);
+    assert_eq!(tiers.tiers[3].lock_seconds, 14 * SECONDS_PER_DAY);
+    assert!(!tiers.tiers[3].enabled);
+    assert_eq!(tiers.tiers[3].max_stakes, 100);
+    assert_eq!(tiers.tiers[3].active_stakes, 7);
+    for tier in &tiers.tiers {
+        assert_eq!(tier.max_total_stake, 0);
+        assert_eq!(tier.max_user_stake, 0);
+        assert_eq!((tier.open_at, tier.close_at), (0, 0));
+        assert_eq!(tier.total_staked_sol, 0);
+    }
+
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &tier_config)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_version_1_user_account_stakes() {
+    let user = Pubkey::new_from_array([9; 32]);
+    let platform_state = pda::platform_state();
+    let tier_config = pda::tier_config();
+    let user_account = pda::user_account(&user);
+
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0));
+    program_test.add_account(tier_config, fixture_account(TIER_CONFIG_V0));
+    program_test.add_account(user_account, fixture_account(USER_ACCOUNT_V1));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    // Version 1 stakes are too short to decode as the current ones
+    let raw = context.banks_client.get_account(user_account).await.unwrap().unwrap();
+    assert!(UserAccount::try_deserialize(&mut raw.data.as_slice()).is_err());
+
+    let migrate_ixs = [
+        instructions::migrate_account(&payer.pubkey(), &tier_config),
+        instructions::migrate_account(&payer.pubkey(), &user_account),
+    ];
+    send(&mut context, &migrate_ixs, &payer).await.unwrap();
+
+    // Stakes keep their values, and rewards still stop at expiry for them
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.version, UserAccount::VERSION);
+    assert_eq!(account.stakes.len(), 2);
+    assert_eq!(account.stakes[0].amount, 49_000_000_000);
+    assert_eq!(account.stakes[0].claimed_rewards, 1_000_000);
+    assert!(account.stakes.iter().all(|stake| stake.post_lock == PostLock::None));
+    assert_eq!(account.total_claimed_sol, 1_000_000);
+
+    let raw = context.banks_client.get_account(user_account).await.unwrap().unwrap();
+    assert_eq!(raw.data.len(), UserAccount::space_for_stakes(10));
+    assert!(raw.lamports >= Rent::default().minimum_balance(raw.data.len()));
+
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &user_account)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_version_2_user_account_to_unbonding_stakes() {
+    let user = Pubkey::new_from_array([9; 32]);
+    let platform_state = pda::platform_state();
+    let tier_config = pda::tier_config();
+    let user_account = pda::user_account(&user);
+
+    let mut program_test = program_test();
+    program_test.add_account(platform_state, fixture_account(PLATFORM_STATE_V0));
+    program_test.add_account(tier_config, fixture_account(TIER_CONFIG_V0));
+    program_test.add_account(user_account, fixture_account(USER_ACCOUNT_V2));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let migrate_ixs = [
+        instructions::migrate_account(&payer.pubkey(), &tier_config),
+        instructions::migrate_account(&payer.pubkey(), &user_account),
+    ];
+    send(&mut context, &migrate_ixs, &payer).await.unwrap();
+
+    // Post-lock policies carry over, and no stake starts out unbonding
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.version, UserAccount::VERSION);
+    assert_eq!(account.stakes.len(), 2);
+    assert_eq!(account.stakes[0].amount, 49_000_000_000);
+    assert!(account.stakes[0].post_lock == PostLock::None);
+    assert!(account.stakes[0].is_active);
+    assert!(account.stakes[1].post_lock == PostLock::Flexible);
+    assert_eq!(account.stakes[1].post_lock_rate_bps, 2_500);
+    assert!(account.stakes.iter().all(|stake| !stake.is_unbonding));
+    assert_eq!(account.total_claimed_sol, 1_000_000);
+
+    let raw = context.banks_client.get_account(user_account).await.unwrap().unwrap();
+    assert_eq!(raw.data.len(), UserAccount::space_for_stakes(10));
+
+    let result = send(&mut context, &[instructions::migrate_account(&payer.pubkey(), &user_account)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrates_version_3_stakes_to_their_tier_terms() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let user_account = pda::user_account(&admin);
+    let deposit_ix = instructions::deposit_sol(&admin, 10 * LAMPORTS_PER_SOL, 1, None, None, false);
+    send(&mut context, &[deposit_ix], &payer).await.unwrap();
+
+    // Version 3 stakes have the same layout but no terms of their own
+    let mut account: UserAccount = fetch(&mut context, user_account).await;
+    account.version = 3;
+    account.stakes[0].terms_fixed = false;
+    account.stakes[0].multiplier = 0;
+    account.stakes[0].lock_days = 0;
+    let mut raw = context.banks_client.get_account(user_account).await.unwrap().unwrap();
+    let len = raw.data.len();
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(len, 0);
+    raw.data = data;
+    context.set_account(&user_account, &raw.into());
+
+    // Until migrated they read the live tier
+    let update_ix = instructions::update_tier_config(&admin, 1, SYMBIOTIC_MIN_STAKE, 4, 60 * SECONDS_PER_DAY, 0);
+    send(&mut context, &[update_ix], &payer).await.unwrap();
+    let tiers: TierConfig = fetch(&mut context, pda::tier_config()).await;
+    let terms = account.stakes[0].terms(&tiers.tiers[1]);
+    assert_eq!((terms.multiplier, terms.lock_days), (4, 60));
+
+    send(&mut context, &[instructions::migrate_account(&admin, &user_account)], &payer).await.unwrap();
+
+    // Migrating fixes the tier's terms at that point; the size is unchanged
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    assert_eq!(account.version, UserAccount::VERSION);
+    assert!(account.stakes[0].terms_fixed);
+    assert_eq!((account.stakes[0].multiplier, account.stakes[0].lock_days), (4, 60));
+    let raw = context.banks_client.get_account(user_account).await.unwrap().unwrap();
+    assert_eq!(raw.data.len(), len);
+
+    let result = send(&mut context, &[instructions::migrate_account(&admin, &user_account)], &payer).await;
+    assert_error(result, SpaceMoneyError::AccountAlreadyMigrated);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signer, system_instruction,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{PenaltyCurve, PenaltyStep};
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::pda;
+
+use common::*;
+
+fn set_penalty_ix(admin: Pubkey, steps: Vec<PenaltyStep>) -> Instruction {
+    instructions::set_tier_penalty(&admin, 0, PenaltyCurve::Step, 0, &steps, true, true)
+}
+
+fn claim_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::claim_rewards(&StakeAuthority::Depositor(user), None, stake_index, false)
+}
+
+fn force_withdraw_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::force_withdraw(&StakeAuthority::Depositor(user), None, stake_index, false)
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn step_penalty_takes_principal_and_claws_back_claimed_rewards() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    let fund_ix = system_instruction::transfer(&admin, &pda::platform_state(), 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // Steps must run through the lock in order
+    let unordered = vec![
+        PenaltyStep { from_bps: 5_000, rate_bps: 1_000 },
+        PenaltyStep { from_bps: 0, rate_bps: 5_000 },
+    ];
+    let result = send(&mut context, &[set_penalty_ix(admin, unordered)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidTierParams);
+
+    // Half of everything in the first half of Boot's lock, a tenth after
+    let steps = vec![
+        PenaltyStep { from_bps: 0, rate_bps: 5_000 },
+        PenaltyStep { from_bps: 5_000, rate_bps: 1_000 },
+    ];
+    send(&mut context, &[set_penalty_ix(admin, steps)], &payer).await.unwrap();
+    let tier = fetch_tier_config(&mut context).await.tiers[0].clone();
+    assert!(tier.penalty_curve == PenaltyCurve::Step);
+    assert_eq!(tier.penalty_step_count, 2);
+
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+    let (principal, _) = spacemoney_math::calculate_net_deposit(10 * LAMPORTS_PER_SOL).unwrap();
+
+    // The curve is fixed once the tier has stakes under it
+    let lenient = vec![PenaltyStep { from_bps: 0, rate_bps: 100 }];
+    let result = send(&mut context, &[set_penalty_ix(admin, lenient)], &payer).await;
+    assert_error(result, SpaceMoneyError::TierHasActiveStakes);
+    let daily = spacemoney_math::calculate_daily_reward(principal, 1).unwrap();
+
+    set_clock(&mut context, now + 10 * SECONDS_PER_DAY).await;
+    send(&mut context, &[claim_ix(admin, 0)], &payer).await.unwrap();
+    let treasury_before = fetch_platform_state(&mut context).await.treasury_sol;
+
+    // Two thirds through the lock: 10% of the unclaimed ten days, of the
+    // principal and of the ten days already claimed
+    set_clock(&mut context, now + 20 * SECONDS_PER_DAY).await;
+    let balance_before = context.banks_client.get_balance(admin).await.unwrap();
+    send(&mut context, &[force_withdraw_ix(admin, 0)], &payer).await.unwrap();
+    let balance_after = context.banks_client.get_balance(admin).await.unwrap();
+
+    let penalty = daily * 10 / 10 + principal / 10 + daily * 10 / 10;
+    let fee = 5_000;
+    assert_eq!(balance_after + fee - balance_before, principal + daily * 10 - penalty);
+    assert_eq!(fetch_platform_state(&mut context).await.treasury_sol, treasury_before + penalty);
+}
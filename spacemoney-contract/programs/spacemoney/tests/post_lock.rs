@@ -0,0 +1,65 @@
+mod common;
+
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer,
+    system_instruction,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::PostLock;
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::pda;
+
+use common::*;
+
+fn set_post_lock_ix(admin: Pubkey, post_lock: PostLock, post_lock_rate_bps: u16) -> Instruction {
+    instructions::set_tier_post_lock(&admin, 0, post_lock, post_lock_rate_bps)
+}
+
+fn withdraw_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::withdraw_sol(&StakeAuthority::Depositor(user), stake_index, false)
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn auto_rolled_stakes_relock_until_a_withdrawal_window() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+
+    // Fund the vault for two locks of rewards
+    let fund_ix = system_instruction::transfer(&admin, &pda::platform_state(), 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // The flexible rate can't exceed the locked rate
+    let result = send(&mut context, &[set_post_lock_ix(admin, PostLock::Flexible, 10_001)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidTierParams);
+
+    // Boot stakes made from now on roll over at expiry
+    send(&mut context, &[set_post_lock_ix(admin, PostLock::AutoRoll, 0)], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+    let stake = fetch_user_account(&mut context, admin).await.stakes[0];
+    assert!(stake.post_lock == PostLock::AutoRoll);
+
+    // Changing the tier doesn't touch the existing stake
+    send(&mut context, &[set_post_lock_ix(admin, PostLock::None, 0)], &payer).await.unwrap();
+
+    // Two days after the first expiry the stake is in its second lock
+    let lock_length = stake.lock_until - stake.deposited_at;
+    set_clock(&mut context, stake.lock_until + 2 * SECONDS_PER_DAY).await;
+    let result = send(&mut context, &[withdraw_ix(admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::StakeLocked);
+
+    // Right after the second expiry it pays out both locks
+    set_clock(&mut context, stake.lock_until + lock_length).await;
+    let balance_before = context.banks_client.get_balance(admin).await.unwrap();
+    send(&mut context, &[withdraw_ix(admin, 0)], &payer).await.unwrap();
+    let balance_after = context.banks_client.get_balance(admin).await.unwrap();
+
+    let rewards = spacemoney_math::calculate_rewards(stake.amount, BOOT_MULTIPLIER, BOOT_LOCK_DAYS).unwrap();
+    let fee = 5_000;
+    assert_eq!(balance_after + fee - balance_before, stake.amount + 2 * rewards);
+    assert!(!fetch_user_account(&mut context, admin).await.stakes[0].is_active);
+}
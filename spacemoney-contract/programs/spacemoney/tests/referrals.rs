@@ -0,0 +1,81 @@
+mod common;
+
+use anchor_lang::InstructionData;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer,
+};
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{ReferralAccount, TokenType};
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+async fn fetch_referral_account(context: &mut ProgramTestContext, referrer: Pubkey) -> ReferralAccount {
+    fetch(context, pda::referral_account(&referrer)).await
+}
+
+// A USDT claim is sent without token accounts; it must fail on the empty
+// balance before they're needed
+fn claim_referral_rewards_ix(referrer: Pubkey, token_type: TokenType) -> Instruction {
+    let mut instruction = instructions::claim_referral_rewards(&referrer, None);
+    instruction.data = spacemoney::instruction::ClaimReferralRewards { token_type }.data();
+    instruction
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8, referrer: Option<Pubkey>) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, referrer.as_ref(), None, false)
+}
+
+#[tokio::test]
+async fn referrers_earn_a_share_of_deposit_fees() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let referrer = funded_keypair(&mut context, 2 * LAMPORTS_PER_SOL).await;
+
+    // Only the admin sets the share, and it can't exceed the whole fee
+    let result = send(&mut context, &[instructions::set_referral_share(&referrer.pubkey(), 2500)], &referrer).await;
+    assert_error(result, SpaceMoneyError::Unauthorized);
+    let result = send(&mut context, &[instructions::set_referral_share(&admin, 10_001)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidReferralShare);
+    send(&mut context, &[instructions::set_referral_share(&admin, 2500)], &payer).await.unwrap();
+    assert_eq!(fetch_platform_state(&mut context).await.referral_share_bps, 2500);
+
+    send(&mut context, &[instructions::register_referrer(&referrer.pubkey())], &referrer).await.unwrap();
+    let result = send(&mut context, &[claim_referral_rewards_ix(referrer.pubkey(), TokenType::SOL)], &referrer).await;
+    assert_error(result, SpaceMoneyError::NoReferralRewards);
+
+    // Referring your own deposit is rejected
+    let result = send(&mut context, &[deposit_ix(referrer.pubkey(), LAMPORTS_PER_SOL, 0, Some(referrer.pubkey()))], &referrer).await;
+    assert_error(result, SpaceMoneyError::SelfReferral);
+
+    // A quarter of the 2% fee goes to the referrer, the rest to the treasury
+    let amount = 10 * LAMPORTS_PER_SOL;
+    let (_, fee) = spacemoney_math::calculate_net_deposit(amount).unwrap();
+    let reward = spacemoney_math::calculate_referral_reward(fee, 2500).unwrap();
+    send(&mut context, &[deposit_ix(admin, amount, 0, Some(referrer.pubkey()))], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(admin, amount, 0, None)], &payer).await.unwrap();
+    assert_eq!(fetch_platform_state(&mut context).await.treasury_sol, 2 * fee - reward);
+
+    let referral = fetch_referral_account(&mut context, referrer.pubkey()).await;
+    assert_eq!(referral.referrer, referrer.pubkey());
+    assert_eq!(referral.referred_deposits, 1);
+    assert_eq!(referral.referred_volume_sol, amount);
+    assert_eq!(referral.pending_sol, reward);
+    assert_eq!(referral.pending_usdt, 0);
+
+    let result = send(&mut context, &[claim_referral_rewards_ix(referrer.pubkey(), TokenType::USDT)], &referrer).await;
+    assert_error(result, SpaceMoneyError::NoReferralRewards);
+
+    // Paid out of the vault
+    let vault_before = lamports(&mut context, pda::platform_state()).await;
+    send(&mut context, &[claim_referral_rewards_ix(referrer.pubkey(), TokenType::SOL)], &referrer).await.unwrap();
+    let vault_after = lamports(&mut context, pda::platform_state()).await;
+    assert_eq!(vault_before - vault_after, reward);
+
+    let referral = fetch_referral_account(&mut context, referrer.pubkey()).await;
+    assert_eq!(referral.pending_sol, 0);
+    assert_eq!(referral.total_claimed_sol, reward);
+    let result = send(&mut context, &[claim_referral_rewards_ix(referrer.pubkey(), TokenType::SOL)], &referrer).await;
+    assert_error(result, SpaceMoneyError::NoReferralRewards);
+}
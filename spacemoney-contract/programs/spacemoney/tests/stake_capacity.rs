@@ -0,0 +1,77 @@
+mod common;
+
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent,
+    signature::Signer,
+};
+use spacemoney::states::UserAccount;
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+fn deposit_ix(user: Pubkey) -> Instruction {
+    instructions::deposit_sol(&user, 2 * LAMPORTS_PER_SOL, 3, None, None, false)
+}
+
+async fn account_len(context: &mut ProgramTestContext, user: Pubkey) -> usize {
+    let account = context
+        .banks_client
+        .get_account(pda::user_account(&user))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(account.lamports >= Rent::default().minimum_balance(account.data.len()));
+    account.data.len()
+}
+
+#[tokio::test]
+async fn full_accounts_grow_for_deposits_splits_and_transfers() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let add_tier_ix = instructions::add_tier(&admin, "Open", LAMPORTS_PER_SOL, 2, 0, 0);
+    let transfers_ix = instructions::set_stake_transfers(&admin, true);
+    send(&mut context, &[add_tier_ix, transfers_ix], &payer).await.unwrap();
+
+    // Ten deposits fill the first allocation exactly
+    for _ in 0..10 {
+        send(&mut context, &[deposit_ix(admin)], &payer).await.unwrap();
+    }
+    assert_eq!(account_len(&mut context, admin).await, UserAccount::space_for_stakes(10));
+
+    // The eleventh grows the account by one stake, at the depositor's expense
+    let balance_before = lamports(&mut context, admin).await;
+    send(&mut context, &[deposit_ix(admin)], &payer).await.unwrap();
+    let balance_after = lamports(&mut context, admin).await;
+    assert_eq!(account_len(&mut context, admin).await, UserAccount::space_for_stakes(11));
+    let grown_rent = Rent::default().minimum_balance(UserAccount::space_for_stakes(11))
+        - Rent::default().minimum_balance(UserAccount::space_for_stakes(10));
+    let fee = 5_000;
+    assert_eq!(balance_before - balance_after, 2 * LAMPORTS_PER_SOL + grown_rent + fee);
+
+    // So does a split, which adds a stake without a deposit
+    send(&mut context, &[instructions::split_stake(&admin, 0, 980_000_000)], &payer).await.unwrap();
+    assert_eq!(account_len(&mut context, admin).await, UserAccount::space_for_stakes(12));
+    let account = fetch_user_account(&mut context, admin).await;
+    assert_eq!(account.stakes.len(), 12);
+    assert_eq!(account.stakes[0].amount + account.stakes[11].amount, 1_960_000_000);
+
+    // And a transfer into a full account, which the sender pays for
+    let other = funded_keypair(&mut context, 30 * LAMPORTS_PER_SOL).await;
+    for _ in 0..10 {
+        send(&mut context, &[deposit_ix(other.pubkey())], &other).await.unwrap();
+    }
+    let other_balance = lamports(&mut context, other.pubkey()).await;
+    let transfer_ix = instructions::transfer_stake(&admin, &other.pubkey(), 1);
+    send(&mut context, &[transfer_ix], &payer).await.unwrap();
+    assert_eq!(account_len(&mut context, other.pubkey()).await, UserAccount::space_for_stakes(11));
+    assert_eq!(lamports(&mut context, other.pubkey()).await, other_balance);
+
+    // Closed stakes keep their index, so the sender's account doesn't shrink
+    let account = fetch_user_account(&mut context, admin).await;
+    assert_eq!(account.stakes.len(), 12);
+    assert!(!account.stakes[1].is_active);
+    let recipient = fetch_user_account(&mut context, other.pubkey()).await;
+    assert_eq!(recipient.stakes.len(), 11);
+    assert!(recipient.stakes[10].is_active);
+}
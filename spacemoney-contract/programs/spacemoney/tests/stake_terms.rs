@@ -0,0 +1,115 @@
+mod common;
+
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signer, system_instruction,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{AccrualMode, Rounding};
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::pda;
+
+use common::*;
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn tier_updates_leave_existing_stakes_on_their_terms() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let authority = StakeAuthority::Depositor(admin);
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    let fund_ix = system_instruction::transfer(&admin, &pda::platform_state(), 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // 10 SOL in Symbiotic at 2x for 90 days, with a month of rewards claimed
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 1)], &payer).await.unwrap();
+    let (principal, _) = spacemoney_math::calculate_net_deposit(10 * LAMPORTS_PER_SOL).unwrap();
+    let daily = spacemoney_math::calculate_daily_reward(principal, SYMBIOTIC_MULTIPLIER).unwrap();
+
+    set_clock(&mut context, now + 30 * SECONDS_PER_DAY).await;
+    let claim_ix = instructions::claim_rewards(&authority, None, 0, false);
+    send(&mut context, &[claim_ix], &payer).await.unwrap();
+
+    // Cutting the tier to 1x for 10 days only reaches later deposits
+    let update_ix = instructions::update_tier_config(&admin, 1, SYMBIOTIC_MIN_STAKE, 1, 10 * SECONDS_PER_DAY, 0);
+    send(&mut context, &[update_ix], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 1)], &payer).await.unwrap();
+
+    let account = fetch_user_account(&mut context, admin).await;
+    assert!(account.stakes.iter().all(|stake| stake.terms_fixed));
+    assert_eq!(account.stakes[0].multiplier as u64, SYMBIOTIC_MULTIPLIER);
+    assert_eq!(account.stakes[0].lock_days as i64, SYMBIOTIC_LOCK_DAYS);
+    assert_eq!((account.stakes[1].multiplier, account.stakes[1].lock_days), (1, 10));
+
+    // Stakes on different terms can't be merged, even once both have matured
+    set_clock(&mut context, now + 90 * SECONDS_PER_DAY).await;
+    let balance_before = context.banks_client.get_balance(admin).await.unwrap();
+    let merge_ix = instructions::merge_stakes(&admin, 0, vec![1]);
+    let result = send(&mut context, &[merge_ix], &payer).await;
+    assert_error(result, SpaceMoneyError::IncompatibleStakes);
+
+    // The first stake still pays out its full 90 days at 2x
+    send(&mut context, &[instructions::withdraw_sol(&authority, 0, false)], &payer).await.unwrap();
+    let balance_after = context.banks_client.get_balance(admin).await.unwrap();
+
+    // Both transactions paid a fee
+    let fees = 2 * 5_000;
+    assert_eq!(balance_after + fees - balance_before, principal + daily * 60);
+}
+
+#[tokio::test]
+async fn accrual_changes_leave_existing_stakes_on_their_terms() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let authority = StakeAuthority::Depositor(admin);
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    let fund_ix = system_instruction::transfer(&admin, &pda::platform_state(), 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // A daily Boot stake, then Boot switches to per-second accrual
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+    let (principal, _) = spacemoney_math::calculate_net_deposit(10 * LAMPORTS_PER_SOL).unwrap();
+    let daily = spacemoney_math::calculate_daily_reward(principal, BOOT_MULTIPLIER).unwrap();
+
+    let accrual_ix = instructions::set_tier_accrual(&admin, 0, AccrualMode::PerSecond, Rounding::Up);
+    send(&mut context, &[accrual_ix], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+
+    let account = fetch_user_account(&mut context, admin).await;
+    assert!(account.stakes[0].accrual_mode == AccrualMode::Daily);
+    assert!(account.stakes[0].rounding == Rounding::Down);
+    assert!(account.stakes[1].accrual_mode == AccrualMode::PerSecond);
+    assert!(account.stakes[1].rounding == Rounding::Up);
+
+    // Half a day on, the first stake still only has its whole days
+    set_clock(&mut context, now + 10 * SECONDS_PER_DAY + SECONDS_PER_DAY / 2).await;
+    let balance_before = context.banks_client.get_balance(admin).await.unwrap();
+    let claim_ix = instructions::claim_rewards(&authority, None, 0, false);
+    send(&mut context, &[claim_ix], &payer).await.unwrap();
+    let balance_after = context.banks_client.get_balance(admin).await.unwrap();
+
+    let fee = 5_000;
+    assert_eq!(balance_after + fee - balance_before, daily * 10);
+}
+
+#[tokio::test]
+async fn tier_terms_are_checked_against_what_stakes_can_record() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+
+    let too_large = u32::MAX as u64 + 1;
+    let add_ix = instructions::add_tier(&admin, "Huge", LAMPORTS_PER_SOL, too_large, SECONDS_PER_DAY, 0);
+    let result = send(&mut context, &[add_ix], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidTierParams);
+
+    let lock_seconds = too_large as i64 * SECONDS_PER_DAY;
+    let update_ix = instructions::update_tier_config(&admin, 0, BOOT_MIN_STAKE, 1, lock_seconds, 0);
+    let result = send(&mut context, &[update_ix], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidTierParams);
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signer,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney_client::instructions;
+
+use common::*;
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn deposits_respect_tier_caps_and_window() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+    let (open_at, close_at) = (now + SECONDS_PER_DAY, now + 2 * SECONDS_PER_DAY);
+
+    // A campaign tier capped at 4 SOL overall and 3 SOL per user, open for one day
+    let add_tier_ix = instructions::add_tier(&admin, "Campaign", LAMPORTS_PER_SOL, 2, 7 * SECONDS_PER_DAY, 0);
+    let set_limits_ix = instructions::set_tier_limits(
+        &admin,
+        3,
+        4 * LAMPORTS_PER_SOL,
+        3 * LAMPORTS_PER_SOL,
+        open_at,
+        close_at,
+    );
+    send(&mut context, &[add_tier_ix, set_limits_ix], &payer).await.unwrap();
+
+    let tiers = fetch_tier_config(&mut context).await;
+    assert_eq!(tiers.tiers[3].max_total_stake, 4 * LAMPORTS_PER_SOL);
+    assert_eq!(tiers.tiers[3].open_at, open_at);
+
+    // Nothing goes in before the window opens
+    let result = send(&mut context, &[deposit_ix(admin, 2 * LAMPORTS_PER_SOL, 3)], &payer).await;
+    assert_error(result, SpaceMoneyError::TierNotOpen);
+
+    set_clock(&mut context, open_at).await;
+    send(&mut context, &[deposit_ix(admin, 2 * LAMPORTS_PER_SOL, 3)], &payer).await.unwrap();
+    let (first, _) = spacemoney_math::calculate_net_deposit(2 * LAMPORTS_PER_SOL).unwrap();
+
+    // A second 2 SOL stake would take the admin past the per-user cap
+    let result = send(&mut context, &[deposit_ix(admin, 2 * LAMPORTS_PER_SOL, 3)], &payer).await;
+    assert_error(result, SpaceMoneyError::UserStakeCapExceeded);
+
+    // Another user fits under their own cap but not under the tier's
+    let other = funded_keypair(&mut context, 10 * LAMPORTS_PER_SOL).await;
+    let result = send(&mut context, &[deposit_ix(other.pubkey(), 3 * LAMPORTS_PER_SOL, 3)], &other).await;
+    assert_error(result, SpaceMoneyError::TierStakeCapExceeded);
+
+    send(&mut context, &[deposit_ix(other.pubkey(), 2 * LAMPORTS_PER_SOL, 3)], &other).await.unwrap();
+    let (second, _) = spacemoney_math::calculate_net_deposit(2 * LAMPORTS_PER_SOL).unwrap();
+
+    let tiers = fetch_tier_config(&mut context).await;
+    assert_eq!(tiers.tiers[3].active_stakes, 2);
+    assert_eq!(tiers.tiers[3].total_staked_sol, first + second);
+    assert_eq!(tiers.tiers[3].total_staked_usdt, 0);
+
+    // Taking the other user's stake would put the admin over the per-user cap too
+    let transfer_ix = instructions::transfer_stake(&other.pubkey(), &admin, 0);
+    let result = send(&mut context, &[transfer_ix], &other).await;
+    assert_error(result, SpaceMoneyError::UserStakeCapExceeded);
+
+    // Limits are per tier, so Boot still takes deposits
+    send(&mut context, &[deposit_ix(admin, 2 * LAMPORTS_PER_SOL, 0)], &payer).await.unwrap();
+
+    set_clock(&mut context, close_at).await;
+    let result = send(&mut context, &[deposit_ix(admin, LAMPORTS_PER_SOL, 3)], &payer).await;
+    assert_error(result, SpaceMoneyError::TierClosed);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use solana_sdk::{
+    clock::Clock, instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey,
+    signature::Signer, system_instruction,
+};
+use spacemoney::constants::*;
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::PostLock;
+use spacemoney_client::instructions::{self, StakeAuthority};
+use spacemoney_client::pda;
+
+use common::*;
+
+fn request_unstake_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::request_unstake(&StakeAuthority::Depositor(user), stake_index)
+}
+
+fn complete_unstake_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::complete_unstake(&StakeAuthority::Depositor(user), None, stake_index, false)
+}
+
+fn withdraw_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::withdraw_sol(&StakeAuthority::Depositor(user), stake_index, false)
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn flexible_tier_pays_out_after_its_unbonding_cooldown() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    // No lock, earning half of a 2x tier's rate from the start
+    let add_tier_ix = instructions::add_tier(&admin, "Flexible", LAMPORTS_PER_SOL, 2, 0, 0);
+    let set_post_lock_ix = instructions::set_tier_post_lock(&admin, 3, PostLock::Flexible, 5_000);
+    let fund_ix = system_instruction::transfer(&admin, &pda::platform_state(), 10 * LAMPORTS_PER_SOL);
+    send(&mut context, &[add_tier_ix, set_post_lock_ix, fund_ix], &payer).await.unwrap();
+
+    let result = send(&mut context, &[instructions::set_tier_unbonding(&admin, 3, -1)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidTierParams);
+    send(&mut context, &[instructions::set_tier_unbonding(&admin, 3, 2 * SECONDS_PER_DAY)], &payer).await.unwrap();
+    assert_eq!(fetch_tier_config(&mut context).await.tiers[3].unbonding_seconds, 2 * SECONDS_PER_DAY);
+
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 3)], &payer).await.unwrap();
+    let stake = fetch_user_account(&mut context, admin).await.stakes[0];
+    assert_eq!(stake.lock_until, stake.deposited_at);
+
+    // The cooldown replaces the direct withdrawal
+    let result = send(&mut context, &[withdraw_ix(admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::UnbondingRequired);
+    let result = send(&mut context, &[complete_unstake_ix(admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::StakeNotUnbonding);
+
+    let requested_at = now + 10 * SECONDS_PER_DAY;
+    set_clock(&mut context, requested_at).await;
+    send(&mut context, &[request_unstake_ix(admin, 0)], &payer).await.unwrap();
+    let unbonding = fetch_user_account(&mut context, admin).await.stakes[0];
+    assert!(unbonding.is_active && unbonding.is_unbonding);
+    assert_eq!(unbonding.unbonding_until, requested_at + 2 * SECONDS_PER_DAY);
+
+    let result = send(&mut context, &[request_unstake_ix(admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::StakeUnbonding);
+    set_clock(&mut context, requested_at + SECONDS_PER_DAY).await;
+    let result = send(&mut context, &[complete_unstake_ix(admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::UnbondingNotComplete);
+
+    // Pays ten days at half rate; the cooldown itself earns nothing
+    set_clock(&mut context, unbonding.unbonding_until).await;
+    let balance_before = context.banks_client.get_balance(admin).await.unwrap();
+    send(&mut context, &[complete_unstake_ix(admin, 0)], &payer).await.unwrap();
+    let balance_after = context.banks_client.get_balance(admin).await.unwrap();
+
+    let daily = spacemoney_math::calculate_daily_reward(stake.amount, 2).unwrap();
+    let fee = 5_000;
+    assert_eq!(balance_after + fee - balance_before, stake.amount + daily * 10 / 2);
+    assert!(!fetch_user_account(&mut context, admin).await.stakes[0].is_active);
+    assert_eq!(fetch_tier_config(&mut context).await.tiers[3].active_stakes, 0);
+}
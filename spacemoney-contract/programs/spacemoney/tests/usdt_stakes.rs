@@ -0,0 +1,60 @@
+mod common;
+
+use solana_sdk::{clock::Clock, instruction::Instruction, pubkey::Pubkey, signature::Signer};
+use spacemoney::constants::*;
+use spacemoney_client::instructions::{self, StakeAuthority, UsdtAccounts};
+use spacemoney_client::pda;
+
+use common::*;
+
+const USDT: u64 = 1_000_000;
+
+fn deposit_ix(user: Pubkey, usdt: &UsdtAccounts, amount: u64) -> Instruction {
+    instructions::deposit_usdt(&user, usdt, amount, 0, None, None, false)
+}
+
+#[tokio::test]
+async fn every_usdt_exit_releases_its_principal() {
+    let (mut context, payer) = start_insured().await;
+    let admin = payer.pubkey();
+    let authority = StakeAuthority::Depositor(admin);
+    let now = context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp;
+
+    let usdt_mint = fetch_platform_state(&mut context).await.usdt_mint;
+    let usdt = UsdtAccounts {
+        user_token_account: create_token_account(&mut context, &usdt_mint, &admin).await,
+        platform_token_account: create_token_account(&mut context, &usdt_mint, &pda::vault_authority()).await,
+    };
+    mint_tokens(&mut context, &usdt_mint, &usdt.user_token_account, 10_000 * USDT).await;
+    let fund_ix = instructions::fund_rewards(&admin, Some(&usdt), 2_000 * USDT);
+    send(&mut context, &[fund_ix], &payer).await.unwrap();
+
+    // Three Boot stakes, left by a force withdrawal, a withdrawal and an unstake
+    for _ in 0..3 {
+        send(&mut context, &[deposit_ix(admin, &usdt, 2_000 * USDT)], &payer).await.unwrap();
+    }
+    let (principal, _) = spacemoney_math::calculate_net_deposit(2_000 * USDT).unwrap();
+    assert_eq!(fetch_platform_state(&mut context).await.total_staked_usdt, 3 * principal);
+
+    set_clock(&mut context, now + SECONDS_PER_DAY).await;
+    let force_withdraw_ix = instructions::force_withdraw(&authority, Some(&usdt), 0, false);
+    send(&mut context, &[force_withdraw_ix], &payer).await.unwrap();
+    assert_eq!(fetch_platform_state(&mut context).await.total_staked_usdt, 2 * principal);
+
+    set_clock(&mut context, now + (BOOT_LOCK_DAYS + 1) * SECONDS_PER_DAY).await;
+    send(&mut context, &[instructions::withdraw_usdt(&authority, &usdt, 1, false)], &payer).await.unwrap();
+    assert_eq!(fetch_platform_state(&mut context).await.total_staked_usdt, principal);
+
+    let unbonding_ix = instructions::set_tier_unbonding(&admin, 0, SECONDS_PER_DAY);
+    let request_ix = instructions::request_unstake(&authority, 2);
+    send(&mut context, &[unbonding_ix, request_ix], &payer).await.unwrap();
+    set_clock(&mut context, now + (BOOT_LOCK_DAYS + 2) * SECONDS_PER_DAY).await;
+    let complete_ix = instructions::complete_unstake(&authority, Some(&usdt), 2, false);
+    send(&mut context, &[complete_ix], &payer).await.unwrap();
+
+    // Nothing staked is left, so the totals coverage is read against are clear
+    let platform = fetch_platform_state(&mut context).await;
+    assert_eq!(platform.total_staked_usdt, 0);
+    assert_eq!(fetch_tier_config(&mut context).await.tiers[0].total_staked_usdt, 0);
+    assert_eq!(fetch_user_account(&mut context, admin).await.active_stakes(), 0);
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    clock::Clock,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{Keypair, Signer},
+    stake::{self, state::StakeStateV2},
+    system_instruction,
+    vote::{
+        instruction::{self as vote_instruction, CreateVoteAccountConfig},
+        state::{VoteInit, VoteState},
+    },
+};
+use spacemoney::states::{PlatformState, ValidatorList, ValidatorStatus};
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+async fn create_vote_account(context: &mut ProgramTestContext) -> Pubkey {
+    let vote = Keypair::new();
+    let node = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let config = CreateVoteAccountConfig {
+        space: VoteState::size_of() as u64,
+        ..CreateVoteAccountConfig::default()
+    };
+    let instructions = vote_instruction::create_account_with_config(
+        &context.payer.pubkey(),
+        &vote.pubkey(),
+        &VoteInit {
+            node_pubkey: node.pubkey(),
+            authorized_voter: node.pubkey(),
+            authorized_withdrawer: node.pubkey(),
+            commission: 0,
+        },
+        rent.minimum_balance(config.space as usize),
+        config,
+    );
+    let payer = context.payer.insecure_clone();
+    send_signed(context, &instructions, &payer, &[&vote, &node]).await.unwrap();
+    vote.pubkey()
+}
+
+// Skips past the epoch rewards interval, during which stake accounts are locked
+fn warp_to_epoch(context: &mut ProgramTestContext, epoch: u64) {
+    context.warp_to_epoch(epoch).unwrap();
+    context.warp_forward_force_reward_interval_end().unwrap();
+}
+
+#[tokio::test]
+async fn delegates_and_unwinds_validator_stake() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+    let platform_state = pda::platform_state();
+    let validator_list = pda::validator_list();
+
+    // Stake 10 SOL in the Boot tier
+    let deposit_ix = instructions::deposit_sol(&admin, 10 * LAMPORTS_PER_SOL, 0, None, None, false);
+    send(&mut context, &[deposit_ix], &payer).await.unwrap();
+
+    // Register a validator and delegate half of the staked SOL
+    let vote_account = create_vote_account(&mut context).await;
+    let add_ix = instructions::add_validator(&admin, &vote_account);
+    let target_ix = instructions::set_delegation_target(&admin, 5000);
+    send(&mut context, &[add_ix, target_ix], &payer).await.unwrap();
+
+    let vault_before = context.banks_client.get_balance(platform_state).await.unwrap();
+    send(&mut context, &[instructions::rebalance_validator(&admin, &vote_account)], &payer).await.unwrap();
+
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    let list: ValidatorList = fetch(&mut context, validator_list).await;
+    let expected = platform.total_staked_sol / 2;
+    assert_eq!(list.total_delegated, expected);
+    assert!(list.validators[0].status == ValidatorStatus::Active);
+
+    // The stake account is owned by the stake program and delegated to the vote account
+    let stake_address = pda::validator_stake(&vote_account);
+    let stake_account = context.banks_client.get_account(stake_address).await.unwrap().unwrap();
+    assert_eq!(stake_account.owner, stake::program::id());
+    let stake_rent = Rent::default().minimum_balance(StakeStateV2::size_of());
+    assert_eq!(stake_account.lamports, expected + stake_rent);
+    match bincode::deserialize::<StakeStateV2>(&stake_account.data).unwrap() {
+        StakeStateV2::Stake(meta, stake, _) => {
+            assert_eq!(meta.authorized.staker, pda::vault_authority());
+            assert_eq!(meta.authorized.withdrawer, pda::vault_authority());
+            assert_eq!(stake.delegation.voter_pubkey, vote_account);
+            assert_eq!(stake.delegation.stake, expected);
+        }
+        _ => panic!("stake account is not delegated"),
+    }
+    let vault_after = context.banks_client.get_balance(platform_state).await.unwrap();
+    assert_eq!(vault_before - vault_after, expected + stake_rent);
+
+    // Removing the validator deactivates its stake on the next epoch
+    let remove_ix = instructions::remove_validator(&admin, &vote_account);
+    send(&mut context, &[remove_ix], &payer).await.unwrap();
+
+    let epoch = context.banks_client.get_sysvar::<Clock>().await.unwrap().epoch;
+    warp_to_epoch(&mut context, epoch + 1);
+    send(&mut context, &[instructions::rebalance_validator(&admin, &vote_account)], &payer).await.unwrap();
+
+    let list: ValidatorList = fetch(&mut context, validator_list).await;
+    assert!(list.validators[0].status == ValidatorStatus::Deactivating);
+
+    // A liquid pool opened meanwhile earns its share of the yield the stake brings back
+    send(&mut context, &[instructions::initialize_liquid_staking(&admin)], &payer).await.unwrap();
+    let spsol_account = create_token_account(&mut context, &pda::spsol_mint(), &admin).await;
+    let liquid_ix = instructions::deposit_sol_liquid(&admin, &spsol_account, 5 * LAMPORTS_PER_SOL, None, false);
+    send(&mut context, &[liquid_ix], &payer).await.unwrap();
+    let staked = fetch::<PlatformState>(&mut context, platform_state).await;
+    // Stands in for the validator's rewards
+    let yield_ix = system_instruction::transfer(&admin, &stake_address, LAMPORTS_PER_SOL);
+    send(&mut context, &[yield_ix], &payer).await.unwrap();
+
+    // After cooldown everything returns to the vault and the validator is dropped
+    warp_to_epoch(&mut context, epoch + 3);
+    send(&mut context, &[instructions::rebalance_validator(&admin, &vote_account)], &payer).await.unwrap();
+
+    let list: ValidatorList = fetch(&mut context, validator_list).await;
+    assert_eq!(list.total_yield, LAMPORTS_PER_SOL);
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    let liquid_yield = LAMPORTS_PER_SOL as u128 * staked.liquid_staked_sol as u128
+        / (staked.liquid_staked_sol + staked.total_staked_sol) as u128;
+    assert_eq!(platform.liquid_staked_sol, staked.liquid_staked_sol + liquid_yield as u64);
+
+    let list: ValidatorList = fetch(&mut context, validator_list).await;
+    assert!(list.validators.is_empty());
+    assert_eq!(list.total_delegated, 0);
+    assert!(context.banks_client.get_account(stake_address).await.unwrap().is_none());
+    let vault_final = context.banks_client.get_balance(platform_state).await.unwrap();
+    assert!(vault_final >= vault_before);
+}
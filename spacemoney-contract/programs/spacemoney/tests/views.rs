@@ -0,0 +1,62 @@
+mod common;
+
+use solana_sdk::{clock::Clock, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer};
+use spacemoney::constants::*;
+use spacemoney::instructions::{ClaimableView, PlatformStats, UserSummary};
+use spacemoney::states::{PlatformState, TierConfig, UserAccount};
+use spacemoney_client::{instructions, pda};
+
+use common::*;
+
+#[tokio::test]
+async fn views_report_current_values() {
+    // Initialize the platform and stake 10 SOL in the Boot tier
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let user = payer.pubkey();
+    let platform_state = pda::platform_state();
+    let user_account = pda::user_account(&user);
+    let deposit_ix = instructions::deposit_sol(&user, 10 * LAMPORTS_PER_SOL, 0, None, None, false);
+    send(&mut context, &[deposit_ix], &payer).await.unwrap();
+
+    // Move the clock 10 days past the deposit
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += 10 * SECONDS_PER_DAY;
+    context.set_sysvar(&clock);
+    let now = clock.unix_timestamp;
+
+    let get_claimable_ix = instructions::get_claimable(&user, 0);
+    let claimable: ClaimableView = view(&mut context, get_claimable_ix).await;
+
+    // Same numbers the off-chain math gives for the stored stake
+    let account: UserAccount = fetch(&mut context, user_account).await;
+    let tiers: TierConfig = fetch(&mut context, pda::tier_config()).await;
+    let terms = account.stakes[0].terms(&tiers.tiers[0]);
+    assert_eq!(claimable.timestamp, now);
+    assert_eq!(claimable.claimable, terms.claimable_at(now).unwrap());
+    assert!(claimable.claimable > 0);
+    assert_eq!(claimable.max_rewards, terms.max_rewards().unwrap());
+    assert!(claimable.is_locked);
+    assert_eq!(
+        claimable.withdrawable,
+        terms.force_withdraw_at(now).unwrap().total_withdrawal
+    );
+
+    let get_summary_ix = instructions::get_user_summary(&user);
+    let summary: UserSummary = view(&mut context, get_summary_ix).await;
+    assert_eq!(summary.user, user);
+    assert_eq!(summary.active_stakes, 1);
+    assert_eq!(summary.staked_sol, account.stakes[0].amount);
+    assert_eq!(summary.claimable_sol, claimable.claimable);
+    assert_eq!(summary.staked_usdt, 0);
+    assert_eq!(summary.next_unlock, account.stakes[0].lock_until);
+
+    let get_stats_ix = instructions::get_platform_stats(false);
+    let stats: PlatformStats = view(&mut context, get_stats_ix).await;
+    let platform: PlatformState = fetch(&mut context, platform_state).await;
+    assert_eq!(stats.totals.total_staked_sol, platform.total_staked_sol);
+    assert_eq!(stats.totals.treasury_sol, platform.treasury_sol);
+    assert_eq!(stats.spsol_supply, 0);
+    assert!(!stats.is_paused);
+    let balance = context.banks_client.get_balance(platform_state).await.unwrap();
+    assert_eq!(stats.sol_balance, balance);
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use anchor_spl::token::spl_token;
+use solana_program_test::ProgramTestContext;
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Signer,
+};
+use spacemoney::errors::SpaceMoneyError;
+use spacemoney::states::{PlatformState, WithdrawalQueue};
+use spacemoney_client::instructions::{self, StakeAuthority, UsdtAccounts};
+use spacemoney_client::pda;
+
+use common::*;
+
+async fn fetch_withdrawal_queue(context: &mut ProgramTestContext) -> WithdrawalQueue {
+    fetch(context, pda::withdrawal_queue()).await
+}
+
+// Stands in for liquidity that has left the vault, e.g. delegated stake
+async fn set_vault_lamports(context: &mut ProgramTestContext, lamports: u64) {
+    set_lamports(context, pda::platform_state(), lamports).await;
+}
+
+fn queue_withdrawal_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::queue_withdrawal(&StakeAuthority::Depositor(user), None, stake_index)
+}
+
+fn process_queue_ix(destinations: &[Pubkey]) -> Instruction {
+    instructions::process_queue(None, destinations)
+}
+
+fn fund_rewards_ix(funder: Pubkey, amount: u64) -> Instruction {
+    instructions::fund_rewards(&funder, None, amount)
+}
+
+fn withdraw_ix(user: Pubkey, stake_index: u64) -> Instruction {
+    instructions::withdraw_sol(&StakeAuthority::Depositor(user), stake_index, false)
+}
+
+fn deposit_ix(user: Pubkey, amount: u64, tier: u8) -> Instruction {
+    instructions::deposit_sol(&user, amount, tier, None, None, false)
+}
+
+#[tokio::test]
+async fn queued_withdrawals_fill_in_order_as_liquidity_arrives() {
+    let (mut context, payer) = start_initialized(Pubkey::new_unique()).await;
+    let admin = payer.pubkey();
+
+    // No lock and no rewards, so each ticket is just the net principal
+    let add_tier_ix = instructions::add_tier(&admin, "Open", LAMPORTS_PER_SOL, 2, 0, 0);
+    send(&mut context, &[add_tier_ix], &payer).await.unwrap();
+
+    let other = funded_keypair(&mut context, 20 * LAMPORTS_PER_SOL).await;
+    send(&mut context, &[deposit_ix(admin, 10 * LAMPORTS_PER_SOL, 3)], &payer).await.unwrap();
+    send(&mut context, &[deposit_ix(other.pubkey(), 10 * LAMPORTS_PER_SOL, 3)], &other).await.unwrap();
+    let (principal, _) = spacemoney_math::calculate_net_deposit(10 * LAMPORTS_PER_SOL).unwrap();
+
+    // Only one stake's worth stays in the vault
+    let rent = context.banks_client.get_rent().await.unwrap().minimum_balance(PlatformState::LEN);
+    set_vault_lamports(&mut context, rent + principal + LAMPORTS_PER_SOL).await;
+
+    send(&mut context, &[queue_withdrawal_ix(admin, 0)], &payer).await.unwrap();
+    let platform = fetch_platform_state(&mut context).await;
+    assert_eq!(platform.queued_withdrawals_sol, principal);
+    assert_eq!(platform.total_staked_sol, principal);
+
+    // What's left is set aside for the ticket, so a direct withdrawal can't jump the queue
+    let result = send(&mut context, &[withdraw_ix(other.pubkey(), 0)], &other).await;
+    assert_error(result, SpaceMoneyError::InsuranceFundRequired);
+    send(&mut context, &[queue_withdrawal_ix(other.pubkey(), 0)], &other).await.unwrap();
+    let result = send(&mut context, &[queue_withdrawal_ix(other.pubkey(), 0)], &other).await;
+    assert_error(result, SpaceMoneyError::StakeNotFound);
+
+    let queue = fetch_withdrawal_queue(&mut context).await;
+    assert_eq!(queue.tickets.len(), 2);
+    assert_eq!((queue.tickets[0].user, queue.tickets[1].user), (admin, other.pubkey()));
+    assert_eq!(queue.tickets[1].amount().unwrap(), principal);
+
+    // Destinations must follow the queue order
+    let result = send(&mut context, &[process_queue_ix(&[other.pubkey()])], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidQueueDestination);
+
+    // The first ticket fits, the second waits
+    let cranker = funded_keypair(&mut context, LAMPORTS_PER_SOL).await;
+    let balance_before = context.banks_client.get_balance(admin).await.unwrap();
+    send(&mut context, &[process_queue_ix(&[admin, other.pubkey()])], &cranker).await.unwrap();
+    let balance_after = context.banks_client.get_balance(admin).await.unwrap();
+    assert_eq!(balance_after - balance_before, principal);
+
+    let queue = fetch_withdrawal_queue(&mut context).await;
+    assert_eq!(queue.tickets.len(), 1);
+    assert_eq!(queue.tickets[0].user, other.pubkey());
+    assert_eq!(fetch_platform_state(&mut context).await.queued_withdrawals_sol, principal);
+
+    // Anyone can bring in liquidity, which the next crank hands out
+    let result = send(&mut context, &[fund_rewards_ix(admin, 0)], &payer).await;
+    assert_error(result, SpaceMoneyError::InvalidAmount);
+    let treasury_before = fetch_platform_state(&mut context).await.treasury_sol;
+    send(&mut context, &[fund_rewards_ix(admin, 10 * LAMPORTS_PER_SOL)], &payer).await.unwrap();
+
+    let balance_before = context.banks_client.get_balance(other.pubkey()).await.unwrap();
+    send(&mut context, &[process_queue_ix(&[other.pubkey()])], &cranker).await.unwrap();
+    let balance_after = context.banks_client.get_balance(other.pubkey()).await.unwrap();
+    assert_eq!(balance_after - balance_before, principal);
+
+    assert!(fetch_withdrawal_queue(&mut context).await.tickets.is_empty());
+    let platform = fetch_platform_state(&mut context).await;
+    assert_eq!(platform.queued_withdrawals_sol, 0);
+    assert_eq!(platform.total_staked_sol, 0);
+    assert_eq!(platform.treasury_sol, treasury_before);
+}
+
+#[tokio::test]
+async fn unpayable_usdt_tickets_are_skipped_until_redirected() {
+    let (mut context, payer) = start_insured().await;
+    let admin = payer.pubkey();
+
+    let add_tier_ix = instructions::add_tier(&admin, "Open", LAMPORTS_PER_SOL, 2, 0, 0);
+    send(&mut context, &[add_tier_ix], &payer).await.unwrap();
+
+    // Two USDT stakes in the open tier, each queued as soon as it's made
+    let usdt_mint = fetch_platform_state(&mut context).await.usdt_mint;
+    let platform_token_account = create_token_account(&mut context, &usdt_mint, &pda::vault_authority()).await;
+    let other = funded_keypair(&mut context, LAMPORTS_PER_SOL).await;
+    let amount = 2_000_000_000;
+    let mut destinations = Vec::new();
+    for user in [&other, &payer] {
+        let usdt = UsdtAccounts {
+            user_token_account: create_token_account(&mut context, &usdt_mint, &user.pubkey()).await,
+            platform_token_account,
+        };
+        mint_tokens(&mut context, &usdt_mint, &usdt.user_token_account, amount).await;
+        let deposit_ix = instructions::deposit_usdt(&user.pubkey(), &usdt, amount, 3, None, None, false);
+        let queue_ix = instructions::queue_withdrawal(&StakeAuthority::Depositor(user.pubkey()), Some(&usdt), 0);
+        send(&mut context, &[deposit_ix, queue_ix], user).await.unwrap();
+        destinations.push(usdt.user_token_account);
+    }
+    let (principal, _) = spacemoney_math::calculate_net_deposit(amount).unwrap();
+
+    // The first ticket's token account is closed before the crank reaches it
+    let close_ix = spl_token::instruction::close_account(
+        &spl_token::id(),
+        &destinations[0],
+        &other.pubkey(),
+        &other.pubkey(),
+        &[],
+    )
+    .unwrap();
+    send(&mut context, &[close_ix], &other).await.unwrap();
+
+    // The ticket behind it is still paid, and the skipped one keeps its place
+    let process_ix = instructions::process_queue(Some(&platform_token_account), &destinations);
+    send(&mut context, &[process_ix], &payer).await.unwrap();
+    assert_eq!(token_balance(&mut context, destinations[1]).await, principal);
+
+    let queue = fetch_withdrawal_queue(&mut context).await;
+    assert_eq!(queue.tickets.len(), 1);
+    assert_eq!(queue.tickets[0].user, other.pubkey());
+    assert_eq!(fetch_platform_state(&mut context).await.queued_withdrawals_usdt, principal);
+
+    // Only the owner can point the ticket somewhere else
+    let ticket_id = queue.tickets[0].id;
+    let new_destination = create_token_account(&mut context, &usdt_mint, &other.pubkey()).await;
+    let admin_account = create_token_account(&mut context, &usdt_mint, &admin).await;
+    let result = send(&mut context, &[instructions::redirect_ticket(&admin, &admin_account, ticket_id)], &payer).await;
+    assert_error(result, SpaceMoneyError::TicketNotFound);
+    let redirect_ix = instructions::redirect_ticket(&other.pubkey(), &new_destination, ticket_id);
+    send(&mut context, &[redirect_ix], &other).await.unwrap();
+
+    let process_ix = instructions::process_queue(Some(&platform_token_account), &[new_destination]);
+    send(&mut context, &[process_ix], &payer).await.unwrap();
+    assert_eq!(token_balance(&mut context, new_destination).await, principal);
+    assert!(fetch_withdrawal_queue(&mut context).await.tickets.is_empty());
+    assert_eq!(fetch_platform_state(&mut context).await.queued_withdrawals_usdt, 0);
+}